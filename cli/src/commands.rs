@@ -0,0 +1,484 @@
+//! Subcommands and the instructions they send
+//! Inputs are checked with the program's own validation functions before anything is signed,
+//! so a rejected value fails locally with the same error the program would return
+
+use crate::input::ProbabilityTableFile;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anyhow::{anyhow, bail, Result};
+use clap::Subcommand;
+use farm_game::constants::{PAUSE_CLAIM, PAUSE_OPEN, PAUSE_PLANT, PAUSE_PURCHASE};
+use farm_game::state::{Config, RandomnessSource};
+use farm_game::validation::{admin_validation, economic_validation, game_validation};
+use farm_game_client::{accounts, instructions, AccountFetcher, RandomnessAccounts};
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, PartialEq, Subcommand)]
+pub enum Command {
+    // ===== ADMIN =====
+    /// Create the global config (signer becomes admin)
+    InitializeConfig {
+        #[arg(long)]
+        treasury: Pubkey,
+        /// Reward rate in WEED base units per second (program default when omitted)
+        #[arg(long)]
+        base_rate: Option<u64>,
+        /// Halving interval in seconds (program default when omitted)
+        #[arg(long)]
+        halving_interval: Option<i64>,
+        #[arg(long)]
+        protocol_referral_address: Option<Pubkey>,
+    },
+    /// Create the WEED Token 2022 mint
+    CreateRewardMint,
+    /// Create the global statistics account
+    InitializeGlobalStats,
+    /// Create the emission schedule from the config's base rate and halving interval
+    InitializeEmissionSchedule,
+    /// Create the fee pool that receives seed pack payments
+    InitializeFeePool {
+        #[arg(long)]
+        treasury: Pubkey,
+    },
+    /// Create the probability table with the standard 8-seed table
+    InitializeProbabilityTable,
+    /// Create the farm level config with the default levels
+    InitializeFarmLevelConfig,
+    /// Queue a full probability table replacement from a TOML or JSON file (timelocked)
+    UpdateProbabilityTable {
+        #[arg(long)]
+        file: PathBuf,
+        /// Act through the role registry instead of as admin
+        #[arg(long)]
+        role_registry: bool,
+    },
+    /// Apply a queued config change once its timelock has elapsed
+    ExecuteConfigChange {
+        #[arg(long)]
+        change_id: u64,
+        #[arg(long)]
+        role_registry: bool,
+    },
+    /// Reveal a hidden seed type (0-based index)
+    RevealSeed {
+        #[arg(long)]
+        index: u8,
+        #[arg(long)]
+        grow_power: u64,
+        /// Percentage, 0-100
+        #[arg(long)]
+        probability: f32,
+        #[arg(long)]
+        role_registry: bool,
+    },
+    /// Change the values of a revealed seed type (0-based index)
+    UpdateSeedValues {
+        #[arg(long)]
+        index: u8,
+        #[arg(long)]
+        grow_power: u64,
+        #[arg(long)]
+        probability: f32,
+        #[arg(long)]
+        role_registry: bool,
+    },
+    /// Replace the farm levels; one comma-separated entry per level
+    UpdateFarmLevelConfig {
+        #[arg(long, value_delimiter = ',', required = true)]
+        capacities: Vec<u8>,
+        /// Cumulative pack purchases needed for each level
+        #[arg(long, value_delimiter = ',', required = true)]
+        thresholds: Vec<u32>,
+        #[arg(long, value_delimiter = ',')]
+        names: Option<Vec<String>>,
+    },
+    /// Change the WEED transfer fee (whole percentages up to 10%)
+    UpdateTransferFee {
+        #[arg(long)]
+        basis_points: u16,
+        #[arg(long)]
+        maximum_fee: u64,
+    },
+    /// Set the WEED metadata URI or an additional metadata field
+    UpdateTokenMetadata {
+        #[arg(long)]
+        field: String,
+        #[arg(long)]
+        value: String,
+    },
+    /// Pause all user-facing instructions
+    Pause {
+        #[arg(long, default_value_t = 0)]
+        reason: u8,
+        #[arg(long)]
+        role_registry: bool,
+    },
+    /// Resume after a pause
+    Unpause {
+        #[arg(long)]
+        role_registry: bool,
+    },
+    
+    // ===== USER =====
+    /// Create the signer's seed storage
+    InitializeSeedStorage,
+    /// Buy a farm space (pays the farm space cost in SOL to the treasury)
+    BuyFarmSpace,
+    /// Buy seed packs with WEED
+    PurchaseSeedPack {
+        #[arg(long, default_value_t = 1)]
+        quantity: u8,
+        /// Non-zero entropy mixed into the pack (derived from the clock when omitted)
+        #[arg(long)]
+        entropy_seed: Option<u64>,
+        #[arg(long, default_value_t = 0)]
+        max_vrf_fee: u64,
+        /// Switchboard randomness account (oracle randomness source only)
+        #[arg(long)]
+        randomness_account: Option<Pubkey>,
+    },
+    /// Open a purchased seed pack into the seed storage
+    OpenSeedPack {
+        #[arg(long)]
+        pack_id: u64,
+        #[arg(long, default_value_t = 1)]
+        quantity: u8,
+        #[arg(long)]
+        randomness_account: Option<Pubkey>,
+    },
+    /// Plant a stored seed on the farm space
+    PlantSeed {
+        #[arg(long)]
+        seed_id: u64,
+    },
+    /// Remove a planted seed back into storage
+    RemoveSeed {
+        #[arg(long)]
+        seed_id: u64,
+    },
+    /// Claim farming and referral rewards (referrer accounts are resolved automatically)
+    Claim,
+}
+
+/// Convert a program validation error into a CLI error naming the rejected input
+fn check<T>(result: anchor_lang::Result<T>, input: &str) -> Result<T> {
+    result.map_err(|error| anyhow!("invalid {}: {}", input, error))
+}
+
+fn pause_check(config: &Config, flags: u8) -> Result<()> {
+    check(admin_validation::validate_subsystem_not_paused(config, flags), "state")
+}
+
+/// Randomness accounts for `source`; the oracle needs the Switchboard account from the command line
+fn randomness_accounts(
+    source: RandomnessSource,
+    config: &Config,
+    randomness_account: Option<Pubkey>,
+) -> Result<RandomnessAccounts> {
+    Ok(match source {
+        RandomnessSource::SlotHash => RandomnessAccounts::SlotHash,
+        RandomnessSource::AdminBeacon => RandomnessAccounts::AdminBeacon,
+        RandomnessSource::Oracle => RandomnessAccounts::Oracle {
+            randomness_account: randomness_account
+                .ok_or_else(|| anyhow!("--randomness-account is required with the oracle randomness source"))?,
+            oracle_program: config.oracle_program,
+        },
+    })
+}
+
+fn clock_entropy() -> u64 {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos() as u64)
+        .unwrap_or_default();
+    nanos.max(1)
+}
+
+impl Command {
+    /// Validate the inputs and build the instructions, reading on-chain state through `fetcher`
+    pub fn instructions(&self, fetcher: &impl AccountFetcher, signer: &Pubkey) -> Result<Vec<Instruction>> {
+        let ix = match self {
+            Command::InitializeConfig {
+                treasury,
+                base_rate,
+                halving_interval,
+                protocol_referral_address,
+            } => {
+                check(economic_validation::validate_treasury_address(*treasury), "treasury")?;
+                check(
+                    economic_validation::validate_halving_config(
+                        base_rate.unwrap_or(Config::DEFAULT_BASE_RATE),
+                        halving_interval.unwrap_or(Config::DEFAULT_HALVING_INTERVAL),
+                    ),
+                    "base rate or halving interval",
+                )?;
+                instructions::initialize_config(
+                    signer,
+                    *base_rate,
+                    *halving_interval,
+                    *treasury,
+                    *protocol_referral_address,
+                )
+            }
+            Command::CreateRewardMint => instructions::create_reward_mint(signer),
+            Command::InitializeGlobalStats => instructions::initialize_global_stats(signer),
+            Command::InitializeEmissionSchedule => instructions::initialize_emission_schedule(signer),
+            Command::InitializeFeePool { treasury } => {
+                check(economic_validation::validate_treasury_address(*treasury), "treasury")?;
+                instructions::initialize_fee_pool(signer, *treasury)
+            }
+            Command::InitializeProbabilityTable => instructions::initialize_probability_table(signer),
+            Command::InitializeFarmLevelConfig => instructions::initialize_farm_level_config(signer),
+            Command::UpdateProbabilityTable { file, role_registry } => {
+                let change = ProbabilityTableFile::read(file)?.into_change()?;
+                let config = accounts::config(fetcher)?;
+                check(admin_validation::validate_config_change(&config, &change), "probability table")?;
+                instructions::queue_config_change(signer, *role_registry, config.config_change_counter, change)
+            }
+            Command::ExecuteConfigChange { change_id, role_registry } => {
+                let pending = accounts::pending_config_change(fetcher, *change_id)?;
+                instructions::execute_config_change(signer, *role_registry, *change_id, &pending.change)
+            }
+            Command::RevealSeed {
+                index,
+                grow_power,
+                probability,
+                role_registry,
+            } => {
+                check(admin_validation::validate_seed_values(*index, *grow_power, *probability), "seed values")?;
+                if accounts::probability_table(fetcher)?.is_seed_revealed(*index) {
+                    bail!("seed {} is already revealed, use update-seed-values", index);
+                }
+                instructions::reveal_seed(signer, *role_registry, *index, *grow_power, *probability)
+            }
+            Command::UpdateSeedValues {
+                index,
+                grow_power,
+                probability,
+                role_registry,
+            } => {
+                check(admin_validation::validate_seed_values(*index, *grow_power, *probability), "seed values")?;
+                if !accounts::probability_table(fetcher)?.is_seed_revealed(*index) {
+                    bail!("seed {} is not revealed yet, use reveal-seed", index);
+                }
+                instructions::update_seed_values(signer, *role_registry, *index, *grow_power, *probability)
+            }
+            Command::UpdateFarmLevelConfig {
+                capacities,
+                thresholds,
+                names,
+            } => {
+                let max_level = u8::try_from(capacities.len()).unwrap_or(u8::MAX);
+                check(
+                    game_validation::validate_farm_level_config_update(
+                        max_level,
+                        capacities,
+                        thresholds,
+                        names.as_deref(),
+                    ),
+                    "farm levels",
+                )?;
+                instructions::update_farm_level_config(
+                    signer,
+                    max_level,
+                    capacities.clone(),
+                    thresholds.clone(),
+                    names.clone(),
+                )
+            }
+            Command::UpdateTransferFee {
+                basis_points,
+                maximum_fee,
+            } => {
+                check(admin_validation::validate_transfer_fee(*basis_points, *maximum_fee), "transfer fee")?;
+                instructions::update_transfer_fee(signer, *basis_points, *maximum_fee)
+            }
+            Command::UpdateTokenMetadata { field, value } => {
+                check(admin_validation::validate_token_metadata_update(field, value), "metadata")?;
+                instructions::update_token_metadata(signer, field.clone(), value.clone())
+            }
+            Command::Pause { reason, role_registry } => instructions::pause_system(signer, *role_registry, *reason),
+            Command::Unpause { role_registry } => instructions::unpause_system(signer, *role_registry),
+            
+            Command::InitializeSeedStorage => instructions::initialize_seed_storage(signer),
+            Command::BuyFarmSpace => {
+                let config = accounts::config(fetcher)?;
+                check(admin_validation::validate_system_not_paused(&config), "state")?;
+                instructions::buy_farm_space(signer, &config.treasury)
+            }
+            Command::PurchaseSeedPack {
+                quantity,
+                entropy_seed,
+                max_vrf_fee,
+                randomness_account,
+            } => {
+                let entropy_seed = entropy_seed.unwrap_or_else(clock_entropy);
+                check(economic_validation::validate_seed_pack_quantity(*quantity), "quantity")?;
+                check(game_validation::validate_user_entropy_seed(entropy_seed), "entropy seed")?;
+                
+                let config = accounts::config(fetcher)?;
+                pause_check(&config, PAUSE_PURCHASE)?;
+                let randomness = randomness_accounts(config.randomness_source, &config, *randomness_account)?;
+                let has_farm_space = accounts::farm_space(fetcher, signer)?.is_some();
+                instructions::purchase_seed_pack(
+                    signer,
+                    config.seed_pack_counter,
+                    has_farm_space,
+                    &randomness,
+                    *quantity,
+                    entropy_seed,
+                    *max_vrf_fee,
+                )
+            }
+            Command::OpenSeedPack {
+                pack_id,
+                quantity,
+                randomness_account,
+            } => {
+                check(economic_validation::validate_seed_pack_quantity(*quantity), "quantity")?;
+                let config = accounts::config(fetcher)?;
+                pause_check(&config, PAUSE_OPEN)?;
+                let pack = accounts::seed_pack(fetcher, signer, *pack_id)?;
+                if pack.is_opened {
+                    bail!("seed pack {} is already opened", pack_id);
+                }
+                let randomness = randomness_accounts(pack.randomness_source, &config, *randomness_account)?;
+                instructions::open_seed_pack(signer, *pack_id, &randomness, *quantity)
+            }
+            Command::PlantSeed { seed_id } => {
+                pause_check(&accounts::config(fetcher)?, PAUSE_PLANT)?;
+                instructions::plant_seed(signer, *seed_id)
+            }
+            Command::RemoveSeed { seed_id } => instructions::remove_seed(signer, *seed_id),
+            Command::Claim => {
+                pause_check(&accounts::config(fetcher)?, PAUSE_CLAIM)?;
+                let referrers = accounts::claim_referrers(fetcher, signer)?;
+                let fee_funded = accounts::global_stats(fetcher)?.supply_exhausted;
+                instructions::claim_reward_with_referral_rewards(signer, &referrers, fee_funded)
+            }
+        };
+        Ok(vec![ix])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use farm_game::state::ProbabilityTable;
+    use farm_game_client::pda;
+    use anchor_lang::{AccountDeserialize, AccountSerialize};
+    use std::collections::HashMap;
+    
+    fn zeroed<T: AccountDeserialize>() -> T {
+        T::try_deserialize_unchecked(&mut &[0u8; 4096][..]).unwrap()
+    }
+    
+    fn insert<T: AccountSerialize>(accounts: &mut HashMap<Pubkey, Vec<u8>>, address: Pubkey, account: &T) {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        accounts.insert(address, data);
+    }
+    
+    #[test]
+    fn test_inputs_are_validated_before_building() {
+        let accounts = HashMap::new();
+        let admin = Pubkey::new_unique();
+        
+        // Rejected locally without touching chain state
+        let bad_levels = Command::UpdateFarmLevelConfig {
+            capacities: vec![4, 6, 6],
+            thresholds: vec![0, 30, 100],
+            names: None,
+        };
+        let error = bad_levels.instructions(&accounts, &admin).unwrap_err();
+        assert!(error.to_string().contains("invalid farm levels"));
+        
+        let bad_fee = Command::UpdateTransferFee {
+            basis_points: 1_500,
+            maximum_fee: 1,
+        };
+        assert!(bad_fee.instructions(&accounts, &admin).is_err());
+        
+        let bad_seed = Command::RevealSeed {
+            index: 16,
+            grow_power: 100,
+            probability: 1.0,
+            role_registry: false,
+        };
+        assert!(bad_seed.instructions(&accounts, &admin).is_err());
+        
+        let zero_treasury = Command::InitializeConfig {
+            treasury: Pubkey::default(),
+            base_rate: None,
+            halving_interval: None,
+            protocol_referral_address: None,
+        };
+        assert!(zero_treasury.instructions(&accounts, &admin).is_err());
+        
+        // Valid input builds one instruction for the signer
+        let levels = Command::UpdateFarmLevelConfig {
+            capacities: vec![4, 6, 10],
+            thresholds: vec![0, 30, 100],
+            names: None,
+        };
+        let ixs = levels.instructions(&accounts, &admin).unwrap();
+        assert_eq!(ixs.len(), 1);
+        assert!(ixs[0].accounts.iter().any(|meta| meta.pubkey == admin && meta.is_signer));
+    }
+    
+    #[test]
+    fn test_reveal_checks_table_state() {
+        let mut accounts = HashMap::new();
+        let admin = Pubkey::new_unique();
+        insert(&mut accounts, pda::probability_table(), &ProbabilityTable::init_table_1());
+        
+        // Seeds 0-7 of the standard table are revealed, 8-15 are hidden
+        let reveal = |index| Command::RevealSeed {
+            index,
+            grow_power: 60_000,
+            probability: 1.0,
+            role_registry: false,
+        };
+        assert!(reveal(0).instructions(&accounts, &admin).is_err());
+        assert!(reveal(8).instructions(&accounts, &admin).is_ok());
+        
+        let update = Command::UpdateSeedValues {
+            index: 8,
+            grow_power: 60_000,
+            probability: 1.0,
+            role_registry: false,
+        };
+        assert!(update.instructions(&accounts, &admin).is_err());
+    }
+    
+    #[test]
+    fn test_purchase_reads_config() {
+        let mut accounts = HashMap::new();
+        let user = Pubkey::new_unique();
+        let mut config: Config = zeroed();
+        config.seed_pack_counter = 7;
+        insert(&mut accounts, pda::config(), &config);
+        
+        let purchase = |randomness_account| Command::PurchaseSeedPack {
+            quantity: 2,
+            entropy_seed: Some(42),
+            max_vrf_fee: 0,
+            randomness_account,
+        };
+        let ixs = purchase(None).instructions(&accounts, &user).unwrap();
+        assert!(ixs[0].accounts.iter().any(|meta| meta.pubkey == pda::seed_pack(&user, 7)));
+        
+        // The oracle source needs the randomness account
+        config.randomness_source = RandomnessSource::Oracle;
+        insert(&mut accounts, pda::config(), &config);
+        assert!(purchase(None).instructions(&accounts, &user).is_err());
+        let randomness_account = Pubkey::new_unique();
+        let ixs = purchase(Some(randomness_account)).instructions(&accounts, &user).unwrap();
+        assert!(ixs[0].accounts.iter().any(|meta| meta.pubkey == randomness_account));
+        
+        // Paused purchases fail before signing
+        config.paused_subsystems = PAUSE_PURCHASE;
+        insert(&mut accounts, pda::config(), &config);
+        assert!(purchase(Some(randomness_account)).instructions(&accounts, &user).is_err());
+    }
+}
@@ -0,0 +1,163 @@
+//! File inputs: signer keypairs and probability tables
+//! Probability tables are read from TOML or JSON, chosen by file extension
+
+use anyhow::{anyhow, bail, Context, Result};
+use farm_game::state::ConfigChange;
+use serde::Deserialize;
+use solana_keypair::Keypair;
+use std::path::{Path, PathBuf};
+
+/// Expand a leading `~/` to the home directory
+pub fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), std::env::var_os("HOME")) {
+        (Ok(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Load a keypair file in the Solana CLI format (JSON array of 64 bytes)
+pub fn load_keypair(path: &Path) -> Result<Keypair> {
+    let path = expand_home(path);
+    solana_keypair::read_keypair_file(&path)
+        .map_err(|error| anyhow!("failed to read keypair {}: {}", path.display(), error))
+}
+
+/// Full probability table replacement, as written by operators
+/// `seed_count` is taken from the number of entries; all arrays must have the same length
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProbabilityTableFile {
+    pub version: u32,
+    pub name: String,
+    pub grow_powers: Vec<u64>,
+    /// Cumulative thresholds out of 10000, strictly ascending and ending at 10000
+    pub probability_thresholds: Vec<u16>,
+    pub probability_percentages: Vec<f32>,
+    /// Expected grow power of a pack with this table
+    pub expected_value: u64,
+}
+
+impl ProbabilityTableFile {
+    /// Parse TOML or JSON depending on the extension of `path`
+    pub fn parse(path: &Path, contents: &str) -> Result<Self> {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("toml") => toml::from_str(contents).context("invalid TOML probability table"),
+            Some("json") => serde_json::from_str(contents).context("invalid JSON probability table"),
+            _ => bail!("probability table must be a .toml or .json file: {}", path.display()),
+        }
+    }
+    
+    pub fn read(path: &Path) -> Result<Self> {
+        let contents =
+            std::fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+        Self::parse(path, &contents)
+    }
+    
+    /// Timelocked change replacing the on-chain table
+    pub fn into_change(self) -> Result<ConfigChange> {
+        let count = self.grow_powers.len();
+        if count == 0 || count > 16 {
+            bail!("probability table must have 1-16 seeds, got {}", count);
+        }
+        if self.probability_thresholds.len() != count || self.probability_percentages.len() != count {
+            bail!("grow_powers, probability_thresholds and probability_percentages must have the same length");
+        }
+        if self.name.len() > 32 {
+            bail!("probability table name is longer than 32 bytes");
+        }
+        
+        let mut grow_powers = [0u64; 16];
+        let mut probability_thresholds = [0u16; 16];
+        let mut probability_percentages = [0f32; 16];
+        let mut name = [0u8; 32];
+        grow_powers[..count].copy_from_slice(&self.grow_powers);
+        probability_thresholds[..count].copy_from_slice(&self.probability_thresholds);
+        probability_percentages[..count].copy_from_slice(&self.probability_percentages);
+        name[..self.name.len()].copy_from_slice(self.name.as_bytes());
+        
+        Ok(ConfigChange::ProbabilityTable {
+            version: self.version,
+            seed_count: count as u8,
+            grow_powers,
+            probability_thresholds,
+            probability_percentages,
+            expected_value: self.expected_value,
+            name,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use farm_game::validation::admin_validation::validate_probability_table_update;
+    
+    const TOML_TABLE: &str = r#"
+version = 2
+name = "SpringEvent"
+grow_powers = [100, 180, 420]
+probability_thresholds = [5000, 8000, 10000]
+probability_percentages = [50.0, 30.0, 20.0]
+expected_value = 188
+"#;
+
+    const JSON_TABLE: &str = r#"{
+  "version": 2,
+  "name": "SpringEvent",
+  "grow_powers": [100, 180, 420],
+  "probability_thresholds": [5000, 8000, 10000],
+  "probability_percentages": [50.0, 30.0, 20.0],
+  "expected_value": 188
+}"#;
+
+    #[test]
+    fn test_probability_table_formats() {
+        let from_toml = ProbabilityTableFile::parse(Path::new("table.toml"), TOML_TABLE).unwrap();
+        let from_json = ProbabilityTableFile::parse(Path::new("table.json"), JSON_TABLE).unwrap();
+        assert_eq!(from_toml, from_json);
+        assert!(ProbabilityTableFile::parse(Path::new("table.yaml"), TOML_TABLE).is_err());
+        assert!(ProbabilityTableFile::parse(Path::new("table.json"), TOML_TABLE).is_err());
+        
+        let ConfigChange::ProbabilityTable {
+            seed_count,
+            grow_powers,
+            probability_thresholds,
+            probability_percentages,
+            name,
+            ..
+        } = from_toml.into_change().unwrap()
+        else {
+            panic!("expected a probability table change");
+        };
+        assert_eq!(seed_count, 3);
+        assert_eq!(&grow_powers[..4], &[100, 180, 420, 0]);
+        assert_eq!(&name[..11], b"SpringEvent");
+        validate_probability_table_update(seed_count, &grow_powers, &probability_thresholds, &probability_percentages)
+            .unwrap();
+    }
+    
+    #[test]
+    fn test_probability_table_shape_checks() {
+        let table = ProbabilityTableFile::parse(Path::new("table.toml"), TOML_TABLE).unwrap();
+        
+        let mismatched = ProbabilityTableFile {
+            probability_percentages: vec![50.0, 50.0],
+            ..table.clone()
+        };
+        assert!(mismatched.into_change().is_err());
+        
+        let empty = ProbabilityTableFile {
+            grow_powers: vec![],
+            probability_thresholds: vec![],
+            probability_percentages: vec![],
+            ..table.clone()
+        };
+        assert!(empty.into_change().is_err());
+        
+        let long_name = ProbabilityTableFile {
+            name: "x".repeat(33),
+            ..table
+        };
+        assert!(long_name.into_change().is_err());
+    }
+}
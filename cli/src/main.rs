@@ -0,0 +1,101 @@
+//! Operator CLI for the farm_game program
+//! Wraps admin and user instructions: loads the signer from a keypair file, validates inputs
+//! with the program's validation functions, then sends or (with --dry-run) only simulates
+
+mod commands;
+mod input;
+mod rpc;
+
+use anyhow::{bail, Result};
+use clap::Parser;
+use commands::Command;
+use rpc::RpcClient;
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+use std::path::PathBuf;
+
+#[derive(Debug, Parser)]
+#[command(name = "farm-game-cli", version, about = "Admin and user operations for farm_game")]
+struct Cli {
+    /// JSON-RPC endpoint
+    #[arg(long, env = "FARM_GAME_RPC_URL", default_value = "http://127.0.0.1:8899", global = true)]
+    url: String,
+    /// Signer and fee payer keypair file
+    #[arg(long, default_value = "~/.config/solana/id.json", global = true)]
+    keypair: PathBuf,
+    /// Simulate the transaction and print its logs without sending it
+    #[arg(long, global = true)]
+    dry_run: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+fn run(cli: Cli) -> Result<()> {
+    let signer = input::load_keypair(&cli.keypair)?;
+    let rpc = RpcClient::new(cli.url);
+    
+    let instructions = cli.command.instructions(&rpc, &signer.pubkey())?;
+    let blockhash = rpc.latest_blockhash()?;
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&signer.pubkey()), &[&signer], blockhash);
+    
+    if cli.dry_run {
+        let simulation = rpc.simulate(&transaction)?;
+        for log in &simulation.logs {
+            println!("{}", log);
+        }
+        if let Some(err) = simulation.err {
+            bail!("simulation failed: {}", err);
+        }
+        println!(
+            "simulation succeeded ({} compute units), nothing was sent",
+            simulation.units_consumed.unwrap_or_default()
+        );
+        return Ok(());
+    }
+    
+    let signature = rpc.send_and_confirm(&transaction)?;
+    println!("{}", signature);
+    Ok(())
+}
+
+fn main() {
+    if let Err(error) = run(Cli::parse()) {
+        eprintln!("error: {:#}", error);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+    
+    #[test]
+    fn test_cli_arguments() {
+        Cli::command().debug_assert();
+        
+        let cli = Cli::try_parse_from([
+            "farm-game-cli",
+            "update-farm-level-config",
+            "--capacities",
+            "4,6,10",
+            "--thresholds",
+            "0,30,100",
+            "--dry-run",
+        ])
+        .unwrap();
+        assert!(cli.dry_run);
+        assert_eq!(
+            cli.command,
+            Command::UpdateFarmLevelConfig {
+                capacities: vec![4, 6, 10],
+                thresholds: vec![0, 30, 100],
+                names: None,
+            }
+        );
+        
+        // Malformed pubkeys are rejected by the parser
+        assert!(Cli::try_parse_from(["farm-game-cli", "initialize-fee-pool", "--treasury", "nope"]).is_err());
+    }
+}
@@ -0,0 +1,129 @@
+//! Minimal JSON-RPC client
+//! Covers only what the CLI needs: account reads, blockhash, simulate and send
+
+use anyhow::{anyhow, bail, Context, Result};
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::hash::Hash;
+use base64::Engine;
+use farm_game_client::{AccountFetcher, ClientError};
+use serde_json::{json, Value};
+use solana_transaction::Transaction;
+use std::time::{Duration, Instant};
+
+/// How long `send_and_confirm` waits for confirmation
+const CONFIRM_TIMEOUT: Duration = Duration::from_secs(60);
+const CONFIRM_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Result of `simulateTransaction`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Simulation {
+    /// Transaction error, `None` when the simulation succeeded
+    pub err: Option<Value>,
+    pub logs: Vec<String>,
+    pub units_consumed: Option<u64>,
+}
+
+pub struct RpcClient {
+    url: String,
+    agent: ureq::Agent,
+}
+
+impl RpcClient {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            agent: ureq::AgentBuilder::new().timeout(Duration::from_secs(30)).build(),
+        }
+    }
+    
+    fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let request = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let mut response: Value = self
+            .agent
+            .post(&self.url)
+            .send_json(request)
+            .with_context(|| format!("{} request to {} failed", method, self.url))?
+            .into_json()
+            .with_context(|| format!("invalid {} response", method))?;
+        
+        if let Some(error) = response.get("error") {
+            bail!("{} failed: {}", method, error);
+        }
+        Ok(response["result"].take())
+    }
+    
+    pub fn get_account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>> {
+        let result = self.call(
+            "getAccountInfo",
+            json!([address.to_string(), { "encoding": "base64", "commitment": "confirmed" }]),
+        )?;
+        if result["value"].is_null() {
+            return Ok(None);
+        }
+        let data = result["value"]["data"][0]
+            .as_str()
+            .ok_or_else(|| anyhow!("getAccountInfo returned no data for {}", address))?;
+        Ok(Some(base64::engine::general_purpose::STANDARD.decode(data)?))
+    }
+    
+    pub fn latest_blockhash(&self) -> Result<Hash> {
+        let result = self.call("getLatestBlockhash", json!([{ "commitment": "confirmed" }]))?;
+        let blockhash = result["value"]["blockhash"]
+            .as_str()
+            .ok_or_else(|| anyhow!("getLatestBlockhash returned no blockhash"))?;
+        blockhash.parse().map_err(|_| anyhow!("invalid blockhash {}", blockhash))
+    }
+    
+    pub fn simulate(&self, transaction: &Transaction) -> Result<Simulation> {
+        let result = self.call(
+            "simulateTransaction",
+            json!([encode(transaction)?, { "encoding": "base64", "commitment": "confirmed", "sigVerify": true }]),
+        )?;
+        let value = &result["value"];
+        Ok(Simulation {
+            err: Some(value["err"].clone()).filter(|err| !err.is_null()),
+            logs: value["logs"]
+                .as_array()
+                .map(|logs| logs.iter().filter_map(|log| log.as_str().map(str::to_string)).collect())
+                .unwrap_or_default(),
+            units_consumed: value["unitsConsumed"].as_u64(),
+        })
+    }
+    
+    /// Send and wait for `confirmed`, returning the signature
+    pub fn send_and_confirm(&self, transaction: &Transaction) -> Result<String> {
+        let signature = self
+            .call("sendTransaction", json!([encode(transaction)?, { "encoding": "base64" }]))?
+            .as_str()
+            .ok_or_else(|| anyhow!("sendTransaction returned no signature"))?
+            .to_string();
+        
+        let started = Instant::now();
+        while started.elapsed() < CONFIRM_TIMEOUT {
+            let result = self.call("getSignatureStatuses", json!([[signature]]))?;
+            let status = &result["value"][0];
+            if !status.is_null() {
+                if !status["err"].is_null() {
+                    bail!("transaction {} failed: {}", signature, status["err"]);
+                }
+                if matches!(status["confirmationStatus"].as_str(), Some("confirmed" | "finalized")) {
+                    return Ok(signature);
+                }
+            }
+            std::thread::sleep(CONFIRM_POLL_INTERVAL);
+        }
+        bail!("transaction {} was not confirmed within {:?}", signature, CONFIRM_TIMEOUT)
+    }
+}
+
+impl AccountFetcher for RpcClient {
+    fn fetch_account_data(&self, address: &Pubkey) -> farm_game_client::accounts::Result<Option<Vec<u8>>> {
+        self.get_account_data(address)
+            .map_err(|error| ClientError::Fetch(format!("{:#}", error)))
+    }
+}
+
+fn encode(transaction: &Transaction) -> Result<String> {
+    let bytes = bincode::serialize(transaction).context("failed to serialize transaction")?;
+    Ok(base64::engine::general_purpose::STANDARD.encode(bytes))
+}
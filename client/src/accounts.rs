@@ -0,0 +1,260 @@
+//! Account fetch and decode helpers
+//! Fetching is abstracted behind `AccountFetcher`, so any RPC client (or a test fixture)
+//! can back the typed accessors
+
+use crate::instructions::ClaimReferrers;
+use crate::pda;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AccountDeserialize, AnchorDeserialize};
+use farm_game::state::{
+    Config, EmissionSchedule, FarmLevelConfig, FarmSpace, FeePool, GlobalStats, InviteCode, Multisig,
+    PendingConfigChange, ProbabilityTable, Proposal, RandomnessBeacon, RoleRegistry, Seed, SeedPack, SeedStorage,
+    UserState,
+};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Errors raised while fetching or decoding accounts
+#[derive(Debug)]
+pub enum ClientError {
+    /// The account does not exist
+    AccountNotFound(Pubkey),
+    /// The account data does not decode as the expected type
+    InvalidAccountData { address: Pubkey, reason: String },
+    /// The backing fetcher failed (RPC error, I/O, ...)
+    Fetch(String),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::AccountNotFound(address) => write!(f, "account {} not found", address),
+            ClientError::InvalidAccountData { address, reason } => {
+                write!(f, "account {} has invalid data: {}", address, reason)
+            }
+            ClientError::Fetch(reason) => write!(f, "fetch failed: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+pub type Result<T> = std::result::Result<T, ClientError>;
+
+/// Source of raw account data
+pub trait AccountFetcher {
+    /// Account data, or `None` when the account does not exist
+    fn fetch_account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>>;
+}
+
+impl AccountFetcher for HashMap<Pubkey, Vec<u8>> {
+    fn fetch_account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>> {
+        Ok(self.get(address).cloned())
+    }
+}
+
+/// Decode account data, checking the account discriminator
+pub fn decode<T: AccountDeserialize>(address: &Pubkey, data: &[u8]) -> Result<T> {
+    T::try_deserialize(&mut &data[..]).map_err(|error| ClientError::InvalidAccountData {
+        address: *address,
+        reason: error.to_string(),
+    })
+}
+
+/// Decode the return data of a view instruction (`simulate_claim`, `get_farm_level_info`)
+pub fn decode_return<T: AnchorDeserialize>(data: &[u8]) -> Result<T> {
+    T::try_from_slice(data).map_err(|error| ClientError::Fetch(format!("invalid return data: {}", error)))
+}
+
+/// Fetch and decode an account, `None` when it does not exist
+pub fn fetch_optional<T: AccountDeserialize>(fetcher: &impl AccountFetcher, address: &Pubkey) -> Result<Option<T>> {
+    fetcher
+        .fetch_account_data(address)?
+        .map(|data| decode(address, &data))
+        .transpose()
+}
+
+/// Fetch and decode an account that must exist
+pub fn fetch<T: AccountDeserialize>(fetcher: &impl AccountFetcher, address: &Pubkey) -> Result<T> {
+    fetch_optional(fetcher, address)?.ok_or(ClientError::AccountNotFound(*address))
+}
+
+// ===== GLOBAL ACCOUNTS =====
+
+pub fn config(fetcher: &impl AccountFetcher) -> Result<Config> {
+    fetch(fetcher, &pda::config())
+}
+
+pub fn global_stats(fetcher: &impl AccountFetcher) -> Result<GlobalStats> {
+    fetch(fetcher, &pda::global_stats())
+}
+
+pub fn fee_pool(fetcher: &impl AccountFetcher) -> Result<FeePool> {
+    fetch(fetcher, &pda::fee_pool())
+}
+
+pub fn probability_table(fetcher: &impl AccountFetcher) -> Result<ProbabilityTable> {
+    fetch(fetcher, &pda::probability_table())
+}
+
+pub fn farm_level_config(fetcher: &impl AccountFetcher) -> Result<FarmLevelConfig> {
+    fetch(fetcher, &pda::farm_level_config())
+}
+
+pub fn emission_schedule(fetcher: &impl AccountFetcher) -> Result<EmissionSchedule> {
+    fetch(fetcher, &pda::emission_schedule())
+}
+
+/// `None` until `initialize_role_registry` has run
+pub fn role_registry(fetcher: &impl AccountFetcher) -> Result<Option<RoleRegistry>> {
+    fetch_optional(fetcher, &pda::role_registry())
+}
+
+pub fn randomness_beacon(fetcher: &impl AccountFetcher) -> Result<RandomnessBeacon> {
+    fetch(fetcher, &pda::randomness_beacon())
+}
+
+pub fn multisig(fetcher: &impl AccountFetcher) -> Result<Multisig> {
+    fetch(fetcher, &pda::multisig())
+}
+
+pub fn proposal(fetcher: &impl AccountFetcher, proposal_id: u64) -> Result<Proposal> {
+    fetch(fetcher, &pda::proposal(proposal_id))
+}
+
+pub fn pending_config_change(fetcher: &impl AccountFetcher, change_id: u64) -> Result<PendingConfigChange> {
+    fetch(fetcher, &pda::pending_config_change(change_id))
+}
+
+// ===== USER ACCOUNTS =====
+
+/// `None` until the user has been initialized
+pub fn user_state(fetcher: &impl AccountFetcher, user: &Pubkey) -> Result<Option<UserState>> {
+    fetch_optional(fetcher, &pda::user_state(user))
+}
+
+/// `None` until the user has bought a farm space
+pub fn farm_space(fetcher: &impl AccountFetcher, user: &Pubkey) -> Result<Option<FarmSpace>> {
+    fetch_optional(fetcher, &pda::farm_space(user))
+}
+
+/// `None` until the user has initialized seed storage
+pub fn seed_storage(fetcher: &impl AccountFetcher, user: &Pubkey) -> Result<Option<SeedStorage>> {
+    fetch_optional(fetcher, &pda::seed_storage(user))
+}
+
+/// `None` once the seed has been discarded
+pub fn seed(fetcher: &impl AccountFetcher, user: &Pubkey, seed_id: u64) -> Result<Option<Seed>> {
+    fetch_optional(fetcher, &pda::seed(user, seed_id))
+}
+
+pub fn seed_pack(fetcher: &impl AccountFetcher, user: &Pubkey, pack_id: u64) -> Result<SeedPack> {
+    fetch(fetcher, &pda::seed_pack(user, pack_id))
+}
+
+pub fn invite_code(fetcher: &impl AccountFetcher, code: &[u8; 12]) -> Result<Option<InviteCode>> {
+    fetch_optional(fetcher, &pda::invite_code(code))
+}
+
+/// Resolve the referrer accounts of `user`'s claim from the on-chain referral chain
+pub fn claim_referrers(fetcher: &impl AccountFetcher, user: &Pubkey) -> Result<ClaimReferrers> {
+    let config = config(fetcher)?;
+    let state = user_state(fetcher, user)?.ok_or(ClientError::AccountNotFound(pda::user_state(user)))?;
+    let level1 = state.referrer;
+    
+    // The protocol address ends the chain: it has no UserState and thus no referrer of its own.
+    // Neither does a self-referral, whose referrer earns no share
+    let level2 = match level1 {
+        Some(referrer) if referrer != config.protocol_referral_address && referrer != *user => {
+            user_state(fetcher, &referrer)?.and_then(|referrer_state| referrer_state.referrer)
+        }
+        _ => None,
+    };
+    
+    Ok(ClaimReferrers::new(user, level1, level2, &config.protocol_referral_address))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::AccountSerialize;
+    
+    /// All-zero account, for fixtures of the types without `Default`
+    fn zeroed<T: AccountDeserialize>() -> T {
+        T::try_deserialize_unchecked(&mut &[0u8; 4096][..]).unwrap()
+    }
+    
+    fn user_state_for(owner: Pubkey, referrer: Option<Pubkey>) -> UserState {
+        UserState {
+            owner,
+            referrer,
+            ..zeroed()
+        }
+    }
+    
+    fn insert<T: AccountSerialize>(accounts: &mut HashMap<Pubkey, Vec<u8>>, address: Pubkey, account: &T) {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        accounts.insert(address, data);
+    }
+    
+    #[test]
+    fn test_fetch_and_decode() {
+        let mut accounts = HashMap::new();
+        let user = Pubkey::new_unique();
+        insert(&mut accounts, pda::user_state(&user), &user_state_for(user, None));
+        
+        assert_eq!(user_state(&accounts, &user).unwrap().unwrap().owner, user);
+        assert!(user_state(&accounts, &Pubkey::new_unique()).unwrap().is_none());
+        assert!(matches!(config(&accounts), Err(ClientError::AccountNotFound(_))));
+        
+        // Data of another account type is rejected by its discriminator
+        let data = accounts[&pda::user_state(&user)].clone();
+        accounts.insert(pda::farm_space(&user), data);
+        assert!(matches!(
+            farm_space(&accounts, &user),
+            Err(ClientError::InvalidAccountData { .. })
+        ));
+    }
+    
+    #[test]
+    fn test_claim_referrers_follow_referral_chain() {
+        let protocol = Pubkey::new_unique();
+        let (alice, bob, carol) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        
+        let mut accounts = HashMap::new();
+        let config = Config {
+            protocol_referral_address: protocol,
+            ..zeroed()
+        };
+        insert(&mut accounts, pda::config(), &config);
+        // carol <- bob <- alice <- protocol
+        insert(&mut accounts, pda::user_state(&alice), &user_state_for(alice, Some(protocol)));
+        insert(&mut accounts, pda::user_state(&bob), &user_state_for(bob, Some(alice)));
+        insert(&mut accounts, pda::user_state(&carol), &user_state_for(carol, Some(bob)));
+        
+        let referrers = claim_referrers(&accounts, &carol).unwrap();
+        assert_eq!(referrers.level1.unwrap().user_state, Some(pda::user_state(&bob)));
+        assert_eq!(referrers.level2.unwrap().user_state, Some(pda::user_state(&alice)));
+        
+        // The protocol address is passed without a UserState
+        let referrers = claim_referrers(&accounts, &bob).unwrap();
+        assert_eq!(referrers.level2.unwrap().referrer, protocol);
+        assert_eq!(referrers.level2.unwrap().user_state, None);
+        
+        let referrers = claim_referrers(&accounts, &alice).unwrap();
+        assert_eq!(referrers.level1.unwrap().user_state, None);
+        assert!(referrers.level2.is_none());
+        
+        // Self-referrers and cycles back to the claimant are left out, as the program expects
+        let dave = Pubkey::new_unique();
+        insert(&mut accounts, pda::user_state(&dave), &user_state_for(dave, Some(dave)));
+        assert_eq!(claim_referrers(&accounts, &dave).unwrap(), ClaimReferrers::default());
+        let erin = Pubkey::new_unique();
+        insert(&mut accounts, pda::user_state(&erin), &user_state_for(erin, Some(dave)));
+        insert(&mut accounts, pda::user_state(&dave), &user_state_for(dave, Some(erin)));
+        let referrers = claim_referrers(&accounts, &erin).unwrap();
+        assert_eq!(referrers.level1.unwrap().user_state, Some(pda::user_state(&dave)));
+        assert!(referrers.level2.is_none());
+    }
+}
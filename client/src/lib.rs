@@ -0,0 +1,10 @@
+//! Typed Rust client for the farm_game program
+//! PDA helpers, instruction builders for every entry point and account fetch/decode helpers,
+//! all built on the program crate's own account and instruction types
+
+pub mod accounts;
+pub mod instructions;
+pub mod pda;
+
+pub use accounts::{AccountFetcher, ClientError};
+pub use instructions::{ClaimReferrers, RandomnessAccounts, ReferrerAccounts};
@@ -0,0 +1,149 @@
+//! PDA derivation for every farm_game account
+//! Seeds come from `farm_game::constants::seeds`, so clients never repeat the string literals
+
+use anchor_lang::prelude::Pubkey;
+use farm_game::constants::seeds;
+use farm_game::utils::{generate_invite_code_hash, get_fixed_salt};
+
+fn find(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &farm_game::ID).0
+}
+
+/// ["config"]
+pub fn config() -> Pubkey {
+    find(&[seeds::CONFIG])
+}
+
+/// ["global_stats"]
+pub fn global_stats() -> Pubkey {
+    find(&[seeds::GLOBAL_STATS])
+}
+
+/// ["fee_pool"], also the vault authority for fee-funded claims
+pub fn fee_pool() -> Pubkey {
+    find(&[seeds::FEE_POOL])
+}
+
+/// ["reward_mint"]
+pub fn reward_mint() -> Pubkey {
+    find(&[seeds::REWARD_MINT])
+}
+
+/// ["mint_authority"], also the transfer fee and withheld withdraw authority
+pub fn mint_authority() -> Pubkey {
+    find(&[seeds::MINT_AUTHORITY])
+}
+
+/// ["probability_table"]
+pub fn probability_table() -> Pubkey {
+    find(&[seeds::PROBABILITY_TABLE])
+}
+
+/// ["farm_level_config"]
+pub fn farm_level_config() -> Pubkey {
+    find(&[seeds::FARM_LEVEL_CONFIG])
+}
+
+/// ["emission_schedule"]
+pub fn emission_schedule() -> Pubkey {
+    find(&[seeds::EMISSION_SCHEDULE])
+}
+
+/// ["role_registry"]
+pub fn role_registry() -> Pubkey {
+    find(&[seeds::ROLE_REGISTRY])
+}
+
+/// ["randomness_beacon"]
+pub fn randomness_beacon() -> Pubkey {
+    find(&[seeds::RANDOMNESS_BEACON])
+}
+
+/// ["multisig"]
+pub fn multisig() -> Pubkey {
+    find(&[seeds::MULTISIG])
+}
+
+/// ["multisig_signer"], becomes `Config.admin` once governance is handed over
+pub fn multisig_signer() -> Pubkey {
+    find(&[seeds::MULTISIG_SIGNER])
+}
+
+/// ["proposal", proposal_id]
+pub fn proposal(proposal_id: u64) -> Pubkey {
+    find(&[seeds::PROPOSAL, &proposal_id.to_le_bytes()])
+}
+
+/// ["pending_config_change", change_id]
+pub fn pending_config_change(change_id: u64) -> Pubkey {
+    find(&[seeds::PENDING_CONFIG_CHANGE, &change_id.to_le_bytes()])
+}
+
+/// ["user", user]
+pub fn user_state(user: &Pubkey) -> Pubkey {
+    find(&[seeds::USER, user.as_ref()])
+}
+
+/// ["farm_space", user]
+pub fn farm_space(user: &Pubkey) -> Pubkey {
+    find(&[seeds::FARM_SPACE, user.as_ref()])
+}
+
+/// ["seed_storage", user]
+pub fn seed_storage(user: &Pubkey) -> Pubkey {
+    find(&[seeds::SEED_STORAGE, user.as_ref()])
+}
+
+/// ["seed", user, seed_id]
+/// Seed ids are per user: every farm's initial seed has id 0
+pub fn seed(user: &Pubkey, seed_id: u64) -> Pubkey {
+    find(&[seeds::SEED, user.as_ref(), &seed_id.to_le_bytes()])
+}
+
+/// ["seed_pack", user, pack_id]
+/// A new pack uses the current `Config.seed_pack_counter` as its id
+pub fn seed_pack(user: &Pubkey, pack_id: u64) -> Pubkey {
+    find(&[seeds::SEED_PACK, user.as_ref(), &pack_id.to_le_bytes()])
+}
+
+/// ["invite_code", hash(code || fixed salt)]
+pub fn invite_code(code: &[u8; 12]) -> Pubkey {
+    let code_hash = generate_invite_code_hash(code, &get_fixed_salt());
+    find(&[seeds::INVITE_CODE, &code_hash])
+}
+
+/// WEED associated token account of `owner` (Token 2022)
+pub fn reward_token_account(owner: &Pubkey) -> Pubkey {
+    anchor_spl::associated_token::get_associated_token_address_with_program_id(
+        owner,
+        &reward_mint(),
+        &anchor_spl::token_2022::ID,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_pdas_match_program_seeds() {
+        let user = Pubkey::new_unique();
+        
+        // Same derivation as the #[account(seeds = ...)] constraints
+        assert_eq!(config(), Pubkey::find_program_address(&[b"config"], &farm_game::ID).0);
+        assert_eq!(user_state(&user), Pubkey::find_program_address(&[b"user", user.as_ref()], &farm_game::ID).0);
+        assert_eq!(
+            seed_pack(&user, 7),
+            Pubkey::find_program_address(&[b"seed_pack", user.as_ref(), &7u64.to_le_bytes()], &farm_game::ID).0
+        );
+        assert_eq!(
+            emission_schedule(),
+            Pubkey::find_program_address(&[b"emission_schedule"], &farm_game::ID).0
+        );
+        
+        // Per-user and per-id accounts do not collide
+        assert_ne!(seed(&user, 0), seed(&user, 1));
+        assert_ne!(farm_space(&user), farm_space(&Pubkey::new_unique()));
+        assert_ne!(invite_code(b"ABCDEFGH1234"), invite_code(b"ABCDEFGH1235"));
+    }
+}
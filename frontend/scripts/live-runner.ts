@@ -94,6 +94,17 @@ async function runLiveUserJourney() {
       }
     }
     
+    try {
+      const scheduleTx = await adminClient.initializeEmissionSchedule();
+      console.log('✅ Emission Schedule初期化成功! Tx:', scheduleTx);
+    } catch (error) {
+      if (error.toString().includes('already in use')) {
+        console.log('✅ Emission Schedule既に初期化済み');
+      } else {
+        console.log('❌ Emission Schedule初期化エラー:', error.toString());
+      }
+    }
+    
     // 7. ユーザー操作開始
     console.log('\n7️⃣ ユーザー操作開始...');
     const userWallet = new Wallet(user);
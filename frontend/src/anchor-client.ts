@@ -16,6 +16,8 @@ import {
   type SeedPackAccount,
   type SeedAccount,
   type GlobalStatsAccount,
+  type PendingConfigChangeAccount,
+  type ClaimSimulation,
   type TransactionResult,
   type ProgramError,
   isUserStateAccount,
@@ -70,8 +72,14 @@ interface ProgramAccountNamespace {
   globalStats: {
     fetchNullable(address: PublicKey): Promise<GlobalStatsAccount | null>;
   };
+  pendingConfigChange: {
+    fetchNullable(address: PublicKey): Promise<PendingConfigChangeAccount | null>;
+  };
 }
 
+// Timelocked config change as encoded by Anchor (one variant key per change kind)
+type ConfigChangeArg = { seedPackCost: { newCost: BN } };
+
 // Program interface for type-safe methods
 interface ProgramMethodNamespace {
   initializeConfig(
@@ -106,6 +114,11 @@ interface ProgramMethodNamespace {
       rpc(): Promise<string>;
     };
   };
+  simulateClaim(): {
+    accounts(accounts: Record<string, PublicKey>): {
+      view(): Promise<ClaimSimulation>;
+    };
+  };
   plantSeed(seedId: BN): {
     accounts(accounts: Record<string, PublicKey>): {
       rpc(): Promise<string>;
@@ -161,6 +174,16 @@ interface ProgramMethodNamespace {
       rpc(): Promise<string>;
     };
   };
+  queueConfigChange(change: ConfigChangeArg): {
+    accounts(accounts: Record<string, PublicKey | null>): {
+      rpc(): Promise<string>;
+    };
+  };
+  executeConfigChange(): {
+    accounts(accounts: Record<string, PublicKey | null>): {
+      rpc(): Promise<string>;
+    };
+  };
 }
 
 // Enhanced program interface
@@ -305,7 +328,7 @@ export class AnchorClient {
           rewardMint: pdas.rewardMint,
           mintAuthority: pdas.mintAuthority,
           transferFeeConfigAuthority: pdas.mintAuthority,
-          withdrawWithheldAuthority: userPublicKey,
+          withdrawWithheldAuthority: pdas.mintAuthority,
           admin: userPublicKey,
           tokenProgram: new PublicKey('TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb'),
           systemProgram: SystemProgram.programId,
@@ -348,6 +371,33 @@ export class AnchorClient {
     }
   }
 
+  // Initialize emission schedule from the halving config (admin only)
+  async initializeEmissionSchedule(): Promise<string> {
+    try {
+      logger.info('📈 排出スケジュールを初期化中...');
+
+      const userPublicKey = this.provider.wallet.publicKey;
+      const pdas = await this.calculatePDAs(userPublicKey);
+
+      const tx = await this.program.methods
+        .initializeEmissionSchedule()
+        .accounts({
+          config: pdas.config,
+          globalStats: pdas.globalStats,
+          admin: userPublicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      logger.success(`排出スケジュール初期化成功! トランザクション: ${tx}`);
+      return tx;
+    } catch (error) {
+      const programError = this.handleProgramError(error);
+      logger.error(`排出スケジュール初期化エラー: ${programError.message}`);
+      throw programError;
+    }
+  }
+
   // Initialize user account
   async initUser(): Promise<TransactionResult> {
     try {
@@ -519,6 +569,43 @@ export class AnchorClient {
     }
   }
 
+  // Simulate a claim on-chain (read-only) and return the reward breakdown
+  async simulateClaim(): Promise<ClaimSimulation> {
+    try {
+      const userPublicKey = this.provider.wallet.publicKey;
+      const pdas = await this.calculatePDAs(userPublicKey);
+
+      const userStateAccount = await this.program.account.userState.fetchNullable(pdas.userState);
+      if (!userStateAccount) {
+        throw new Error(ERROR_MESSAGES.USER_NOT_INITIALIZED);
+      }
+
+      const accounts: Record<string, PublicKey> = {
+        userState: pdas.userState,
+        config: pdas.config,
+        globalStats: pdas.globalStats,
+        user: userPublicKey,
+      };
+
+      // The level 1 referrer's state tells the program whether a level 2 referrer exists
+      // (the protocol address has no user state and needs none)
+      if (userStateAccount.referrer) {
+        const [level1ReferrerState] = PublicKey.findProgramAddressSync(
+          [Buffer.from('user'), userStateAccount.referrer.toBuffer()],
+          this.program.programId
+        );
+        if (await this.program.account.userState.fetchNullable(level1ReferrerState)) {
+          accounts.level1ReferrerState = level1ReferrerState;
+        }
+      }
+
+      return await this.program.methods.simulateClaim().accounts(accounts).view();
+    } catch (error) {
+      logger.error(`報酬シミュレーションエラー: ${error instanceof Error ? error.message : String(error)}`);
+      throw error;
+    }
+  }
+
   // Fetch user state with type validation
   async fetchUserState(userPublicKey: PublicKey): Promise<UserStateAccount | null> {
     try {
@@ -871,6 +958,7 @@ export class AnchorClient {
         .accounts({
           userState: pdas.userState,
           config: pdas.config,
+          globalStats: pdas.globalStats,
           seedPack: seedPackPDA,
           rewardMint: pdas.rewardMint,
           userTokenAccount: userTokenAccount,
@@ -918,6 +1006,7 @@ export class AnchorClient {
         .accounts({
           seedPack: seedPackPDA,
           config: pdas.config,
+          globalStats: pdas.globalStats,
           seedStorage: seedStoragePDA,
           user: userPublicKey,
           systemProgram: SystemProgram.programId,
@@ -1153,6 +1242,10 @@ export class AnchorClient {
     totalSupply: number;
     currentRewardsPerSecond: number;
     lastUpdateTime: number;
+    totalBurned: number;
+    totalPacksSold: number;
+    totalSeedsGenerated: number;
+    totalSolCollected: number;
   } | null> {
     try {
       const [globalStatsPDA] = await PublicKey.findProgramAddress(
@@ -1172,6 +1265,10 @@ export class AnchorClient {
         totalSupply: safeBNToNumber(globalStats.totalSupply),
         currentRewardsPerSecond: safeBNToNumber(globalStats.currentRewardsPerSecond),
         lastUpdateTime: safeBNToNumber(globalStats.lastUpdateTime),
+        totalBurned: safeBNToNumber(globalStats.totalBurned),
+        totalPacksSold: safeBNToNumber(globalStats.totalPacksSold),
+        totalSeedsGenerated: safeBNToNumber(globalStats.totalSeedsGenerated),
+        totalSolCollected: safeBNToNumber(globalStats.totalSolCollected),
       };
     } catch (error) {
       logger.error(`グローバル統計取得エラー: ${error instanceof Error ? error.message : String(error)}`);
@@ -1260,21 +1357,24 @@ export class AnchorClient {
   // ===== ADMIN FUNCTIONS =====
 
   /**
-   * Update seed pack cost (admin only)
-   * Allows dynamic adjustment of WEED price for seed packs
+   * Queue a seed pack cost change (admin or pricing manager)
+   * Price changes go through the timelock: the change is stored in a PendingConfigChange
+   * and applied with `executeConfigChange` once `config_change_delay` has elapsed
    * 
    * @param newSeedPackCost - New cost in WEED tokens (with 6 decimals)
-   * @returns Transaction result
+   * @returns Change ID to pass to `executeConfigChange`
    * 
    * @example
    * // Set seed pack cost to 500 WEED
-   * await client.updateSeedPackCost(500_000_000);
+   * const { changeId } = await client.queueSeedPackCostChange(500_000_000);
+   * // ...after the timelock delay
+   * await client.executeConfigChange(changeId);
    */
-  async updateSeedPackCost(newSeedPackCost: number): Promise<TransactionResult> {
+  async queueSeedPackCostChange(newSeedPackCost: number): Promise<{ transaction: string; changeId: BN }> {
     try {
-      logger.info('Updating seed pack cost', { newSeedPackCost });
+      logger.info('Queueing seed pack cost change', { newSeedPackCost });
       
-      // Validation
+      // Validation (same bounds as validate_config_change)
       if (newSeedPackCost <= 0) {
         throw new Error('Seed pack cost must be greater than 0');
       }
@@ -1285,29 +1385,92 @@ export class AnchorClient {
 
       const userPublicKey = this.provider.wallet.publicKey;
       const pdas = await this.calculatePDAs(userPublicKey);
+      const config = await this.program.account.config.fetchNullable(pdas.config);
+      if (!config) {
+        throw new Error('Config is not initialized');
+      }
+      const changeId: BN = config.configChangeCounter;
 
       const tx = await this.program.methods
-        .updateSeedPackCost(new BN(newSeedPackCost))
+        .queueConfigChange({ seedPackCost: { newCost: new BN(newSeedPackCost) } })
         .accounts({
           config: pdas.config,
-          admin: userPublicKey,
+          pendingChange: this.pendingConfigChangePDA(changeId),
+          roleRegistry: null,
+          authority: userPublicKey,
+          systemProgram: SystemProgram.programId,
         })
         .rpc();
 
-      logger.success('Seed pack cost updated successfully', { 
+      logger.success('Seed pack cost change queued', { 
         transaction: tx,
+        changeId: changeId.toString(),
         newCostWeed: newSeedPackCost / 1_000_000
       });
 
+      return { transaction: tx, changeId };
+
+    } catch (error) {
+      const programError = this.handleProgramError(error);
+      logger.error('Failed to queue seed pack cost change:', programError.message);
+      throw programError;
+    }
+  }
+
+  /**
+   * Apply a queued config change once its timelock has elapsed
+   * Only price changes are queued from this client, so no optional accounts are needed
+   * 
+   * @param changeId - ID returned by `queueSeedPackCostChange`
+   * @returns Transaction result
+   */
+  async executeConfigChange(changeId: BN): Promise<TransactionResult> {
+    try {
+      logger.info('Executing config change', { changeId: changeId.toString() });
+
+      const userPublicKey = this.provider.wallet.publicKey;
+      const pdas = await this.calculatePDAs(userPublicKey);
+      const pendingChange = this.pendingConfigChangePDA(changeId);
+      const pending = await this.program.account.pendingConfigChange.fetchNullable(pendingChange);
+      if (!pending) {
+        throw new Error(`Config change ${changeId.toString()} is not queued`);
+      }
+      if (Date.now() / 1000 < pending.executableAt.toNumber()) {
+        throw new Error(`Config change ${changeId.toString()} is timelocked until ${pending.executableAt.toString()}`);
+      }
+
+      const tx = await this.program.methods
+        .executeConfigChange()
+        .accounts({
+          config: pdas.config,
+          pendingChange,
+          probabilityTable: null,
+          emissionSchedule: null,
+          globalStats: null,
+          roleRegistry: null,
+          authority: userPublicKey,
+        })
+        .rpc();
+
+      logger.success('Config change executed', { transaction: tx, changeId: changeId.toString() });
+
       return tx;
 
     } catch (error) {
       const programError = this.handleProgramError(error);
-      logger.error('Failed to update seed pack cost:', programError.message);
+      logger.error('Failed to execute config change:', programError.message);
       throw programError;
     }
   }
 
+  private pendingConfigChangePDA(changeId: BN): PublicKey {
+    const [pendingChange] = PublicKey.findProgramAddressSync(
+      [Buffer.from('pending_config_change'), changeId.toArrayLike(Buffer, 'le', 8)],
+      this.program.programId
+    );
+    return pendingChange;
+  }
+
   /**
    * Reveal a new seed type (admin only)
    * Makes a previously hidden seed type visible to users with its values
@@ -230,7 +230,8 @@ const GameControls: React.FC<GameControlsProps> = ({
           try {
             uiCallbacks.showLoading('グローバル統計を初期化中...');
             await gameClient.initializeGlobalStats();
-            uiCallbacks.showSuccess('グローバル統計初期化完了');
+            await gameClient.initializeEmissionSchedule();
+            uiCallbacks.showSuccess('グローバル統計・排出スケジュール初期化完了');
             await uiCallbacks.updateGameState();
           } catch (error) {
             uiCallbacks.showError(`グローバル統計初期化失敗: ${error instanceof Error ? error.message : String(error)}`);
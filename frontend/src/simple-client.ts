@@ -77,7 +77,7 @@ export class SimpleClient {
         rewardMint: pdas.rewardMint,
         mintAuthority: pdas.mintAuthority,
         transferFeeConfigAuthority: pdas.mintAuthority,
-        withdrawWithheldAuthority: userPublicKey,
+        withdrawWithheldAuthority: pdas.mintAuthority,
         admin: userPublicKey,
         tokenProgram: new PublicKey('TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb'),
         systemProgram: SystemProgram.programId,
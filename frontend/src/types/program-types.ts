@@ -45,6 +45,11 @@ export interface ConfigAccount {
   protocolReferralAddress: PublicKey;
   totalSupplyMinted: BN;
   operator: PublicKey;
+  configChangeDelay: BN;
+  configChangeCounter: BN;
+  transferFeeBasisPoints: number;
+  pendingTransferFeeBasisPoints: number;
+  pendingTransferFeeEpoch: BN;
   reserve: number[];
 }
 
@@ -95,12 +100,40 @@ export interface SeedStorageAccount {
   reserve: number[];
 }
 
+// Reward breakdown returned by the simulate_claim view
+export interface ClaimSimulation {
+  farmingReward: BN;
+  claimantFarmingReward: BN;
+  level1Reward: BN;
+  level2Reward: BN;
+  pendingReferralRewards: BN;
+  totalClaimable: BN;
+  halvingPeriodsCrossed: number;
+  supplyHeadroom: BN;
+  supplyExhausted: boolean;
+  timestamp: BN;
+}
+
 export interface GlobalStatsAccount {
   totalGrowPower: BN;
   totalFarmSpaces: BN;
   totalSupply: BN;
   currentRewardsPerSecond: BN;
   lastUpdateTime: BN;
+  totalBurned: BN;
+  totalPacksSold: BN;
+  totalSeedsGenerated: BN;
+  totalSolCollected: BN;
+  reserve: number[];
+}
+
+// Timelocked config change waiting for its delay (PDA: ["pending_config_change", change_id])
+export interface PendingConfigChangeAccount {
+  changeId: BN;
+  proposer: PublicKey;
+  change: Record<string, unknown>;
+  queuedAt: BN;
+  executableAt: BN;
   reserve: number[];
 }
 
@@ -0,0 +1,195 @@
+//! Decoding of farm_game event payloads and account snapshots
+//! Events are matched on their Anchor discriminator and borsh-decoded from the program's own types,
+//! so the indexer always follows the on-chain layout
+
+use anchor_lang::{AccountDeserialize, AnchorDeserialize, Discriminator};
+use anyhow::{Context, Result};
+use farm_game::events::*;
+use farm_game::state::{FarmSpace, GlobalStats, InviteCode, ProbabilityTable, Seed, SeedPack, SeedStorage, UserState};
+
+macro_rules! game_events {
+    ($($name:ident),* $(,)?) => {
+        /// Every event emitted by the program
+        #[allow(clippy::large_enum_variant)]
+        pub enum GameEvent {
+            $($name($name),)*
+        }
+        
+        impl GameEvent {
+            /// Decode an event payload (discriminator + borsh data)
+            /// Returns `Ok(None)` for discriminators this build does not know
+            pub fn decode(data: &[u8]) -> Result<Option<Self>> {
+                $(
+                    if let Some(mut body) = data.strip_prefix(<$name as Discriminator>::DISCRIMINATOR) {
+                        let event = $name::deserialize(&mut body)
+                            .with_context(|| format!("failed to decode {}", stringify!($name)))?;
+                        return Ok(Some(GameEvent::$name(event)));
+                    }
+                )*
+                Ok(None)
+            }
+            
+            /// Event type name, as stored in the event log
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(GameEvent::$name(_) => stringify!($name),)*
+                }
+            }
+        }
+    };
+}
+
+game_events!(
+    UserInitializedEvent,
+    FarmSpacePurchasedEvent,
+    FarmSpaceUpgradedEvent,
+    FarmSpaceMigratedEvent,
+    SeedPackPurchasedEvent,
+    SeedPackOpenedEvent,
+    SeedsPlantedEvent,
+    SeedsRemovedEvent,
+    SeedsDiscardedEvent,
+    RewardClaimedEvent,
+    ReferralRewardAccruedEvent,
+    PendingReferralRewardsEvent,
+    SupplyExhaustedEvent,
+    InviteCodeCreatedEvent,
+    InviteCodeUsedEvent,
+    ConfigInitializedEvent,
+    ConfigUpdatedEvent,
+    TokenMetadataUpdatedEvent,
+    FarmLevelConfigUpdatedEvent,
+    ProbabilityTableInitializedEvent,
+    ProbabilityTableUpdatedEvent,
+    EmissionScheduleInitializedEvent,
+    RandomnessBeaconPublishedEvent,
+    FeesWithdrawnEvent,
+    AdminProposedEvent,
+    AdminChangedEvent,
+    OperatorProposedEvent,
+    OperatorChangedEvent,
+    RoleGrantedEvent,
+    RoleRevokedEvent,
+    SystemPauseChangedEvent,
+    SubsystemPauseChangedEvent,
+    ConfigChangeQueuedEvent,
+    ConfigChangeExecutedEvent,
+    ConfigChangeCancelledEvent,
+    MultisigSignersChangedEvent,
+    ProposalCreatedEvent,
+    ProposalApprovedEvent,
+    ProposalExecutedEvent,
+    WithheldFeesHarvestedEvent,
+    WithheldFeesWithdrawnEvent,
+    WithheldFeesToFeePoolEvent,
+    TransferFeeUpdatedEvent,
+    AccountMigratedEvent,
+);
+
+/// Program accounts the projection understands
+#[allow(clippy::large_enum_variant)]
+pub enum GameAccount {
+    UserState(UserState),
+    FarmSpace(FarmSpace),
+    Seed(Seed),
+    SeedStorage(SeedStorage),
+    SeedPack(SeedPack),
+    InviteCode(InviteCode),
+    ProbabilityTable(ProbabilityTable),
+    GlobalStats(GlobalStats),
+}
+
+impl GameAccount {
+    /// Decode account data by its discriminator
+    /// Returns `Ok(None)` for accounts the projection does not track (config, fee pool, ...)
+    pub fn decode(data: &[u8]) -> Result<Option<Self>> {
+        fn load<T: AccountDeserialize + Discriminator>(data: &[u8]) -> Result<Option<T>> {
+            if !data.starts_with(T::DISCRIMINATOR) {
+                return Ok(None);
+            }
+            T::try_deserialize(&mut &data[..])
+                .map(Some)
+                .map_err(|error| anyhow::anyhow!("failed to decode account: {}", error))
+        }
+        
+        if let Some(account) = load(data)? {
+            return Ok(Some(GameAccount::UserState(account)));
+        }
+        if let Some(account) = load(data)? {
+            return Ok(Some(GameAccount::FarmSpace(account)));
+        }
+        if let Some(account) = load(data)? {
+            return Ok(Some(GameAccount::Seed(account)));
+        }
+        if let Some(account) = load(data)? {
+            return Ok(Some(GameAccount::SeedStorage(account)));
+        }
+        if let Some(account) = load(data)? {
+            return Ok(Some(GameAccount::SeedPack(account)));
+        }
+        if let Some(account) = load(data)? {
+            return Ok(Some(GameAccount::InviteCode(account)));
+        }
+        if let Some(account) = load(data)? {
+            return Ok(Some(GameAccount::ProbabilityTable(account)));
+        }
+        if let Some(account) = load(data)? {
+            return Ok(Some(GameAccount::GlobalStats(account)));
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::prelude::Pubkey;
+    use anchor_lang::{AccountSerialize, Event};
+    
+    #[test]
+    fn test_event_round_trip() {
+        let user = Pubkey::new_unique();
+        let data = UserInitializedEvent {
+            user,
+            referrer: None,
+            initialized_by: user,
+            timestamp: 42,
+        }
+        .data();
+        
+        match GameEvent::decode(&data).unwrap() {
+            Some(GameEvent::UserInitializedEvent(event)) => {
+                assert_eq!(event.user, user);
+                assert_eq!(event.timestamp, 42);
+            }
+            _ => panic!("expected UserInitializedEvent"),
+        }
+        
+        // Unknown discriminators are skipped, truncated payloads are errors
+        assert!(GameEvent::decode(&[0u8; 16]).unwrap().is_none());
+        assert!(GameEvent::decode(&data[..data.len() - 1]).is_err());
+    }
+    
+    #[test]
+    fn test_account_decoding() {
+        let farm = FarmSpace {
+            owner: Pubkey::new_unique(),
+            level: 2,
+            capacity: 6,
+            seed_count: 1,
+            total_grow_power: 100,
+            version: 1,
+            reserve: [0; 31],
+        };
+        let mut data = Vec::new();
+        farm.try_serialize(&mut data).unwrap();
+        
+        match GameAccount::decode(&data).unwrap() {
+            Some(GameAccount::FarmSpace(decoded)) => assert_eq!(decoded.capacity, 6),
+            _ => panic!("expected FarmSpace"),
+        }
+        
+        assert!(GameAccount::decode(&[1u8; 64]).unwrap().is_none());
+        assert!(GameAccount::decode(&data[..12]).is_err());
+    }
+}
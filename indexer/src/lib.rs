@@ -0,0 +1,12 @@
+//! Off-chain indexer for the farm_game program
+//! Rebuilds users, farms, seed inventories, referral trees, claim history and
+//! pack odds vs outcomes into SQLite from emitted events and account snapshots
+
+pub mod decode;
+pub mod projection;
+pub mod schema;
+pub mod source;
+
+pub use decode::{GameAccount, GameEvent};
+pub use projection::{Indexer, ReplayStats};
+pub use source::{Input, RawAccount, RawEvent, Record};
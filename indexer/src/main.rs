@@ -0,0 +1,26 @@
+//! farm-game-indexer CLI
+//!
+//! Usage: farm-game-indexer replay <log.jsonl> <db.sqlite>
+
+use anyhow::{bail, Context, Result};
+use farm_game_indexer::Indexer;
+use std::fs::File;
+use std::io::BufReader;
+
+fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (log, db) = match args.as_slice() {
+        [command, log, db] if command == "replay" => (log, db),
+        _ => bail!("usage: farm-game-indexer replay <log.jsonl> <db.sqlite>"),
+    };
+    
+    let file = File::open(log).with_context(|| format!("cannot open {}", log))?;
+    let mut indexer = Indexer::open(db)?;
+    let stats = indexer.replay(BufReader::new(file))?;
+    
+    println!(
+        "Replayed {}: {} events, {} duplicates, {} unknown, {} account snapshots",
+        log, stats.events, stats.duplicates, stats.unknown, stats.accounts
+    );
+    Ok(())
+}
@@ -0,0 +1,740 @@
+//! Projection of events and account snapshots into SQLite
+//! Events drive the history (claims, referral accruals, pack outcomes); account snapshots,
+//! when present in the log, overwrite the derived current state with the on-chain values
+
+use crate::decode::{GameAccount, GameEvent};
+use crate::schema::SCHEMA;
+use crate::source::{read_records, Input, RawAccount, RawEvent, Record};
+use anchor_lang::prelude::Pubkey;
+use anyhow::Result;
+use farm_game::state::{FarmSpace, ProbabilityTable};
+use rusqlite::{params, Connection};
+use std::io::BufRead;
+use std::path::Path;
+
+/// Counters reported after a replay
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReplayStats {
+    /// Events applied to the projection
+    pub events: u64,
+    /// Events already recorded under the same signature and index
+    pub duplicates: u64,
+    /// Payloads with an unknown discriminator
+    pub unknown: u64,
+    /// Account snapshots applied
+    pub accounts: u64,
+}
+
+/// SQLite projection of the game state
+pub struct Indexer {
+    conn: Connection,
+    program_id: Pubkey,
+}
+
+impl Indexer {
+    /// Open (or create) a database file
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::new(Connection::open(path)?)
+    }
+    
+    /// In-memory database, used by tests and dry runs
+    pub fn open_in_memory() -> Result<Self> {
+        Self::new(Connection::open_in_memory()?)
+    }
+    
+    fn new(conn: Connection) -> Result<Self> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self {
+            conn,
+            program_id: farm_game::ID,
+        })
+    }
+    
+    /// Read access for queries against the projection
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+    
+    /// Replay a JSON-lines log; each record is applied in its own transaction
+    pub fn replay(&mut self, reader: impl BufRead) -> Result<ReplayStats> {
+        let mut stats = ReplayStats::default();
+        for record in read_records(reader)? {
+            self.apply_record(record, &mut stats)?;
+        }
+        Ok(stats)
+    }
+    
+    /// Apply a single record
+    pub fn apply_record(&mut self, record: Record, stats: &mut ReplayStats) -> Result<()> {
+        let inputs = record.into_inputs(&self.program_id)?;
+        let tx = self.conn.transaction()?;
+        for input in inputs {
+            match input {
+                Input::Event(raw) => apply_event(&tx, &raw, stats)?,
+                Input::Account(raw) => apply_account(&tx, &raw, stats)?,
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+fn apply_event(conn: &Connection, raw: &RawEvent, stats: &mut ReplayStats) -> Result<()> {
+    let Some(event) = GameEvent::decode(&raw.data)? else {
+        stats.unknown += 1;
+        return Ok(());
+    };
+    
+    let inserted = conn.execute(
+        "INSERT OR IGNORE INTO event_log (slot, signature, event_index, name) VALUES (?1, ?2, ?3, ?4)",
+        params![raw.slot, raw.signature, raw.index, event.name()],
+    )?;
+    if inserted == 0 {
+        stats.duplicates += 1;
+        return Ok(());
+    }
+    stats.events += 1;
+    
+    let slot = raw.slot;
+    match event {
+        GameEvent::UserInitializedEvent(e) => {
+            conn.execute(
+                "INSERT INTO users (owner, referrer, initialized_by, created_at, updated_slot)
+                 VALUES (?1, ?2, ?3, ?4, ?5)
+                 ON CONFLICT (owner) DO UPDATE SET
+                    referrer = excluded.referrer,
+                    initialized_by = excluded.initialized_by,
+                    created_at = excluded.created_at,
+                    updated_slot = excluded.updated_slot",
+                params![key(&e.user), e.referrer.as_ref().map(key), key(&e.initialized_by), e.timestamp, slot],
+            )?;
+        }
+        GameEvent::FarmSpacePurchasedEvent(e) => {
+            ensure_user(conn, &e.user, slot)?;
+            conn.execute(
+                "UPDATE users SET has_farm_space = 1, total_grow_power = total_grow_power + ?2, updated_slot = ?3
+                 WHERE owner = ?1",
+                params![key(&e.user), e.grow_power, slot],
+            )?;
+            conn.execute(
+                "INSERT OR REPLACE INTO farms
+                    (address, owner, level, capacity, seed_count, total_grow_power, sol_paid, created_at, updated_slot)
+                 VALUES (?1, ?2, 1, ?3, 1, ?4, ?5, ?6, ?7)",
+                params![
+                    key(&e.farm_space),
+                    key(&e.user),
+                    FarmSpace::get_capacity_for_level(1),
+                    e.grow_power,
+                    e.sol_paid,
+                    e.timestamp,
+                    slot
+                ],
+            )?;
+            // The free initial seed is always Seed1 with id 0, planted right away
+            conn.execute(
+                "INSERT OR REPLACE INTO seeds
+                    (owner, seed_id, seed_type, grow_power, pack_id, status, farm_space, created_at, updated_slot)
+                 VALUES (?1, 0, 0, ?2, NULL, 'planted', ?3, ?4, ?5)",
+                params![key(&e.user), e.grow_power, key(&e.farm_space), e.timestamp, slot],
+            )?;
+        }
+        GameEvent::FarmSpaceUpgradedEvent(e) => {
+            conn.execute(
+                "UPDATE farms SET level = ?2, capacity = ?3, updated_slot = ?4 WHERE address = ?1",
+                params![key(&e.farm_space), e.level, e.capacity, slot],
+            )?;
+            conn.execute(
+                "UPDATE users SET total_packs_purchased = ?2, updated_slot = ?3 WHERE owner = ?1",
+                params![key(&e.user), e.total_packs_purchased, slot],
+            )?;
+        }
+        GameEvent::FarmSpaceMigratedEvent(e) => {
+            conn.execute(
+                "UPDATE farms SET level = ?2, capacity = ?3, updated_slot = ?4 WHERE address = ?1",
+                params![key(&e.farm_space), e.level, e.capacity, slot],
+            )?;
+        }
+        GameEvent::SeedPackPurchasedEvent(e) => {
+            ensure_user(conn, &e.user, slot)?;
+            conn.execute(
+                "INSERT OR REPLACE INTO packs
+                    (owner, pack_id, quantity, weed_burned, vrf_fee_paid, randomness_source, commit_slot, purchased_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    key(&e.user),
+                    e.pack_id,
+                    e.quantity,
+                    e.weed_burned,
+                    e.vrf_fee_paid,
+                    format!("{:?}", e.randomness_source),
+                    e.commit_slot,
+                    e.timestamp
+                ],
+            )?;
+            conn.execute(
+                "UPDATE users SET total_packs_purchased = total_packs_purchased + ?2, updated_slot = ?3
+                 WHERE owner = ?1",
+                params![key(&e.user), e.quantity, slot],
+            )?;
+        }
+        GameEvent::SeedPackOpenedEvent(e) => {
+            conn.execute(
+                "UPDATE packs SET opened_at = ?3, random_value = ?4 WHERE owner = ?1 AND pack_id = ?2",
+                params![key(&e.user), e.pack_id, e.timestamp, e.random_value.to_string()],
+            )?;
+            for seed in &e.seeds {
+                conn.execute(
+                    "INSERT OR REPLACE INTO seeds
+                        (owner, seed_id, seed_type, grow_power, pack_id, status, farm_space, created_at, updated_slot)
+                     VALUES (?1, ?2, ?3, ?4, ?5, 'stored', NULL, ?6, ?7)",
+                    params![key(&e.user), seed.seed_id, seed.seed_type as u8, seed.grow_power, e.pack_id, e.timestamp, slot],
+                )?;
+            }
+        }
+        GameEvent::SeedsPlantedEvent(e) => {
+            set_seed_status(conn, &e.user, &e.seed_ids, "planted", Some(&e.farm_space), slot)?;
+            update_farm_power(conn, &e.farm_space, e.seed_ids.len() as i64, e.farm_grow_power, slot)?;
+            update_user_power(conn, &e.user, e.user_grow_power, slot)?;
+        }
+        GameEvent::SeedsRemovedEvent(e) => {
+            set_seed_status(conn, &e.user, &e.seed_ids, "stored", None, slot)?;
+            update_farm_power(conn, &e.farm_space, -(e.seed_ids.len() as i64), e.farm_grow_power, slot)?;
+            update_user_power(conn, &e.user, e.user_grow_power, slot)?;
+        }
+        GameEvent::SeedsDiscardedEvent(e) => {
+            set_seed_status(conn, &e.user, &e.seed_ids, "discarded", None, slot)?;
+        }
+        GameEvent::RewardClaimedEvent(e) => {
+            ensure_user(conn, &e.user, slot)?;
+            conn.execute(
+                "INSERT INTO claims
+                    (owner, farming_reward, claimant_farming_reward, level1_reward, level2_reward,
+                     referral_rewards, total_reward, paid_amount, fee_funded, signature, slot, timestamp)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                params![
+                    key(&e.user),
+                    e.farming_reward,
+                    e.claimant_farming_reward,
+                    e.level1_reward,
+                    e.level2_reward,
+                    e.referral_rewards,
+                    e.total_reward,
+                    e.paid_amount,
+                    e.fee_funded,
+                    raw.signature,
+                    slot,
+                    e.timestamp
+                ],
+            )?;
+            // A claim always clears the pending referral balance
+            conn.execute(
+                "UPDATE users SET total_claimed = total_claimed + ?2, pending_referral_rewards = 0, updated_slot = ?3
+                 WHERE owner = ?1",
+                params![key(&e.user), e.paid_amount, slot],
+            )?;
+        }
+        GameEvent::ReferralRewardAccruedEvent(e) => {
+            ensure_user(conn, &e.referrer, slot)?;
+            // The legacy distribute_referral_reward instruction does not know the referee
+            let referee = (e.referee != Pubkey::default()).then(|| key(&e.referee));
+            conn.execute(
+                "INSERT INTO referral_rewards (referrer, referee, level, amount, pending_total, slot, timestamp)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![key(&e.referrer), referee, e.level, e.amount, e.pending_total, slot, e.timestamp],
+            )?;
+            set_pending_referral_rewards(conn, &e.referrer, e.pending_total, slot)?;
+        }
+        GameEvent::PendingReferralRewardsEvent(e) => {
+            set_pending_referral_rewards(conn, &e.user, e.pending_amount, slot)?;
+        }
+        GameEvent::InviteCodeCreatedEvent(e) => {
+            conn.execute(
+                "INSERT OR REPLACE INTO invites (address, inviter, code_hash, uses, max_uses, created_at)
+                 VALUES (?1, ?2, ?3, 0, ?4, ?5)",
+                params![key(&e.invite_account), key(&e.inviter), hex(&e.code_hash), e.max_uses, e.timestamp],
+            )?;
+        }
+        GameEvent::InviteCodeUsedEvent(e) => {
+            conn.execute(
+                "INSERT INTO invites (address, inviter, uses, max_uses) VALUES (?1, ?2, ?3, ?4)
+                 ON CONFLICT (address) DO UPDATE SET uses = excluded.uses, max_uses = excluded.max_uses",
+                params![key(&e.invite_account), key(&e.inviter), e.uses, e.max_uses],
+            )?;
+        }
+        GameEvent::ProbabilityTableInitializedEvent(e) => {
+            // initialize_probability_table always writes table 1
+            let table = ProbabilityTable::init_table_1();
+            conn.execute("DELETE FROM pack_odds", [])?;
+            insert_odds(conn, &table, e.version, slot)?;
+        }
+        GameEvent::ProbabilityTableUpdatedEvent(e) => {
+            conn.execute(
+                "INSERT INTO pack_odds (seed_index, grow_power, probability_percentage, revealed, updated_slot)
+                 VALUES (?1, ?2, ?3, ?4, ?5)
+                 ON CONFLICT (seed_index) DO UPDATE SET
+                    grow_power = excluded.grow_power,
+                    probability_percentage = excluded.probability_percentage,
+                    revealed = excluded.revealed,
+                    updated_slot = excluded.updated_slot",
+                params![e.seed_index, e.grow_power, e.probability_percentage as f64, e.revealed, slot],
+            )?;
+        }
+        GameEvent::SupplyExhaustedEvent(_) => {
+            conn.execute("UPDATE global_stats SET supply_exhausted = 1, updated_slot = ?1", params![slot])?;
+        }
+        // Admin, governance and fee events are kept in the event log only
+        _ => {}
+    }
+    
+    Ok(())
+}
+
+fn apply_account(conn: &Connection, raw: &RawAccount, stats: &mut ReplayStats) -> Result<()> {
+    let Some(account) = GameAccount::decode(&raw.data)? else {
+        return Ok(());
+    };
+    stats.accounts += 1;
+    
+    let slot = raw.slot;
+    match account {
+        GameAccount::UserState(user) => {
+            conn.execute(
+                "INSERT INTO users
+                    (owner, referrer, has_farm_space, total_grow_power, total_packs_purchased,
+                     pending_referral_rewards, updated_slot)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                 ON CONFLICT (owner) DO UPDATE SET
+                    referrer = excluded.referrer,
+                    has_farm_space = excluded.has_farm_space,
+                    total_grow_power = excluded.total_grow_power,
+                    total_packs_purchased = excluded.total_packs_purchased,
+                    pending_referral_rewards = excluded.pending_referral_rewards,
+                    updated_slot = excluded.updated_slot",
+                params![
+                    key(&user.owner),
+                    user.referrer.as_ref().map(key),
+                    user.has_farm_space,
+                    user.total_grow_power,
+                    user.total_packs_purchased,
+                    user.pending_referral_rewards,
+                    slot
+                ],
+            )?;
+        }
+        GameAccount::FarmSpace(farm) => {
+            conn.execute(
+                "INSERT INTO farms (address, owner, level, capacity, seed_count, total_grow_power, updated_slot)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                 ON CONFLICT (address) DO UPDATE SET
+                    level = excluded.level,
+                    capacity = excluded.capacity,
+                    seed_count = excluded.seed_count,
+                    total_grow_power = excluded.total_grow_power,
+                    updated_slot = excluded.updated_slot",
+                params![
+                    key(&raw.pubkey),
+                    key(&farm.owner),
+                    farm.level,
+                    farm.capacity,
+                    farm.seed_count,
+                    farm.total_grow_power,
+                    slot
+                ],
+            )?;
+        }
+        GameAccount::Seed(seed) => {
+            let status = if seed.is_planted { "planted" } else { "stored" };
+            conn.execute(
+                "INSERT INTO seeds (owner, seed_id, seed_type, grow_power, status, farm_space, created_at, updated_slot)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                 ON CONFLICT (owner, seed_id) DO UPDATE SET
+                    seed_type = excluded.seed_type,
+                    grow_power = excluded.grow_power,
+                    status = excluded.status,
+                    farm_space = excluded.farm_space,
+                    updated_slot = excluded.updated_slot",
+                params![
+                    key(&seed.owner),
+                    seed.seed_id,
+                    seed.seed_type as u8,
+                    seed.grow_power,
+                    status,
+                    seed.planted_farm_space.as_ref().map(key),
+                    seed.created_at,
+                    slot
+                ],
+            )?;
+        }
+        GameAccount::SeedStorage(storage) => {
+            // Storage only lists ids and types; grow power comes from the pack event or Seed account
+            for (seed_id, seed_type) in storage.seed_ids.iter().zip(&storage.seed_types) {
+                conn.execute(
+                    "INSERT OR IGNORE INTO seeds (owner, seed_id, seed_type, grow_power, status, updated_slot)
+                     VALUES (?1, ?2, ?3, 0, 'stored', ?4)",
+                    params![key(&storage.owner), seed_id, *seed_type as u8, slot],
+                )?;
+            }
+        }
+        GameAccount::SeedPack(pack) => {
+            conn.execute(
+                "INSERT INTO packs
+                    (owner, pack_id, quantity, vrf_fee_paid, randomness_source, commit_slot, purchased_at,
+                     opened_at, random_value)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+                 ON CONFLICT (owner, pack_id) DO UPDATE SET
+                    opened_at = excluded.opened_at,
+                    random_value = excluded.random_value",
+                params![
+                    key(&pack.owner),
+                    pack.pack_id,
+                    pack.quantity,
+                    pack.vrf_fee_paid,
+                    format!("{:?}", pack.randomness_source),
+                    pack.commit_slot,
+                    pack.purchased_at,
+                    pack.opened_at,
+                    pack.final_random_value.map(|value| value.to_string())
+                ],
+            )?;
+        }
+        GameAccount::InviteCode(invite) => {
+            conn.execute(
+                "INSERT INTO invites (address, inviter, code_hash, uses, max_uses, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                 ON CONFLICT (address) DO UPDATE SET
+                    code_hash = excluded.code_hash,
+                    uses = excluded.uses,
+                    max_uses = excluded.max_uses",
+                params![
+                    key(&raw.pubkey),
+                    key(&invite.inviter),
+                    hex(&invite.code_hash),
+                    invite.uses,
+                    invite.max_uses,
+                    invite.created_at
+                ],
+            )?;
+        }
+        GameAccount::ProbabilityTable(table) => {
+            conn.execute("DELETE FROM pack_odds", [])?;
+            insert_odds(conn, &table, table.version, slot)?;
+        }
+        GameAccount::GlobalStats(stats) => {
+            conn.execute(
+                "INSERT OR REPLACE INTO global_stats
+                    (id, total_grow_power, total_farm_spaces, total_supply, current_rewards_per_second,
+                     total_burned, total_packs_sold, total_seeds_generated, total_sol_collected,
+                     supply_exhausted, updated_slot)
+                 VALUES (1, ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    stats.total_grow_power,
+                    stats.total_farm_spaces,
+                    stats.total_supply,
+                    stats.current_rewards_per_second,
+                    stats.total_burned,
+                    stats.total_packs_sold,
+                    stats.total_seeds_generated,
+                    stats.total_sol_collected,
+                    stats.supply_exhausted,
+                    slot
+                ],
+            )?;
+        }
+    }
+    
+    Ok(())
+}
+
+fn ensure_user(conn: &Connection, owner: &Pubkey, slot: u64) -> Result<()> {
+    conn.execute(
+        "INSERT OR IGNORE INTO users (owner, updated_slot) VALUES (?1, ?2)",
+        params![key(owner), slot],
+    )?;
+    Ok(())
+}
+
+fn set_seed_status(
+    conn: &Connection,
+    owner: &Pubkey,
+    seed_ids: &[u64],
+    status: &str,
+    farm_space: Option<&Pubkey>,
+    slot: u64,
+) -> Result<()> {
+    for seed_id in seed_ids {
+        conn.execute(
+            "UPDATE seeds SET status = ?3, farm_space = ?4, updated_slot = ?5 WHERE owner = ?1 AND seed_id = ?2",
+            params![key(owner), seed_id, status, farm_space.map(key), slot],
+        )?;
+    }
+    Ok(())
+}
+
+fn update_farm_power(conn: &Connection, farm_space: &Pubkey, seed_delta: i64, grow_power: u64, slot: u64) -> Result<()> {
+    conn.execute(
+        "UPDATE farms SET seed_count = MAX(seed_count + ?2, 0), total_grow_power = ?3, updated_slot = ?4
+         WHERE address = ?1",
+        params![key(farm_space), seed_delta, grow_power, slot],
+    )?;
+    Ok(())
+}
+
+fn update_user_power(conn: &Connection, owner: &Pubkey, grow_power: u64, slot: u64) -> Result<()> {
+    conn.execute(
+        "UPDATE users SET total_grow_power = ?2, updated_slot = ?3 WHERE owner = ?1",
+        params![key(owner), grow_power, slot],
+    )?;
+    Ok(())
+}
+
+fn set_pending_referral_rewards(conn: &Connection, owner: &Pubkey, pending: u64, slot: u64) -> Result<()> {
+    conn.execute(
+        "UPDATE users SET pending_referral_rewards = ?2, updated_slot = ?3 WHERE owner = ?1",
+        params![key(owner), pending, slot],
+    )?;
+    Ok(())
+}
+
+fn insert_odds(conn: &Connection, table: &ProbabilityTable, version: u32, slot: u64) -> Result<()> {
+    for index in 0..table.seed_count.min(16) {
+        let i = index as usize;
+        conn.execute(
+            "INSERT OR REPLACE INTO pack_odds
+                (seed_index, grow_power, probability_percentage, revealed, table_version, updated_slot)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                index,
+                table.grow_powers[i],
+                table.probability_percentages[i] as f64,
+                table.is_seed_revealed(index),
+                version,
+                slot
+            ],
+        )?;
+    }
+    Ok(())
+}
+
+fn key(pubkey: &Pubkey) -> String {
+    pubkey.to_string()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::{AccountSerialize, Event};
+    use base64::Engine;
+    use farm_game::events::*;
+    use farm_game::state::{RandomnessSource, SeedType};
+    
+    fn encode(data: &[u8]) -> String {
+        base64::engine::general_purpose::STANDARD.encode(data)
+    }
+    
+    /// One transaction's logs, as `getTransaction` returns them
+    fn logs_line(slot: u64, signature: &str, events: &[Vec<u8>]) -> String {
+        let program = farm_game::ID.to_string();
+        let mut logs = vec![format!("Program {} invoke [1]", program)];
+        logs.extend(events.iter().map(|data| format!("Program data: {}", encode(data))));
+        logs.push(format!("Program {} success", program));
+        serde_json::json!({ "kind": "logs", "slot": slot, "signature": signature, "logs": logs }).to_string()
+    }
+    
+    fn count(indexer: &Indexer, sql: &str) -> i64 {
+        indexer.connection().query_row(sql, [], |row| row.get(0)).unwrap()
+    }
+    
+    #[test]
+    fn test_replay_builds_projection() {
+        let alice = Pubkey::new_unique();
+        let bob = Pubkey::new_unique();
+        let carol = Pubkey::new_unique();
+        let farm = Pubkey::new_unique();
+        
+        let log = [
+            logs_line(1, "init", &[
+                UserInitializedEvent { user: alice, referrer: None, initialized_by: alice, timestamp: 10 }.data(),
+                UserInitializedEvent { user: bob, referrer: Some(alice), initialized_by: bob, timestamp: 11 }.data(),
+                UserInitializedEvent { user: carol, referrer: Some(bob), initialized_by: carol, timestamp: 12 }.data(),
+                ProbabilityTableInitializedEvent {
+                    admin: alice,
+                    version: 1,
+                    seed_count: 8,
+                    expected_value: 0,
+                    timestamp: 12,
+                }
+                .data(),
+            ]),
+            logs_line(2, "farm", &[FarmSpacePurchasedEvent {
+                user: bob,
+                farm_space: farm,
+                sol_paid: 500_000_000,
+                initial_seed: Pubkey::new_unique(),
+                grow_power: 100,
+                timestamp: 20,
+            }
+            .data()]),
+            logs_line(3, "pack", &[SeedPackPurchasedEvent {
+                user: bob,
+                pack_id: 1,
+                quantity: 2,
+                weed_burned: 600,
+                vrf_fee_paid: 0,
+                randomness_source: RandomnessSource::SlotHash,
+                commit_slot: 3,
+                timestamp: 30,
+            }
+            .data()]),
+            logs_line(4, "open", &[SeedPackOpenedEvent {
+                user: bob,
+                pack_id: 1,
+                random_value: u64::MAX,
+                seeds: vec![
+                    GeneratedSeed { seed_id: 1, seed_type: SeedType::Seed1, grow_power: 100 },
+                    GeneratedSeed { seed_id: 2, seed_type: SeedType::Seed2, grow_power: 180 },
+                ],
+                timestamp: 40,
+            }
+            .data()]),
+            logs_line(5, "plant", &[
+                SeedsPlantedEvent {
+                    user: bob,
+                    farm_space: farm,
+                    seed_ids: vec![1],
+                    grow_power_added: 100,
+                    farm_grow_power: 200,
+                    user_grow_power: 200,
+                    timestamp: 50,
+                }
+                .data(),
+                SeedsDiscardedEvent { user: bob, seed_ids: vec![2], rent_recovered: 1, storage_count: 0, timestamp: 50 }
+                    .data(),
+            ]),
+            logs_line(6, "claim", &[
+                ReferralRewardAccruedEvent {
+                    referrer: alice,
+                    referee: bob,
+                    level: 1,
+                    amount: 10,
+                    pending_total: 10,
+                    timestamp: 60,
+                }
+                .data(),
+                RewardClaimedEvent {
+                    user: bob,
+                    farming_reward: 100,
+                    claimant_farming_reward: 85,
+                    level1_reward: 10,
+                    level2_reward: 5,
+                    referral_rewards: 0,
+                    total_reward: 85,
+                    paid_amount: 85,
+                    fee_funded: false,
+                    timestamp: 60,
+                }
+                .data(),
+            ]),
+        ]
+        .join("\n");
+        
+        let mut indexer = Indexer::open_in_memory().unwrap();
+        let stats = indexer.replay(log.as_bytes()).unwrap();
+        assert_eq!(stats, ReplayStats { events: 11, duplicates: 0, unknown: 0, accounts: 0 });
+        
+        // Users and the two-level referral tree
+        assert_eq!(count(&indexer, "SELECT COUNT(*) FROM users"), 3);
+        let (level1, level2): (String, String) = indexer
+            .connection()
+            .query_row("SELECT level1, level2 FROM referral_tree WHERE user = ?1", [carol.to_string()], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .unwrap();
+        assert_eq!((level1, level2), (bob.to_string(), alice.to_string()));
+        
+        // Farm and seed inventory, including the initial seed with id 0
+        assert_eq!(count(&indexer, "SELECT total_grow_power FROM farms"), 200);
+        assert_eq!(count(&indexer, "SELECT seed_count FROM farms"), 2);
+        assert_eq!(count(&indexer, "SELECT COUNT(*) FROM seeds WHERE status = 'planted'"), 2);
+        assert_eq!(count(&indexer, "SELECT COUNT(*) FROM seeds WHERE status = 'discarded'"), 1);
+        let random_value: String =
+            indexer.connection().query_row("SELECT random_value FROM packs", [], |row| row.get(0)).unwrap();
+        assert_eq!(random_value, u64::MAX.to_string());
+        
+        // Claim history and referral balances
+        assert_eq!(count(&indexer, "SELECT paid_amount FROM claims"), 85);
+        let pending = format!("SELECT pending_referral_rewards FROM users WHERE owner = '{}'", alice);
+        assert_eq!(count(&indexer, &pending), 10);
+        let claimed = format!("SELECT total_claimed FROM users WHERE owner = '{}'", bob);
+        assert_eq!(count(&indexer, &claimed), 85);
+        
+        // Odds vs outcomes: two seeds came from packs, one of each type
+        let table = ProbabilityTable::init_table_1();
+        assert_eq!(count(&indexer, "SELECT COUNT(*) FROM pack_odds"), table.seed_count as i64);
+        let (expected, observed, observed_percentage): (f64, i64, f64) = indexer
+            .connection()
+            .query_row(
+                "SELECT expected_percentage, observed, observed_percentage FROM pack_odds_vs_outcomes WHERE seed_index = 1",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .unwrap();
+        assert!(expected > 0.0);
+        assert_eq!((observed, observed_percentage), (1, 50.0));
+        
+        // Replaying the same log is idempotent
+        let stats = indexer.replay(log.as_bytes()).unwrap();
+        assert_eq!(stats, ReplayStats { events: 0, duplicates: 11, unknown: 0, accounts: 0 });
+        assert_eq!(count(&indexer, "SELECT COUNT(*) FROM claims"), 1);
+        assert_eq!(count(&indexer, &claimed), 85);
+    }
+    
+    #[test]
+    fn test_account_snapshot_overrides_state() {
+        let owner = Pubkey::new_unique();
+        let address = Pubkey::new_unique();
+        let farm = FarmSpace {
+            owner,
+            level: 3,
+            capacity: 12,
+            seed_count: 4,
+            total_grow_power: 900,
+            version: 1,
+            reserve: [0; 31],
+        };
+        let mut data = Vec::new();
+        farm.try_serialize(&mut data).unwrap();
+        
+        let log = [
+            logs_line(1, "farm", &[FarmSpacePurchasedEvent {
+                user: owner,
+                farm_space: address,
+                sol_paid: 1,
+                initial_seed: Pubkey::new_unique(),
+                grow_power: 100,
+                timestamp: 1,
+            }
+            .data()]),
+            serde_json::json!({ "kind": "account", "slot": 2, "pubkey": address.to_string(), "data": encode(&data) })
+                .to_string(),
+            // Payloads with unknown discriminators are counted and skipped
+            serde_json::json!({ "kind": "event", "slot": 3, "data": encode(&[7u8; 12]) }).to_string(),
+        ]
+        .join("\n");
+        
+        let mut indexer = Indexer::open_in_memory().unwrap();
+        let stats = indexer.replay(log.as_bytes()).unwrap();
+        assert_eq!(stats, ReplayStats { events: 1, duplicates: 0, unknown: 1, accounts: 1 });
+        
+        let (level, capacity, grow_power, updated_slot): (u8, u8, u64, u64) = indexer
+            .connection()
+            .query_row("SELECT level, capacity, total_grow_power, updated_slot FROM farms", [], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+            })
+            .unwrap();
+        assert_eq!((level, capacity, grow_power, updated_slot), (3, 12, 900, 2));
+    }
+}
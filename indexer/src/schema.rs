@@ -0,0 +1,155 @@
+//! SQLite schema of the projection
+//! Pubkeys are stored as base58 text, token and lamport amounts as INTEGER (u64 values above i64::MAX do not occur in practice)
+
+/// Tables and views, created idempotently on open
+pub const SCHEMA: &str = r#"
+CREATE TABLE IF NOT EXISTS users (
+    owner TEXT PRIMARY KEY,
+    referrer TEXT,
+    initialized_by TEXT,
+    has_farm_space INTEGER NOT NULL DEFAULT 0,
+    total_grow_power INTEGER NOT NULL DEFAULT 0,
+    total_packs_purchased INTEGER NOT NULL DEFAULT 0,
+    pending_referral_rewards INTEGER NOT NULL DEFAULT 0,
+    total_claimed INTEGER NOT NULL DEFAULT 0,
+    created_at INTEGER,
+    updated_slot INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE TABLE IF NOT EXISTS farms (
+    address TEXT PRIMARY KEY,
+    owner TEXT NOT NULL UNIQUE,
+    level INTEGER NOT NULL,
+    capacity INTEGER NOT NULL,
+    seed_count INTEGER NOT NULL DEFAULT 0,
+    total_grow_power INTEGER NOT NULL DEFAULT 0,
+    sol_paid INTEGER NOT NULL DEFAULT 0,
+    created_at INTEGER,
+    updated_slot INTEGER NOT NULL DEFAULT 0
+);
+
+-- Seed ids are per-owner (every farm's initial seed has id 0), so the key is (owner, seed_id)
+CREATE TABLE IF NOT EXISTS seeds (
+    owner TEXT NOT NULL,
+    seed_id INTEGER NOT NULL,
+    seed_type INTEGER NOT NULL,
+    grow_power INTEGER NOT NULL,
+    pack_id INTEGER,
+    status TEXT NOT NULL CHECK (status IN ('stored', 'planted', 'discarded')),
+    farm_space TEXT,
+    created_at INTEGER,
+    updated_slot INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (owner, seed_id)
+);
+
+CREATE TABLE IF NOT EXISTS packs (
+    owner TEXT NOT NULL,
+    pack_id INTEGER NOT NULL,
+    quantity INTEGER NOT NULL,
+    weed_burned INTEGER NOT NULL DEFAULT 0,
+    vrf_fee_paid INTEGER NOT NULL DEFAULT 0,
+    randomness_source TEXT,
+    commit_slot INTEGER,
+    purchased_at INTEGER,
+    opened_at INTEGER,
+    random_value TEXT,
+    PRIMARY KEY (owner, pack_id)
+);
+
+CREATE TABLE IF NOT EXISTS referral_rewards (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    referrer TEXT NOT NULL,
+    referee TEXT,
+    level INTEGER NOT NULL,
+    amount INTEGER NOT NULL,
+    pending_total INTEGER NOT NULL,
+    slot INTEGER NOT NULL,
+    timestamp INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS claims (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    owner TEXT NOT NULL,
+    farming_reward INTEGER NOT NULL,
+    claimant_farming_reward INTEGER NOT NULL,
+    level1_reward INTEGER NOT NULL,
+    level2_reward INTEGER NOT NULL,
+    referral_rewards INTEGER NOT NULL,
+    total_reward INTEGER NOT NULL,
+    paid_amount INTEGER NOT NULL,
+    fee_funded INTEGER NOT NULL,
+    signature TEXT,
+    slot INTEGER NOT NULL,
+    timestamp INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS invites (
+    address TEXT PRIMARY KEY,
+    inviter TEXT NOT NULL,
+    code_hash TEXT,
+    uses INTEGER NOT NULL DEFAULT 0,
+    max_uses INTEGER NOT NULL,
+    created_at INTEGER
+);
+
+-- Current odds of the probability table, one row per seed type
+CREATE TABLE IF NOT EXISTS pack_odds (
+    seed_index INTEGER PRIMARY KEY,
+    grow_power INTEGER NOT NULL,
+    probability_percentage REAL NOT NULL,
+    revealed INTEGER NOT NULL DEFAULT 1,
+    table_version INTEGER,
+    updated_slot INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE TABLE IF NOT EXISTS global_stats (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    total_grow_power INTEGER NOT NULL,
+    total_farm_spaces INTEGER NOT NULL,
+    total_supply INTEGER NOT NULL,
+    current_rewards_per_second INTEGER NOT NULL,
+    total_burned INTEGER NOT NULL,
+    total_packs_sold INTEGER NOT NULL,
+    total_seeds_generated INTEGER NOT NULL,
+    total_sol_collected INTEGER NOT NULL,
+    supply_exhausted INTEGER NOT NULL,
+    updated_slot INTEGER NOT NULL
+);
+
+-- Every decoded event, including the ones the projection does not interpret
+CREATE TABLE IF NOT EXISTS event_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    slot INTEGER NOT NULL,
+    signature TEXT,
+    event_index INTEGER NOT NULL,
+    name TEXT NOT NULL
+);
+
+-- Replaying the same transaction twice must not double-count it
+CREATE UNIQUE INDEX IF NOT EXISTS event_log_position ON event_log (signature, event_index)
+    WHERE signature IS NOT NULL;
+
+CREATE INDEX IF NOT EXISTS seeds_status ON seeds (owner, status);
+CREATE INDEX IF NOT EXISTS claims_owner ON claims (owner);
+CREATE INDEX IF NOT EXISTS referral_rewards_referrer ON referral_rewards (referrer);
+
+-- Level 1 and level 2 referrers of every user
+CREATE VIEW IF NOT EXISTS referral_tree AS
+    SELECT u.owner AS user, u.referrer AS level1, p.referrer AS level2
+    FROM users u
+    LEFT JOIN users p ON p.owner = u.referrer
+    WHERE u.referrer IS NOT NULL;
+
+-- Advertised odds against the seed types actually generated by opened packs
+CREATE VIEW IF NOT EXISTS pack_odds_vs_outcomes AS
+    SELECT o.seed_index,
+           o.grow_power,
+           o.probability_percentage AS expected_percentage,
+           COUNT(s.seed_id) AS observed,
+           CASE WHEN t.total = 0 THEN 0.0
+                ELSE COUNT(s.seed_id) * 100.0 / t.total END AS observed_percentage
+    FROM pack_odds o
+    CROSS JOIN (SELECT COUNT(*) AS total FROM seeds WHERE pack_id IS NOT NULL) t
+    LEFT JOIN seeds s ON s.seed_type = o.seed_index AND s.pack_id IS NOT NULL
+    GROUP BY o.seed_index;
+"#;
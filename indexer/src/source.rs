@@ -0,0 +1,221 @@
+//! JSON-lines input records
+//! Each line is one record captured from RPC (transaction logs or an account snapshot),
+//! so a log file can be replayed offline without a node
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::{Context, Result};
+use base64::Engine;
+use serde::Deserialize;
+use std::io::BufRead;
+
+/// Prefix Anchor's `emit!` writes before the base64 event payload
+const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+
+/// One line of the replay log
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Record {
+    /// Transaction logs as returned by `getTransaction` / `logsSubscribe`
+    Logs {
+        slot: u64,
+        signature: String,
+        logs: Vec<String>,
+    },
+    /// A single base64 event payload (discriminator + borsh data)
+    Event {
+        slot: u64,
+        #[serde(default)]
+        signature: Option<String>,
+        data: String,
+    },
+    /// Base64 account data as returned by `getAccountInfo`
+    Account {
+        slot: u64,
+        pubkey: String,
+        data: String,
+    },
+}
+
+/// Event payload with its position in the log
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawEvent {
+    pub slot: u64,
+    pub signature: Option<String>,
+    /// Index of the event within its transaction
+    pub index: u32,
+    pub data: Vec<u8>,
+}
+
+/// Account data with its address
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawAccount {
+    pub slot: u64,
+    pub pubkey: Pubkey,
+    pub data: Vec<u8>,
+}
+
+/// Decoded input, ready to apply to the projection
+#[derive(Debug, Clone, PartialEq)]
+pub enum Input {
+    Event(RawEvent),
+    Account(RawAccount),
+}
+
+impl Record {
+    /// Parse one JSON line
+    pub fn parse(line: &str) -> Result<Self> {
+        serde_json::from_str(line).context("invalid replay record")
+    }
+    
+    /// Split the record into inputs emitted by `program_id`
+    pub fn into_inputs(self, program_id: &Pubkey) -> Result<Vec<Input>> {
+        match self {
+            Record::Logs { slot, signature, logs } => Ok(program_data(&logs, program_id)?
+                .into_iter()
+                .enumerate()
+                .map(|(index, data)| Input::Event(RawEvent {
+                    slot,
+                    signature: Some(signature.clone()),
+                    index: index as u32,
+                    data,
+                }))
+                .collect()),
+            Record::Event { slot, signature, data } => Ok(vec![Input::Event(RawEvent {
+                slot,
+                signature,
+                index: 0,
+                data: decode_base64(&data)?,
+            })]),
+            Record::Account { slot, pubkey, data } => Ok(vec![Input::Account(RawAccount {
+                slot,
+                pubkey: pubkey.parse().map_err(|_| anyhow::anyhow!("invalid pubkey {}", pubkey))?,
+                data: decode_base64(&data)?,
+            })]),
+        }
+    }
+}
+
+/// Event payloads emitted by `program_id` in a transaction's logs
+/// Tracks the invocation stack so data logged by other programs (CPI targets) is skipped
+pub fn program_data(logs: &[String], program_id: &Pubkey) -> Result<Vec<Vec<u8>>> {
+    let program = program_id.to_string();
+    let mut stack: Vec<&str> = Vec::new();
+    let mut payloads = Vec::new();
+    
+    for line in logs {
+        if let Some(rest) = line.strip_prefix("Program ") {
+            let mut words = rest.split_whitespace();
+            let id = words.next().unwrap_or_default();
+            match words.next() {
+                Some("invoke") => {
+                    stack.push(id);
+                    continue;
+                }
+                Some("success") => {
+                    stack.pop();
+                    continue;
+                }
+                // A failed invocation fails the whole transaction, so its events never took effect
+                Some("failed:") => return Ok(Vec::new()),
+                _ => {}
+            }
+        }
+        
+        if let Some(data) = line.strip_prefix(PROGRAM_DATA_PREFIX) {
+            if stack.last() == Some(&program.as_str()) {
+                payloads.push(decode_base64(data)?);
+            }
+        }
+    }
+    
+    Ok(payloads)
+}
+
+/// Read every record of a JSON-lines file (blank lines are skipped)
+pub fn read_records(reader: impl BufRead) -> Result<Vec<Record>> {
+    let mut records = Vec::new();
+    for (number, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        records.push(Record::parse(&line).with_context(|| format!("line {}", number + 1))?);
+    }
+    Ok(records)
+}
+
+fn decode_base64(data: &str) -> Result<Vec<u8>> {
+    base64::engine::general_purpose::STANDARD
+        .decode(data.trim())
+        .context("invalid base64 payload")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn encode(data: &[u8]) -> String {
+        base64::engine::general_purpose::STANDARD.encode(data)
+    }
+    
+    #[test]
+    fn test_program_data_filters_by_invoking_program() {
+        let program = farm_game::ID;
+        let other = Pubkey::new_unique();
+        let logs = vec![
+            format!("Program {} invoke [1]", program),
+            format!("Program data: {}", encode(b"first")),
+            format!("Program {} invoke [2]", other),
+            format!("Program data: {}", encode(b"cpi")),
+            format!("Program {} success", other),
+            "Program log: hello".to_string(),
+            format!("Program data: {}", encode(b"second")),
+            format!("Program {} success", program),
+        ];
+        
+        let payloads = program_data(&logs, &program).unwrap();
+        assert_eq!(payloads, vec![b"first".to_vec(), b"second".to_vec()]);
+        
+        // Nothing is attributed to a program that never ran
+        assert!(program_data(&logs, &Pubkey::new_unique()).unwrap().is_empty());
+        
+        // Events of a failed transaction are dropped
+        let mut failed = logs.clone();
+        failed.push(format!("Program {} failed: custom program error: 0x1770", program));
+        assert!(program_data(&failed, &program).unwrap().is_empty());
+    }
+    
+    #[test]
+    fn test_record_parsing() {
+        let program = farm_game::ID;
+        let line = format!(
+            r#"{{"kind":"logs","slot":7,"signature":"sig","logs":["Program {} invoke [1]","Program data: {}","Program data: {}","Program {} success"]}}"#,
+            program, encode(b"a"), encode(b"b"), program
+        );
+        let inputs = Record::parse(&line).unwrap().into_inputs(&program).unwrap();
+        assert_eq!(inputs.len(), 2);
+        assert_eq!(inputs[1], Input::Event(RawEvent {
+            slot: 7,
+            signature: Some("sig".to_string()),
+            index: 1,
+            data: b"b".to_vec(),
+        }));
+        
+        let owner = Pubkey::new_unique();
+        let line = format!(r#"{{"kind":"account","slot":9,"pubkey":"{}","data":"{}"}}"#, owner, encode(b"acct"));
+        let inputs = Record::parse(&line).unwrap().into_inputs(&program).unwrap();
+        assert_eq!(inputs, vec![Input::Account(RawAccount { slot: 9, pubkey: owner, data: b"acct".to_vec() })]);
+        
+        // Blank lines are skipped; malformed lines report their line number
+        let log = format!("{}\n\n{{\"kind\":\"event\",\"slot\":1,\"data\":\"{}\"}}\n", line, encode(b"e"));
+        assert_eq!(read_records(log.as_bytes()).unwrap().len(), 2);
+        let error = read_records("{}\n".as_bytes()).unwrap_err();
+        assert!(format!("{:#}", error).contains("line 1"));
+        
+        // Bad base64 and pubkeys are rejected
+        let line = r#"{"kind":"account","slot":1,"pubkey":"not-a-key","data":""}"#;
+        assert!(Record::parse(line).unwrap().into_inputs(&program).is_err());
+        let line = r#"{"kind":"event","slot":1,"data":"***"}"#;
+        assert!(Record::parse(line).unwrap().into_inputs(&program).is_err());
+    }
+}
@@ -1,5 +1,5 @@
-/// Game constants and configuration values
-/// Centralized location for all game balance and system parameters
+//! Game constants and configuration values
+//! Centralized location for all game balance and system parameters
 
 // ===== ECONOMIC CONSTANTS =====
 // ゲーム経済の基盤となる重要な定数群
@@ -186,9 +186,113 @@ pub const MAX_BATCH_PLANT_SIZE: usize = 25;
 /// Maximum batch remove size
 pub const MAX_BATCH_REMOVE_SIZE: usize = 25;
 
+/// Maximum token accounts per withheld fee harvest
+pub const MAX_BATCH_HARVEST_SIZE: usize = 20;
+
 /// Time tolerance for future time validation (seconds)
 pub const TIME_TOLERANCE: i64 = 30;
 
+// ===== PAUSE FLAGS =====
+// Config.paused_subsystems のビット定義
+// 障害発生時に該当サブシステムのみを停止するために使用
+
+/// シードパック購入
+pub const PAUSE_PURCHASE: u8 = 1 << 0;
+
+/// シードパック開封
+pub const PAUSE_OPEN: u8 = 1 << 1;
+
+/// 種の植え付け・除去
+pub const PAUSE_PLANT: u8 = 1 << 2;
+
+/// 報酬請求
+pub const PAUSE_CLAIM: u8 = 1 << 3;
+
+/// 招待コード作成・使用
+pub const PAUSE_INVITE: u8 = 1 << 4;
+
+/// 全サブシステムのビットマスク
+pub const PAUSE_ALL_SUBSYSTEMS: u8 = PAUSE_PURCHASE | PAUSE_OPEN | PAUSE_PLANT | PAUSE_CLAIM | PAUSE_INVITE;
+
+// ===== TIMELOCK CONSTANTS =====
+// 経済パラメータ変更のタイムロック設定
+// プレイヤーが価格変更を事前に把握できるようにするための猶予期間
+
+/// デフォルトのタイムロック期間（24時間）
+#[cfg(not(feature = "localnet"))]
+pub const DEFAULT_CONFIG_CHANGE_DELAY: i64 = 24 * 60 * 60;
+
+/// ローカルネット用ビルドのタイムロック期間（テストで待機できるよう下限と同じ）
+#[cfg(feature = "localnet")]
+pub const DEFAULT_CONFIG_CHANGE_DELAY: i64 = MIN_CONFIG_CHANGE_DELAY;
+
+/// タイムロック期間の下限（1時間）。0を許すとタイムロック自体を無効化できてしまう
+#[cfg(not(feature = "localnet"))]
+pub const MIN_CONFIG_CHANGE_DELAY: i64 = 60 * 60;
+
+/// ローカルネット用ビルドのタイムロック期間の下限（5秒）
+#[cfg(feature = "localnet")]
+pub const MIN_CONFIG_CHANGE_DELAY: i64 = 5;
+
+/// タイムロック期間の上限（30日）
+pub const MAX_CONFIG_CHANGE_DELAY: i64 = 30 * 24 * 60 * 60;
+
+// ===== ROLE CONSTANTS =====
+// RoleRegistry のロールビット定義
+// 管理者は常に全ロールを保持しているものとして扱う
+
+/// 価格管理者：シードパック・農場スペース価格変更のキュー登録
+pub const ROLE_PRICING_MANAGER: u8 = 1 << 0;
+
+/// シードキュレーター：シード公開・値更新・確率テーブル変更
+pub const ROLE_SEED_CURATOR: u8 = 1 << 1;
+
+/// 停止権限者：システム全体・サブシステムの停止と再開
+pub const ROLE_PAUSER: u8 = 1 << 2;
+
+/// 招待発行者：オペレーターと同等の招待上限とユーザー直接初期化
+pub const ROLE_INVITE_ISSUER: u8 = 1 << 3;
+
+/// 全ロールのビットマスク
+pub const ALL_ROLES: u8 = ROLE_PRICING_MANAGER | ROLE_SEED_CURATOR | ROLE_PAUSER | ROLE_INVITE_ISSUER;
+
+/// RoleRegistry に登録可能な最大メンバー数
+pub const MAX_ROLE_MEMBERS: usize = 16;
+
+// ===== MULTISIG CONSTANTS =====
+// 組み込みM-of-N承認フローの設定
+
+/// マルチシグ署名者の最大数
+pub const MAX_MULTISIG_SIGNERS: usize = 10;
+
+/// 提案に含められる最大アカウント数
+pub const MAX_PROPOSAL_ACCOUNTS: usize = 24;
+
+/// 提案に含められる命令データの最大バイト数
+pub const MAX_PROPOSAL_DATA_LEN: usize = 512;
+
+// ===== COMMIT-REVEAL RANDOMNESS CONSTANTS =====
+// SlotHashesを用いたシードパックのコミット・リビール設定
+
+/// 購入スロットからコミット先スロットまでの距離
+/// 購入時点でハッシュが未確定のスロットにコミットするため1以上が必要
+pub const SEED_PACK_COMMIT_DELAY_SLOTS: u64 = 2;
+
+/// SlotHashes sysvarが保持するエントリ数（約3.4分）
+/// ユーザー影響：コミット先スロットがこれより古くなると開封不可
+pub const SLOT_HASHES_MAX_ENTRIES: usize = 512;
+
+// ===== ORACLE RANDOMNESS CONSTANTS =====
+// Switchboard On-Demandランダムネスアカウントの検証設定
+
+/// Switchboard On-Demandプログラム（メインネット）
+/// Config.oracle_program の初期値。ローカル検証ではスタンドインプログラムに切り替える
+pub const SWITCHBOARD_ON_DEMAND_PROGRAM_ID: anchor_lang::prelude::Pubkey = anchor_lang::pubkey!("SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv");
+
+/// 購入時に受け入れるオラクルコミットの最大経過スロット数
+/// 設計思想：古いコミットを流用した結果の先読みを防止
+pub const ORACLE_COMMIT_MAX_AGE_SLOTS: u64 = 10;
+
 // ===== PDA SEEDS =====
 // Program Derived Address（プログラム派生アドレス）の種子定数
 // セキュリティ設計：決定論的アドレス生成による一意性とアクセス制御
@@ -246,8 +350,35 @@ pub mod seeds {
     /// Meteora config PDA seed
     pub const METEORA_CONFIG: &[u8] = b"meteora_config";
     
+    /// Pending config change PDA seed prefix
+    pub const PENDING_CONFIG_CHANGE: &[u8] = b"pending_config_change";
+    
+    /// Role registry PDA seed
+    pub const ROLE_REGISTRY: &[u8] = b"role_registry";
+    
+    /// Multisig PDA seed
+    pub const MULTISIG: &[u8] = b"multisig";
+    
+    /// Multisig signer PDA seed (becomes Config.admin once handed over)
+    pub const MULTISIG_SIGNER: &[u8] = b"multisig_signer";
+    
+    /// Multisig proposal PDA seed prefix
+    pub const PROPOSAL: &[u8] = b"proposal";
+    
+    /// Randomness beacon PDA seed
+    pub const RANDOMNESS_BEACON: &[u8] = b"randomness_beacon";
+    
     /// Pyth entropy request PDA seed prefix
     pub const ENTROPY_REQUEST: &[u8] = b"entropy_request";
+    
+    /// Probability table PDA seed
+    pub const PROBABILITY_TABLE: &[u8] = b"probability_table";
+    
+    /// Farm level config PDA seed
+    pub const FARM_LEVEL_CONFIG: &[u8] = b"farm_level_config";
+    
+    /// Emission schedule PDA seed
+    pub const EMISSION_SCHEDULE: &[u8] = b"emission_schedule";
 }
 
 // ===== TOKEN CONSTANTS =====
@@ -261,6 +392,27 @@ pub const WEED_SYMBOL: &str = "WEED";
 /// WEED token name
 pub const WEED_NAME: &str = "Weed Token";
 
+/// Initial WEED transfer fee (2.00%, matches TRADING_FEE_PERCENTAGE)
+pub const TRANSFER_FEE_BASIS_POINTS: u16 = 200;
+
+/// Initial maximum transfer fee per transfer (1000 WEED)
+pub const TRANSFER_FEE_MAXIMUM: u64 = 1_000 * 1_000_000;
+
+/// Epochs before a new transfer fee takes effect (fixed by Token 2022)
+pub const TRANSFER_FEE_EPOCH_DELAY: u64 = 2;
+
+/// Highest transfer fee the admin can set (10%)
+pub const MAX_TRANSFER_FEE_BASIS_POINTS: u16 = 1_000;
+
+/// Maximum length of the WEED metadata URI
+pub const MAX_METADATA_URI_LENGTH: usize = 200;
+
+/// Maximum length of an additional WEED metadata key
+pub const MAX_METADATA_KEY_LENGTH: usize = 32;
+
+/// Maximum length of an additional WEED metadata value
+pub const MAX_METADATA_VALUE_LENGTH: usize = 200;
+
 // ===== TIME CONSTANTS =====
 // ゲーム内時間システムの基盤定数
 // Solanaブロックチェーンの正確なタイムスタンプを活用
@@ -293,6 +445,13 @@ pub const BASIS_POINTS_TOTAL: u16 = 10000;
 /// Percentage to basis points multiplier
 pub const PERCENTAGE_TO_BASIS_POINTS: u16 = 100;
 
+/// Fixed-point scale of `GlobalStats.acc_reward_per_grow_power`
+pub const ACC_REWARD_PRECISION: u128 = 1_000_000_000_000;
+
+/// Maximum segments in the emission schedule
+/// Also bounds `ConfigChange::EmissionSchedule`, which must fit `ConfigChange::MAX_SIZE`
+pub const MAX_EMISSION_SEGMENTS: usize = 16;
+
 // ===== CALCULATION HELPERS =====
 
 /// Helper functions for common calculations
@@ -332,12 +491,12 @@ impl crate::state::SeedType {
         
         for (i, &threshold) in SEED_PROBABILITY_THRESHOLDS.iter().enumerate() {
             if value < threshold {
-                return unsafe { std::mem::transmute(i as u8) };
+                return unsafe { std::mem::transmute::<u8, Self>(i as u8) };
             }
         }
         
         // Fallback to highest rarity (6th seed type in 0-based indexing is index 5)
-        unsafe { std::mem::transmute(5u8) }
+        unsafe { std::mem::transmute::<u8, Self>(5u8) }
     }
 }
 
@@ -345,20 +504,20 @@ impl crate::state::SeedType {
 
 /// Validate quantity is within acceptable range
 pub fn validate_quantity(quantity: u8) -> bool {
-    quantity >= MIN_QUANTITY && quantity <= MAX_SEED_PACK_QUANTITY
+    (MIN_QUANTITY..=MAX_SEED_PACK_QUANTITY).contains(&quantity)
 }
 
 /// Validate farm level (currently level 5, future expansion to 10)
 pub fn validate_farm_level(level: u8) -> bool {
-    level >= 1 && level <= 5
+    (1..=5).contains(&level)
 }
 
 /// Validate invite code format
 pub fn validate_invite_code(code: &[u8; 12]) -> bool {
     code.iter().all(|&b| {
-        (b >= b'A' && b <= b'Z') || 
-        (b >= b'a' && b <= b'z') || 
-        (b >= b'0' && b <= b'9')
+        b.is_ascii_uppercase() || 
+        b.is_ascii_lowercase() || 
+        b.is_ascii_digit()
     })
 }
 
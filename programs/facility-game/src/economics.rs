@@ -1,9 +1,10 @@
-/// Economics module for game calculations
-/// Centralized location for all economic formulas and calculations
+//! Economics module for game calculations
+//! Centralized location for all economic formulas and calculations
 
 use anchor_lang::prelude::*;
 use crate::constants::*;
 use crate::error::GameError;
+use crate::state::EmissionSegment;
 
 // ===== REWARD CALCULATIONS =====
 
@@ -80,7 +81,7 @@ pub fn calculate_rewards_across_halving(
         // Apply halvings by repeatedly dividing by 2
         current_rate = base_rate;
         for _ in 0..halvings_passed.min(63) { // Prevent excessive halvings
-            current_rate = current_rate / 2;
+            current_rate /= 2;
         }
         current_halving_time = next_halving_time + (halvings_passed * halving_interval);
     }
@@ -106,7 +107,7 @@ pub fn calculate_rewards_across_halving(
         // Move to next period
         start_time = end_time;
         if start_time >= current_halving_time {
-            current_rate = current_rate / 2; // Halve the rate
+            current_rate /= 2; // Halve the rate
             current_halving_time += halving_interval;
         }
     }
@@ -114,6 +115,127 @@ pub fn calculate_rewards_across_halving(
     Ok(total_reward)
 }
 
+// ===== REWARD ACCUMULATOR =====
+// MasterChef-style accounting: `acc_reward_per_grow_power` grows by each
+// period's emission / total grow power, and a user's reward is
+// grow_power × acc - reward_debt. Payouts then depend only on who held grow
+// power during each period, not on when anyone claims. Grow power changes
+// checkpoint the accrued reward first (see `settle_pending_farming_rewards`).
+
+/// Total emission between two times, integrating the piecewise-constant schedule exactly
+/// Nothing is emitted before the first segment; the last segment runs indefinitely
+pub fn calculate_emission(
+    segments: &[EmissionSegment],
+    from_time: i64,
+    to_time: i64,
+) -> Result<u64> {
+    let mut total = 0u64;
+    
+    for (i, segment) in segments.iter().enumerate() {
+        let segment_end = segments.get(i + 1).map_or(to_time, |next| next.start_time.min(to_time));
+        let start = segment.start_time.max(from_time);
+        if segment_end <= start {
+            continue;
+        }
+        
+        let segment_emission = segment.rate.checked_mul((segment_end - start) as u64)
+            .ok_or(GameError::CalculationOverflow)?;
+        total = total.checked_add(segment_emission)
+            .ok_or(GameError::CalculationOverflow)?;
+    }
+    
+    Ok(total)
+}
+
+/// Emission rate in effect at `time` (0 before the first segment)
+pub fn current_emission_rate(segments: &[EmissionSegment], time: i64) -> u64 {
+    segments.iter()
+        .rev()
+        .find(|segment| segment.start_time <= time)
+        .map_or(0, |segment| segment.rate)
+}
+
+/// Number of rate changes (segment starts) in `(from, to]`, i.e. halvings crossed
+pub fn count_rate_changes(segments: &[EmissionSegment], from: i64, to: i64) -> u32 {
+    segments.iter()
+        .filter(|segment| segment.start_time > from && segment.start_time <= to)
+        .count() as u32
+}
+
+/// Express a halving config as schedule segments starting at `start_time`
+/// Matches `calculate_rewards_across_halving` until the rate reaches zero or
+/// `MAX_EMISSION_SEGMENTS` is used up (the last converted rate then continues)
+pub fn halving_emission_segments(
+    base_rate: u64,
+    next_halving_time: i64,
+    halving_interval: i64,
+    start_time: i64,
+) -> Result<Vec<EmissionSegment>> {
+    require!(halving_interval > 0, GameError::InvalidConfig);
+    
+    // Rate in effect at `start_time` and the halving after it
+    let mut rate = base_rate;
+    let mut halving_time = next_halving_time;
+    if start_time >= next_halving_time {
+        let halvings_passed = (start_time - next_halving_time) / halving_interval + 1;
+        rate = base_rate.checked_shr(halvings_passed.min(63) as u32).unwrap_or(0);
+        halving_time = halvings_passed.checked_mul(halving_interval)
+            .and_then(|offset| next_halving_time.checked_add(offset))
+            .ok_or(GameError::CalculationOverflow)?;
+    }
+    
+    let mut segments = vec![EmissionSegment { start_time, rate }];
+    while rate > 0 && segments.len() < MAX_EMISSION_SEGMENTS {
+        rate /= 2;
+        segments.push(EmissionSegment { start_time: halving_time, rate });
+        halving_time = halving_time.checked_add(halving_interval)
+            .ok_or(GameError::CalculationOverflow)?;
+    }
+    
+    Ok(segments)
+}
+
+/// Advance the accumulator by `emission` shared over `total_grow_power`
+/// Nothing accrues while no grow power is planted
+pub fn calculate_acc_reward_per_grow_power(
+    acc_reward_per_grow_power: u128,
+    emission: u64,
+    total_grow_power: u64,
+) -> Result<u128> {
+    if total_grow_power == 0 {
+        return Ok(acc_reward_per_grow_power);
+    }
+    
+    let increment = (emission as u128).checked_mul(ACC_REWARD_PRECISION)
+        .ok_or(GameError::CalculationOverflow)?
+        / total_grow_power as u128;
+    
+    acc_reward_per_grow_power.checked_add(increment)
+        .ok_or(GameError::CalculationOverflow.into())
+}
+
+/// Reward debt for `grow_power` at the current accumulator (grow_power × acc)
+pub fn calculate_reward_debt(grow_power: u64, acc_reward_per_grow_power: u128) -> Result<u128> {
+    (grow_power as u128).checked_mul(acc_reward_per_grow_power)
+        .ok_or(GameError::CalculationOverflow.into())
+}
+
+/// Reward a user has accrued since their debt was last reset
+pub fn calculate_pending_reward(
+    grow_power: u64,
+    acc_reward_per_grow_power: u128,
+    reward_debt: u128,
+) -> Result<u64> {
+    // The accumulator only grows and grow power is fixed between checkpoints,
+    // so the debt never exceeds grow_power × acc
+    let accrued = calculate_reward_debt(grow_power, acc_reward_per_grow_power)?
+        .checked_sub(reward_debt)
+        .ok_or(GameError::CalculationOverflow)?
+        / ACC_REWARD_PRECISION;
+    
+    u64::try_from(accrued).map_err(|_| GameError::CalculationOverflow.into())
+}
+
 // ===== REFERRAL CALCULATIONS =====
 
 /// Calculate referral rewards for Level 1 and Level 2
@@ -147,6 +269,29 @@ pub fn calculate_referral_reward_for_level(base_reward: u64, level: u8) -> Resul
     Ok(reward)
 }
 
+/// Split a claim payment that may fall short of the amount owed
+/// Pending referral rewards are paid first, then the claimant's share of `farming_reward`
+/// Returns (referral rewards paid, farming reward covered by the payment)
+pub fn split_claim_payment(
+    paid: u64,
+    pending_referral_rewards: u64,
+    farming_reward: u64,
+    claimant_amount: u64,
+) -> Result<(u64, u64)> {
+    let paid_referral_rewards = paid.min(pending_referral_rewards);
+    let paid_claimant_amount = (paid - paid_referral_rewards).min(claimant_amount);
+    
+    // The claimant share is a fixed fraction of the farming reward, so scale it back up
+    let paid_farming_reward = if paid_claimant_amount == claimant_amount {
+        farming_reward
+    } else {
+        let scaled = farming_reward as u128 * paid_claimant_amount as u128 / claimant_amount as u128;
+        u64::try_from(scaled).map_err(|_| GameError::CalculationOverflow)?
+    };
+    
+    Ok((paid_referral_rewards, paid_farming_reward))
+}
+
 // ===== UPGRADE CALCULATIONS =====
 
 /// Get upgrade cost for a specific farm level
@@ -240,8 +385,8 @@ pub fn calculate_seed_roi(seed_index: usize) -> f32 {
 // ===== CAPACITY CALCULATIONS =====
 
 /// Calculate total capacity for all farm levels
-pub fn calculate_total_possible_capacity() -> u8 {
-    FARM_CAPACITIES.iter().sum()
+pub fn calculate_total_possible_capacity() -> u16 {
+    FARM_CAPACITIES.iter().map(|&c| c as u16).sum()
 }
 
 /// Calculate capacity utilization percentage
@@ -330,8 +475,8 @@ pub fn calculate_grow_power_share_bps(
 // ===== PROBABILITY CALCULATIONS =====
 
 /// Calculate cumulative probability for seed types
-pub fn calculate_cumulative_probabilities() -> [f32; 9] {
-    let mut cumulative = [0.0f32; 9];
+pub fn calculate_cumulative_probabilities() -> [f32; SEED_PROBABILITIES.len()] {
+    let mut cumulative = [0.0f32; SEED_PROBABILITIES.len()];
     let mut sum = 0.0f32;
     
     for (i, &prob) in SEED_PROBABILITIES.iter().enumerate() {
@@ -475,7 +620,7 @@ mod tests {
     #[test]
     fn test_capacity_calculations() {
         let total_capacity = calculate_total_possible_capacity();
-        assert_eq!(total_capacity, FARM_CAPACITIES.iter().sum::<u8>());
+        assert_eq!(total_capacity, 391); // 4+6+10+16+25+35+50+65+80+100
 
         // Test utilization calculation
         assert_eq!(calculate_capacity_utilization(5, 10), 50.0);
@@ -489,7 +634,7 @@ mod tests {
         let cumulative = calculate_cumulative_probabilities();
         
         // Last element should be close to 100%
-        assert!((cumulative[8] - 100.0).abs() < 0.01);
+        assert!((cumulative[cumulative.len() - 1] - 100.0).abs() < 0.01);
         
         // Should be in ascending order
         for i in 1..cumulative.len() {
@@ -498,8 +643,8 @@ mod tests {
 
         // Test rarity scores
         let rarity1 = calculate_rarity_score(0); // Seed1 (common)
-        let rarity9 = calculate_rarity_score(8); // Seed9 (rare)
-        assert!(rarity9 > rarity1); // Rarer seeds have higher rarity scores
+        let rarity6 = calculate_rarity_score(5); // Seed6 (rare)
+        assert!(rarity6 > rarity1); // Rarer seeds have higher rarity scores
     }
 
     #[test]
@@ -643,4 +788,141 @@ mod tests {
         assert!(reward > 0, "Should calculate rewards for many halving periods");
         assert!(reward < 20000, "Should converge due to halving effect");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_reward_accumulator() {
+        // Two users: A holds 400 grow power throughout, B plants 100 at t=100
+        let schedule = [EmissionSegment { start_time: 0, rate: 10 }];
+        let mut acc = 0u128;
+        let mut debt_a = calculate_reward_debt(400, acc).unwrap();
+        
+        // t=0..100: only A (400 total)
+        acc = calculate_acc_reward_per_grow_power(acc, calculate_emission(&schedule, 0, 100).unwrap(), 400).unwrap();
+        let debt_b = calculate_reward_debt(100, acc).unwrap();
+        
+        // t=100..200: A and B (500 total)
+        acc = calculate_acc_reward_per_grow_power(acc, calculate_emission(&schedule, 100, 200).unwrap(), 500).unwrap();
+        
+        let reward_a = calculate_pending_reward(400, acc, debt_a).unwrap();
+        let reward_b = calculate_pending_reward(100, acc, debt_b).unwrap();
+        assert_eq!(reward_a, 1000 + 800);
+        assert_eq!(reward_b, 200);
+        // Exact emission regardless of claim order
+        assert_eq!(reward_a + reward_b, 2000);
+        
+        // A claims and resets debt: nothing more is pending
+        debt_a = calculate_reward_debt(400, acc).unwrap();
+        assert_eq!(calculate_pending_reward(400, acc, debt_a).unwrap(), 0);
+        
+        // Uneven shares round down, so payouts never exceed emission
+        let acc_uneven = calculate_acc_reward_per_grow_power(0, 1000, 300).unwrap();
+        assert_eq!(calculate_pending_reward(300, acc_uneven, 0).unwrap(), 999);
+        
+        // No grow power: accumulator does not move
+        assert_eq!(calculate_acc_reward_per_grow_power(acc, 1000, 0).unwrap(), acc);
+    }
+    
+    #[test]
+    fn test_emission_schedule() {
+        // Bootstrap boost, decay steps, tail
+        let schedule = [
+            EmissionSegment { start_time: 100, rate: 50 },
+            EmissionSegment { start_time: 200, rate: 20 },
+            EmissionSegment { start_time: 300, rate: 10 },
+            EmissionSegment { start_time: 400, rate: 1 },
+        ];
+        
+        // Nothing before the first segment
+        assert_eq!(calculate_emission(&schedule, 0, 100).unwrap(), 0);
+        // Within one segment and across all of them
+        assert_eq!(calculate_emission(&schedule, 120, 180).unwrap(), 60 * 50);
+        assert_eq!(calculate_emission(&schedule, 50, 450).unwrap(), 5000 + 2000 + 1000 + 50);
+        // Split anywhere, the parts add up to the whole
+        assert_eq!(
+            calculate_emission(&schedule, 50, 250).unwrap() + calculate_emission(&schedule, 250, 450).unwrap(),
+            calculate_emission(&schedule, 50, 450).unwrap()
+        );
+        // The tail runs indefinitely; empty ranges emit nothing
+        assert_eq!(calculate_emission(&schedule, 1000, 2000).unwrap(), 1000);
+        assert_eq!(calculate_emission(&schedule, 450, 450).unwrap(), 0);
+        assert_eq!(calculate_emission(&schedule, 450, 400).unwrap(), 0);
+        assert!(calculate_emission(&[EmissionSegment { start_time: 0, rate: u64::MAX }], 0, 2).is_err());
+        
+        // Rate in effect at a point in time
+        assert_eq!(current_emission_rate(&schedule, 99), 0);
+        assert_eq!(current_emission_rate(&schedule, 100), 50);
+        assert_eq!(current_emission_rate(&schedule, 299), 20);
+        assert_eq!(current_emission_rate(&schedule, 10_000), 1);
+        
+        // Rate changes crossed between two times
+        assert_eq!(count_rate_changes(&schedule, 120, 180), 0);
+        assert_eq!(count_rate_changes(&schedule, 120, 200), 1);
+        assert_eq!(count_rate_changes(&schedule, 0, 10_000), 4);
+        assert_eq!(count_rate_changes(&schedule, 400, 10_000), 0);
+    }
+    
+    #[test]
+    fn test_halving_emission_segments() {
+        let (base_rate, next_halving_time, interval) = (100, 1000, 500);
+        
+        // Converted schedule emits what the halving formula pays a holder of all grow power
+        for start_time in [0, 999, 1000, 1700] {
+            let segments = halving_emission_segments(base_rate, next_halving_time, interval, start_time).unwrap();
+            assert_eq!(segments[0].start_time, start_time);
+            assert!(crate::validation::economic_validation::validate_emission_schedule(&segments).is_ok());
+            for end_time in [start_time + 1, 2600, 5000] {
+                assert_eq!(
+                    calculate_emission(&segments, start_time, end_time).unwrap(),
+                    calculate_rewards_across_halving(1, 1, base_rate, start_time, end_time, next_halving_time, interval).unwrap()
+                );
+            }
+        }
+        
+        // 100 -> 50 -> 25 -> 12 -> 6 -> 3 -> 1 -> 0
+        let segments = halving_emission_segments(base_rate, next_halving_time, interval, 0).unwrap();
+        assert_eq!(segments.len(), 8);
+        assert_eq!(segments[1], EmissionSegment { start_time: 1000, rate: 50 });
+        assert_eq!(segments[7], EmissionSegment { start_time: 4000, rate: 0 });
+        
+        // Large rates are cut off at the segment limit
+        let segments = halving_emission_segments(u64::MAX, next_halving_time, interval, 0).unwrap();
+        assert_eq!(segments.len(), MAX_EMISSION_SEGMENTS);
+        assert!(halving_emission_segments(base_rate, next_halving_time, 0, 0).is_err());
+    }
+    
+    #[test]
+    fn test_pending_reward_checkpoint() {
+        // User holds 100 from acc=0, accumulator reaches 5 per grow power
+        let acc = 5 * ACC_REWARD_PRECISION;
+        let debt = calculate_reward_debt(100, 0).unwrap();
+        let checkpoint = calculate_pending_reward(100, acc, debt).unwrap();
+        assert_eq!(checkpoint, 500);
+        
+        // Planting 50 more right before claiming: the new power only earns from now
+        let debt = calculate_reward_debt(150, acc).unwrap();
+        assert_eq!(calculate_pending_reward(150, acc, debt).unwrap(), 0);
+        
+        // Accumulator reaches 7: 150 × 2 on top of the 500 checkpoint
+        let acc = 7 * ACC_REWARD_PRECISION;
+        assert_eq!(checkpoint + calculate_pending_reward(150, acc, debt).unwrap(), 800);
+        
+        // Debt above grow_power × acc cannot happen and is rejected
+        assert!(calculate_pending_reward(0, acc, debt).is_err());
+    }
+    
+    #[test]
+    fn test_claim_payment_split() {
+        // Farming reward 1000 with an 80% claimant share, plus 300 pending referral rewards
+        assert_eq!(split_claim_payment(1100, 300, 1000, 800).unwrap(), (300, 1000));
+        
+        // Short payment: referral rewards first, then half the claimant share
+        assert_eq!(split_claim_payment(700, 300, 1000, 800).unwrap(), (300, 500));
+        assert_eq!(split_claim_payment(200, 300, 1000, 800).unwrap(), (200, 0));
+        
+        // Empty vault: everything stays pending
+        assert_eq!(split_claim_payment(0, 300, 1000, 800).unwrap(), (0, 0));
+        
+        // Nothing owed to the claimant
+        assert_eq!(split_claim_payment(0, 0, 0, 0).unwrap(), (0, 0));
+    }
+}
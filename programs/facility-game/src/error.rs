@@ -197,4 +197,164 @@ pub enum GameError {
     
     #[msg("Duplicate seed ID found in batch operation")]
     DuplicateSeedId,
-}
\ No newline at end of file
+    
+    // Emergency pause errors
+    #[msg("System is paused - user actions are temporarily disabled")]
+    SystemPaused,
+    
+    #[msg("System is already paused")]
+    SystemAlreadyPaused,
+    
+    #[msg("System is not paused")]
+    SystemNotPaused,
+    
+    #[msg("This subsystem is paused - try again later")]
+    SubsystemPaused,
+    
+    #[msg("Invalid subsystem pause flags")]
+    InvalidPauseFlags,
+    
+    // Authority handover errors
+    #[msg("No pending authority handover")]
+    NoPendingHandover,
+    
+    #[msg("Signer is not the pending authority")]
+    NotPendingAuthority,
+    
+    #[msg("Authority handover proposal has expired")]
+    HandoverExpired,
+    
+    #[msg("Invalid handover expiry")]
+    InvalidHandoverExpiry,
+    
+    // Timelock errors
+    #[msg("Timelock has not elapsed for this config change")]
+    TimelockNotElapsed,
+    
+    #[msg("Invalid timelock delay")]
+    InvalidTimelockDelay,
+    
+    #[msg("Probability table account required for this config change")]
+    ProbabilityTableRequired,
+    
+    #[msg("Emission schedule and global stats accounts required for this config change")]
+    EmissionScheduleRequired,
+    
+    // Role errors
+    #[msg("Signer does not hold the required role")]
+    MissingRole,
+    
+    #[msg("Invalid role flags")]
+    InvalidRoleFlags,
+    
+    #[msg("Role registry is full")]
+    RoleRegistryFull,
+    
+    #[msg("Member not found in role registry")]
+    RoleMemberNotFound,
+    
+    // Multisig errors
+    #[msg("Invalid multisig signers or threshold")]
+    InvalidMultisigConfig,
+    
+    #[msg("Signer is not a multisig member")]
+    NotMultisigSigner,
+    
+    #[msg("Proposal already approved by this signer")]
+    AlreadyApproved,
+    
+    #[msg("Proposal has not reached the approval threshold")]
+    ThresholdNotMet,
+    
+    #[msg("Proposal has already been executed")]
+    ProposalAlreadyExecuted,
+    
+    #[msg("Proposal is stale - multisig membership changed since creation")]
+    ProposalStale,
+    
+    #[msg("Invalid proposal instruction")]
+    InvalidProposal,
+    
+    // Account migration errors
+    #[msg("Account is already at the current layout version")]
+    AccountAlreadyMigrated,
+    
+    #[msg("Account layout version is newer than this program supports")]
+    UnsupportedAccountVersion,
+    
+    // Commit-reveal randomness errors
+    #[msg("Seed pack commit slot has not passed yet")]
+    RandomnessNotReady,
+    
+    #[msg("Commit slot hash is no longer available in SlotHashes")]
+    SlotHashExpired,
+    
+    #[msg("Invalid SlotHashes sysvar data")]
+    InvalidSlotHashes,
+    
+    #[msg("Seed pack uses a legacy layout and must be migrated first")]
+    SeedPackNotMigrated,
+    
+    #[msg("Commit slot hash is still available in SlotHashes")]
+    CommitNotExpired,
+    
+    // Oracle randomness errors
+    #[msg("Oracle program does not match the configured oracle program")]
+    InvalidOracleProgram,
+    
+    #[msg("Oracle randomness commit is stale or already revealed")]
+    StaleOracleCommit,
+    
+    #[msg("Oracle randomness account was re-committed after purchase")]
+    OracleSeedSlotMismatch,
+    
+    // Randomness source errors
+    #[msg("Randomness beacon account is required for the admin beacon source")]
+    RandomnessBeaconRequired,
+    
+    #[msg("Randomness beacon value must be non-zero")]
+    InvalidBeaconValue,
+    
+    // Admin randomness beacon errors
+    #[msg("Admin randomness beacon is only available in localnet builds")]
+    AdminBeaconDisabled,
+    
+    #[msg("A later beacon round replaced the committed one: recommit the pack")]
+    BeaconRoundMissed,
+    
+    // Fee vault errors
+    #[msg("Treasury account does not match the fee pool treasury")]
+    InvalidTreasuryAccount,
+    
+    // Transfer fee errors
+    #[msg("Invalid reward mint or missing transfer fee extension")]
+    InvalidRewardMint,
+    
+    #[msg("Treasury token account must be a reward token account owned by the fee pool treasury")]
+    InvalidTreasuryTokenAccount,
+    
+    // Token metadata errors
+    #[msg("Invalid token metadata field or value")]
+    InvalidMetadataField,
+    
+    // Account migration errors
+    #[msg("User state uses a legacy layout and must be migrated first")]
+    UserStateNotMigrated,
+    
+    // Emission schedule errors
+    #[msg("Emission schedule segments must be non-empty, within the limit and in ascending start time order")]
+    InvalidEmissionSchedule,
+    
+    // Supply exhaustion errors
+    #[msg("Supply is exhausted: pass the fee pool and its reward token account")]
+    FeePoolVaultRequired,
+    
+    #[msg("Fee pool vault must be a reward token account owned by the fee pool")]
+    InvalidFeePoolVault,
+    
+    #[msg("Reward supply is not exhausted yet")]
+    SupplyNotExhausted,
+    
+    #[msg("Reward supply is exhausted: withheld fees go to the fee pool vault")]
+    SupplyExhausted,
+}
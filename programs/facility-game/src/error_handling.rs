@@ -1,5 +1,5 @@
-/// Centralized error handling utilities
-/// Provides consistent error handling patterns across the application
+//! Centralized error handling utilities
+//! Provides consistent error handling patterns across the application
 
 use anchor_lang::prelude::*;
 use crate::error::GameError;
@@ -0,0 +1,520 @@
+//! Anchor events emitted by state-changing instructions
+//! Indexers decode these from transaction logs instead of parsing `msg!` output
+
+use anchor_lang::prelude::*;
+use crate::state::{ConfigChange, RandomnessSource, SeedType};
+
+// ===== USERS & FARM SPACES =====
+
+/// Event emitted when a user account is created (by an issuer or an invite code)
+#[event]
+pub struct UserInitializedEvent {
+    pub user: Pubkey,
+    pub referrer: Option<Pubkey>,
+    pub initialized_by: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a user buys their farm space (with the starter seed planted)
+#[event]
+pub struct FarmSpacePurchasedEvent {
+    pub user: Pubkey,
+    pub farm_space: Pubkey,
+    pub sol_paid: u64,
+    pub initial_seed: Pubkey,
+    pub grow_power: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when pack purchases auto-upgrade a farm space
+#[event]
+pub struct FarmSpaceUpgradedEvent {
+    pub user: Pubkey,
+    pub farm_space: Pubkey,
+    pub level: u8,
+    pub capacity: u8,
+    pub total_packs_purchased: u32,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a farm space is moved onto the current level config
+#[event]
+pub struct FarmSpaceMigratedEvent {
+    pub user: Pubkey,
+    pub farm_space: Pubkey,
+    pub old_level: u8,
+    pub level: u8,
+    pub old_capacity: u8,
+    pub capacity: u8,
+    pub timestamp: i64,
+}
+
+// ===== SEED PACKS & SEEDS =====
+
+/// Event emitted when seed packs are bought (WEED burned, randomness committed)
+#[event]
+pub struct SeedPackPurchasedEvent {
+    pub user: Pubkey,
+    pub pack_id: u64,
+    pub quantity: u8,
+    pub weed_burned: u64,
+    pub vrf_fee_paid: u64,
+    pub randomness_source: RandomnessSource,
+    pub commit_slot: u64,
+    pub timestamp: i64,
+}
+
+/// One seed produced by opening a pack
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct GeneratedSeed {
+    pub seed_id: u64,
+    pub seed_type: SeedType,
+    pub grow_power: u64,
+}
+
+/// Event emitted when a seed pack is opened, with one entry per generated seed
+#[event]
+pub struct SeedPackOpenedEvent {
+    pub user: Pubkey,
+    pub pack_id: u64,
+    pub random_value: u64,
+    pub seeds: Vec<GeneratedSeed>,
+    pub timestamp: i64,
+}
+
+/// Event emitted when an expired seed pack is committed to a new slot
+#[event]
+pub struct SeedPackRecommittedEvent {
+    pub pack_id: u64,
+    pub owner: Pubkey,
+    pub old_commit_slot: u64,
+    pub new_commit_slot: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when seeds are planted (one or a batch)
+#[event]
+pub struct SeedsPlantedEvent {
+    pub user: Pubkey,
+    pub farm_space: Pubkey,
+    pub seed_ids: Vec<u64>,
+    pub grow_power_added: u64,
+    pub farm_grow_power: u64,
+    pub user_grow_power: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when seeds are removed from a farm space (one or a batch)
+#[event]
+pub struct SeedsRemovedEvent {
+    pub user: Pubkey,
+    pub farm_space: Pubkey,
+    pub seed_ids: Vec<u64>,
+    pub grow_power_removed: u64,
+    pub farm_grow_power: u64,
+    pub user_grow_power: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when stored seeds are discarded (one or a batch)
+#[event]
+pub struct SeedsDiscardedEvent {
+    pub user: Pubkey,
+    pub seed_ids: Vec<u64>,
+    pub rent_recovered: u64,
+    pub storage_count: u32,
+    pub timestamp: i64,
+}
+
+// ===== REWARDS & REFERRALS =====
+
+/// Event emitted for every claim, with the reward breakdown
+#[event]
+pub struct RewardClaimedEvent {
+    pub user: Pubkey,
+    /// Farming reward before the referral split
+    pub farming_reward: u64,
+    /// Claimant's share of the farming reward
+    pub claimant_farming_reward: u64,
+    /// Referrer shares of the paid farming reward
+    pub level1_reward: u64,
+    pub level2_reward: u64,
+    /// Pending referral rewards paid with this claim
+    pub referral_rewards: u64,
+    /// Amount owed (claimant share plus referral rewards)
+    pub total_reward: u64,
+    /// Amount actually minted or transferred; the rest stays pending
+    pub paid_amount: u64,
+    /// Paid from the fee pool vault instead of minted
+    pub fee_funded: bool,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a referral reward is added to a referrer's pending balance
+#[event]
+pub struct ReferralRewardAccruedEvent {
+    pub referrer: Pubkey,
+    pub referee: Pubkey,
+    pub level: u8,
+    pub amount: u64,
+    pub pending_total: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when checking pending referral rewards
+#[event]
+pub struct PendingReferralRewardsEvent {
+    pub user: Pubkey,
+    pub pending_amount: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted by the claim that mints the last of TOTAL_WEED_SUPPLY
+#[event]
+pub struct SupplyExhaustedEvent {
+    pub final_claimer: Pubkey,
+    pub final_amount: u64,
+    pub total_minted: u64,
+    pub timestamp: i64,
+}
+
+// ===== INVITES =====
+
+/// Event emitted when an invite code is created (only its hash is published)
+#[event]
+pub struct InviteCodeCreatedEvent {
+    pub inviter: Pubkey,
+    pub invite_account: Pubkey,
+    pub code_hash: [u8; 32],
+    pub max_uses: u32,
+    pub timestamp: i64,
+}
+
+/// Event emitted when an invite code onboards a new user
+#[event]
+pub struct InviteCodeUsedEvent {
+    pub invitee: Pubkey,
+    pub inviter: Pubkey,
+    pub invite_account: Pubkey,
+    pub uses: u32,
+    pub max_uses: u32,
+    pub timestamp: i64,
+}
+
+// ===== CONFIGURATION =====
+
+/// Event emitted when the global config is created
+#[event]
+pub struct ConfigInitializedEvent {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub base_rate: u64,
+    pub halving_interval: i64,
+    pub protocol_referral_address: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Event emitted when the admin updates direct config settings (None = unchanged)
+#[event]
+pub struct ConfigUpdatedEvent {
+    pub admin: Pubkey,
+    pub treasury: Option<Pubkey>,
+    pub max_invite_limit: Option<u8>,
+    pub timestamp: i64,
+}
+
+/// Event emitted when the admin updates WEED token metadata
+#[event]
+pub struct TokenMetadataUpdatedEvent {
+    pub admin: Pubkey,
+    pub field: String,
+    pub value: String,
+    pub timestamp: i64,
+}
+
+/// Event emitted when the farm level config is created or replaced
+#[event]
+pub struct FarmLevelConfigUpdatedEvent {
+    pub admin: Pubkey,
+    pub max_level: u8,
+    pub capacities: Vec<u8>,
+    pub upgrade_thresholds: Vec<u32>,
+    pub timestamp: i64,
+}
+
+/// Event emitted when the probability table is created
+#[event]
+pub struct ProbabilityTableInitializedEvent {
+    pub admin: Pubkey,
+    pub version: u32,
+    pub seed_count: u8,
+    pub expected_value: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a seed type is revealed (`revealed`) or its values change
+#[event]
+pub struct ProbabilityTableUpdatedEvent {
+    pub authority: Pubkey,
+    pub seed_index: u8,
+    pub revealed: bool,
+    pub grow_power: u64,
+    pub probability_percentage: f32,
+    pub seed_count: u8,
+    pub expected_value: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when the emission schedule is created
+#[event]
+pub struct EmissionScheduleInitializedEvent {
+    pub admin: Pubkey,
+    pub segment_count: u8,
+    pub start_time: i64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when the admin publishes a beacon round
+#[event]
+pub struct RandomnessBeaconPublishedEvent {
+    pub round: u64,
+    pub value: [u8; 32],
+    pub timestamp: i64,
+}
+
+/// Event emitted when collected fees are paid out to the treasury
+#[event]
+pub struct FeesWithdrawnEvent {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+// ===== AUTHORITY, ROLES & PAUSE =====
+
+/// Event emitted when a new admin is proposed
+#[event]
+pub struct AdminProposedEvent {
+    pub current_admin: Pubkey,
+    pub pending_admin: Pubkey,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when the admin handover completes
+#[event]
+pub struct AdminChangedEvent {
+    pub previous_admin: Pubkey,
+    pub new_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a new operator is proposed
+#[event]
+pub struct OperatorProposedEvent {
+    pub current_operator: Pubkey,
+    pub pending_operator: Pubkey,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when the operator handover completes
+#[event]
+pub struct OperatorChangedEvent {
+    pub previous_operator: Pubkey,
+    pub new_operator: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Event emitted when roles are granted
+#[event]
+pub struct RoleGrantedEvent {
+    pub admin: Pubkey,
+    pub member: Pubkey,
+    pub roles: u8,
+    pub member_roles: u8,
+    pub timestamp: i64,
+}
+
+/// Event emitted when roles are revoked
+#[event]
+pub struct RoleRevokedEvent {
+    pub admin: Pubkey,
+    pub member: Pubkey,
+    pub roles: u8,
+    pub member_roles: u8,
+    pub timestamp: i64,
+}
+
+/// Event emitted when the global pause is toggled
+#[event]
+pub struct SystemPauseChangedEvent {
+    pub authority: Pubkey,
+    pub is_paused: bool,
+    pub reason: u8,
+    pub timestamp: i64,
+}
+
+/// Event emitted when subsystem pause bits change
+#[event]
+pub struct SubsystemPauseChangedEvent {
+    pub authority: Pubkey,
+    pub flags: u8,
+    pub paused: bool,
+    pub paused_subsystems: u8,
+    pub timestamp: i64,
+}
+
+// ===== TIMELOCK & MULTISIG =====
+
+/// Event emitted when a config change is queued
+#[event]
+pub struct ConfigChangeQueuedEvent {
+    pub change_id: u64,
+    pub proposer: Pubkey,
+    pub change: ConfigChange,
+    pub executable_at: i64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a queued config change is applied
+#[event]
+pub struct ConfigChangeExecutedEvent {
+    pub change_id: u64,
+    pub executor: Pubkey,
+    pub change: ConfigChange,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a queued config change is cancelled
+#[event]
+pub struct ConfigChangeCancelledEvent {
+    pub change_id: u64,
+    pub cancelled_by: Pubkey,
+    pub change: ConfigChange,
+    pub timestamp: i64,
+}
+
+/// Event emitted when multisig membership is set
+#[event]
+pub struct MultisigSignersChangedEvent {
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+    pub owner_set_seqno: u32,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a proposal is created
+#[event]
+pub struct ProposalCreatedEvent {
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub data: Vec<u8>,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a proposal receives an approval
+#[event]
+pub struct ProposalApprovedEvent {
+    pub proposal_id: u64,
+    pub signer: Pubkey,
+    pub approvals: u8,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a proposal is executed
+#[event]
+pub struct ProposalExecutedEvent {
+    pub proposal_id: u64,
+    pub executor: Pubkey,
+    pub timestamp: i64,
+}
+
+// ===== TRANSFER FEES =====
+
+/// Event emitted when withheld fees are harvested into the mint
+#[event]
+pub struct WithheldFeesHarvestedEvent {
+    pub harvester: Pubkey,
+    pub accounts: u8,
+    pub amount: u64,
+    pub mint_withheld: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when withheld fees are withdrawn to the treasury
+#[event]
+pub struct WithheldFeesWithdrawnEvent {
+    pub admin: Pubkey,
+    pub treasury_token_account: Pubkey,
+    pub amount: u64,
+    pub total_withdrawn: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when withheld fees are withdrawn to the fee pool vault
+#[event]
+pub struct WithheldFeesToFeePoolEvent {
+    pub payer: Pubkey,
+    pub fee_pool_token_account: Pubkey,
+    pub amount: u64,
+    pub total_withdrawn: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when the admin changes the WEED transfer fee
+#[event]
+pub struct TransferFeeUpdatedEvent {
+    pub admin: Pubkey,
+    pub old_percentage: u8,
+    pub old_basis_points: u16,
+    pub basis_points: u16,
+    pub maximum_fee: u64,
+    pub effective_epoch: u64,
+    pub timestamp: i64,
+}
+
+// ===== MIGRATIONS =====
+
+/// Event emitted when an account is migrated to a new layout version
+#[event]
+pub struct AccountMigratedEvent {
+    pub account: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+    pub old_len: u32,
+    pub new_len: u32,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::hash::hash;
+    use anchor_lang::Discriminator;
+    
+    #[test]
+    fn test_seed_pack_recommitted_event_layout() {
+        // Moving the event between modules must not change what indexers decode
+        let expected = hash(b"event:SeedPackRecommittedEvent").to_bytes();
+        assert_eq!(SeedPackRecommittedEvent::DISCRIMINATOR, &expected[..8]);
+        
+        let owner = Pubkey::new_unique();
+        let event = SeedPackRecommittedEvent {
+            pack_id: 7,
+            owner,
+            old_commit_slot: 100,
+            new_commit_slot: 250,
+            timestamp: -1,
+        };
+        let data = event.try_to_vec().unwrap();
+        assert_eq!(data.len(), 8 + 32 + 8 + 8 + 8);
+        assert_eq!(&data[..8], &7u64.to_le_bytes());
+        assert_eq!(&data[8..40], owner.as_ref());
+        assert_eq!(&data[40..48], &100u64.to_le_bytes());
+        assert_eq!(&data[48..56], &250u64.to_le_bytes());
+        assert_eq!(&data[56..], &(-1i64).to_le_bytes());
+    }
+}
@@ -3,6 +3,10 @@ use anchor_spl::token_2022::Token2022;
 use spl_token_2022::extension::transfer_fee::instruction::initialize_transfer_fee_config;
 // Removed unused DataV2 import
 use crate::state::*;
+use crate::events::*;
+use crate::error::GameError;
+use crate::constants::{ROLE_PAUSER, ROLE_SEED_CURATOR};
+use crate::validation::admin_validation::has_role;
 
 /// Context for initializing global configuration
 #[derive(Accounts)]
@@ -25,7 +29,7 @@ pub struct InitializeConfig<'info> {
 /// Context for creating reward token mint with transfer fee extension
 #[derive(Accounts)]
 pub struct CreateRewardMint<'info> {
-    /// CHECK: Reward mint account with calculated space for Token2022 + TransferFee extension
+    /// CHECK: Reward mint account with calculated space for Token2022 + TransferFee/MetadataPointer extensions
     #[account(
         mut,
         seeds = [b"reward_mint"],
@@ -49,24 +53,20 @@ pub struct CreateRewardMint<'info> {
     /// CHECK: transfer fee config authority PDA
     pub transfer_fee_config_authority: UncheckedAccount<'info>,
     
-    /// Withdraw withheld authority (treasury)
-    /// CHECK: This should be the treasury pubkey
+    /// Withdraw withheld authority (same as mint authority, see `withdraw_withheld_to_treasury`)
+    #[account(
+        seeds = [b"mint_authority"],
+        bump
+    )]
+    /// CHECK: withdraw withheld authority PDA
     pub withdraw_withheld_authority: UncheckedAccount<'info>,
     
-    /// Token metadata account (optional for test environments)
-    /// CHECK: Metadata account will be created by Metaplex if available
-    pub metadata_account: UncheckedAccount<'info>,
-    
     #[account(mut)]
     pub admin: Signer<'info>,
     
     pub token_program: Program<'info, Token2022>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
-    
-    /// Token Metadata Program (optional for test environments)
-    /// CHECK: Token metadata program
-    pub token_metadata_program: UncheckedAccount<'info>,
 }
 
 /// Initialize system configuration with secure defaults
@@ -94,7 +94,10 @@ pub fn initialize_config(
     config.farm_space_cost_sol = Config::DEFAULT_FARM_SPACE_COST;
     config.max_invite_limit = 5;
     config.trading_fee_percentage = 2;
-    config.protocol_referral_address = protocol_referral_address.unwrap_or(Pubkey::default());
+    config.transfer_fee_basis_points = crate::constants::TRANSFER_FEE_BASIS_POINTS;
+    config.pending_transfer_fee_basis_points = crate::constants::TRANSFER_FEE_BASIS_POINTS;
+    config.pending_transfer_fee_epoch = 0;
+    config.protocol_referral_address = protocol_referral_address.unwrap_or_default();
     
     // Initialize counters
     config.seed_counter = 0;
@@ -106,12 +109,44 @@ pub fn initialize_config(
     // Set operator address (with unlimited invites)
     config.operator = "43eUMnsf1QoFmE2ZkHxbXxZCAJht7pPpFFPUYicUYbjJ".parse::<Pubkey>().unwrap();
     
+    // System starts unpaused
+    config.is_paused = false;
+    config.pause_reason = 0;
+    config.paused_at = 0;
+    config.paused_subsystems = 0;
+    
+    // No authority handover in progress
+    config.pending_admin = None;
+    config.pending_admin_expires_at = 0;
+    config.pending_operator = None;
+    config.pending_operator_expires_at = 0;
+    
+    // Economic config changes go through the timelock
+    config.config_change_delay = crate::constants::DEFAULT_CONFIG_CHANGE_DELAY;
+    config.config_change_counter = 0;
+    
+    // Oracle randomness accounts must be owned by Switchboard On-Demand
+    config.oracle_program = crate::constants::SWITCHBOARD_ON_DEMAND_PROGRAM_ID;
+    config.randomness_source = RandomnessSource::Oracle;
+    
+    // New accounts start at the current layout version
+    config.version = Config::CURRENT_VERSION;
+    
     // Zero out reserved space
-    config.reserve = [0; 2];
+    config.reserve = [0; 1];
     
     msg!("System config initialized: rate={}, halving={}s, treasury={}", 
          config.base_rate, config.halving_interval, treasury);
     
+    emit!(ConfigInitializedEvent {
+        admin: config.admin,
+        treasury,
+        base_rate: config.base_rate,
+        halving_interval: config.halving_interval,
+        protocol_referral_address: config.protocol_referral_address,
+        timestamp: current_time,
+    });
+    
     Ok(())
 }
 
@@ -120,16 +155,37 @@ pub fn create_reward_mint(ctx: Context<CreateRewardMint>) -> Result<()> {
     use spl_token_2022::instruction;
     use anchor_spl::token_2022::spl_token_2022::extension::ExtensionType;
     use anchor_spl::token_2022::spl_token_2022::state::Mint;
+    use anchor_spl::token_2022_extensions::spl_pod::optional_keys::OptionalNonZeroPubkey;
+    use anchor_spl::token_2022_extensions::spl_token_metadata_interface::state::TokenMetadata;
+    use anchor_spl::token_2022_extensions::{
+        metadata_pointer_initialize,
+        token_metadata_initialize,
+        MetadataPointerInitialize,
+        TokenMetadataInitialize,
+    };
+    use crate::constants::{WEED_NAME, WEED_SYMBOL};
     
     let mint_authority = &ctx.accounts.mint_authority;
     let transfer_fee_config_authority = &ctx.accounts.transfer_fee_config_authority;
     let withdraw_withheld_authority = &ctx.accounts.withdraw_withheld_authority;
     
-    // Calculate required space for mint with transfer fee extension
+    // Calculate required space for mint with transfer fee and metadata pointer extensions
     let space = ExtensionType::try_calculate_account_len::<Mint>(
-        &[ExtensionType::TransferFeeConfig]
+        &[ExtensionType::TransferFeeConfig, ExtensionType::MetadataPointer]
     )?;
-    let lamports = ctx.accounts.rent.minimum_balance(space);
+    
+    // The metadata TLV entry is appended by Token 2022 after InitializeMint,
+    // so fund its rent up front (the URI starts empty, see `update_token_metadata`)
+    let token_metadata = TokenMetadata {
+        update_authority: OptionalNonZeroPubkey::try_from(Some(mint_authority.key()))?,
+        mint: ctx.accounts.reward_mint.key(),
+        name: WEED_NAME.to_string(),
+        symbol: WEED_SYMBOL.to_string(),
+        uri: String::new(),
+        additional_metadata: Vec::new(),
+    };
+    let metadata_space = token_metadata.tlv_size_of()?;
+    let lamports = ctx.accounts.rent.minimum_balance(space + metadata_space);
     
     // Create account instruction with proper space
     let create_account_ix = anchor_lang::solana_program::system_instruction::create_account(
@@ -141,8 +197,8 @@ pub fn create_reward_mint(ctx: Context<CreateRewardMint>) -> Result<()> {
     );
     
     // Initialize transfer fee extension
-    let transfer_fee_basis_points = 200u16; // 2.00%
-    let maximum_fee = 1_000_000_000u64; // 1000 WEED max fee
+    let transfer_fee_basis_points = crate::constants::TRANSFER_FEE_BASIS_POINTS; // 2.00%
+    let maximum_fee = crate::constants::TRANSFER_FEE_MAXIMUM; // 1000 WEED max fee
     
     let init_transfer_fee_ix = initialize_transfer_fee_config(
         &ctx.accounts.token_program.key(),
@@ -159,7 +215,7 @@ pub fn create_reward_mint(ctx: Context<CreateRewardMint>) -> Result<()> {
         &ctx.accounts.reward_mint.key(),
         &mint_authority.key(),
         Some(&mint_authority.key()),
-        6, // decimals
+        crate::constants::WEED_DECIMALS,
     )?;
     
     // Prepare PDA signer seeds
@@ -186,6 +242,19 @@ pub fn create_reward_mint(ctx: Context<CreateRewardMint>) -> Result<()> {
         ],
     )?;
     
+    // Point metadata at the mint itself (must precede InitializeMint)
+    metadata_pointer_initialize(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            MetadataPointerInitialize {
+                token_program_id: ctx.accounts.token_program.to_account_info(),
+                mint: ctx.accounts.reward_mint.to_account_info(),
+            },
+        ),
+        Some(mint_authority.key()),
+        Some(ctx.accounts.reward_mint.key()),
+    )?;
+    
     // Initialize mint
     anchor_lang::solana_program::program::invoke(
         &init_mint_ix,
@@ -196,15 +265,118 @@ pub fn create_reward_mint(ctx: Context<CreateRewardMint>) -> Result<()> {
         ],
     )?;
     
-    msg!("WEED token mint created with 2% transfer fee using SPL Token 2022: {}", ctx.accounts.reward_mint.key());
+    // Write name/symbol into the mint, signed by the mint authority PDA (also update authority)
+    let mint_authority_seeds: &[&[u8]] = &[b"mint_authority", &[ctx.bumps.mint_authority]];
+    token_metadata_initialize(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TokenMetadataInitialize {
+                program_id: ctx.accounts.token_program.to_account_info(),
+                metadata: ctx.accounts.reward_mint.to_account_info(),
+                update_authority: mint_authority.to_account_info(),
+                mint_authority: mint_authority.to_account_info(),
+                mint: ctx.accounts.reward_mint.to_account_info(),
+            },
+            &[mint_authority_seeds],
+        ),
+        token_metadata.name,
+        token_metadata.symbol,
+        token_metadata.uri,
+    )?;
     
-    // Skip metadata creation for now to avoid signing issues
-    msg!("Skipping metadata creation in test environment");
+    msg!("WEED token mint created with 2% transfer fee and on-chain metadata using SPL Token 2022: {}", ctx.accounts.reward_mint.key());
     
     Ok(())
 }
 
-// Removed unused create_token_metadata function
+// ===== TOKEN METADATA =====
+
+/// Context for updating WEED token metadata stored in the mint
+#[derive(Accounts)]
+pub struct UpdateTokenMetadata<'info> {
+    /// CHECK: Token 2022 mint account with TokenMetadata extension
+    #[account(
+        mut,
+        seeds = [b"reward_mint"],
+        bump
+    )]
+    pub reward_mint: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [b"mint_authority"],
+        bump
+    )]
+    /// CHECK: mint authority PDA (metadata update authority)
+    pub mint_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = config.admin == admin.key() @ GameError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    /// Pays for any extra rent when the metadata grows
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Update the WEED metadata URI or an additional field (admin only)
+pub fn update_token_metadata(ctx: Context<UpdateTokenMetadata>, field: String, value: String) -> Result<()> {
+    use anchor_spl::token_2022_extensions::spl_token_metadata_interface::state::Field;
+    use anchor_spl::token_2022_extensions::{token_metadata_update_field, TokenMetadataUpdateField};
+    
+    crate::validation::admin_validation::validate_token_metadata_update(&field, &value)?;
+    let metadata_field = match field.as_str() {
+        "uri" => Field::Uri,
+        _ => Field::Key(field.clone()),
+    };
+    
+    // Token 2022 reallocs the mint for the new metadata but does not fund it
+    let reward_mint_info = ctx.accounts.reward_mint.to_account_info();
+    let mut token_metadata = crate::utils::mint_token_metadata(&reward_mint_info)?;
+    let old_metadata_space = token_metadata.tlv_size_of()?;
+    token_metadata.update(metadata_field.clone(), value.clone());
+    let new_len = reward_mint_info.data_len()
+        .saturating_sub(old_metadata_space)
+        .checked_add(token_metadata.tlv_size_of()?)
+        .ok_or(GameError::CalculationOverflow)?;
+    crate::utils::top_up_rent_exemption(
+        &reward_mint_info,
+        &ctx.accounts.admin,
+        &ctx.accounts.system_program,
+        new_len,
+    )?;
+    
+    let mint_authority_seeds: &[&[u8]] = &[b"mint_authority", &[ctx.bumps.mint_authority]];
+    token_metadata_update_field(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TokenMetadataUpdateField {
+                program_id: ctx.accounts.token_program.to_account_info(),
+                metadata: reward_mint_info,
+                update_authority: ctx.accounts.mint_authority.to_account_info(),
+            },
+            &[mint_authority_seeds],
+        ),
+        metadata_field,
+        value.clone(),
+    )?;
+    
+    msg!("WEED metadata updated: {}={}", field, value);
+    
+    emit!(TokenMetadataUpdatedEvent {
+        admin: ctx.accounts.admin.key(),
+        field,
+        value,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
 
 /// Context for initializing global stats
 #[derive(Accounts)]
@@ -236,12 +408,47 @@ pub struct InitializeFeePool<'info> {
     )]
     pub fee_pool: Account<'info, FeePool>,
     
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = config.admin == admin.key() @ GameError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
     #[account(mut)]
     pub admin: Signer<'info>,
     
     pub system_program: Program<'info, System>,
 }
 
+/// Context for withdrawing collected fees to the treasury
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    #[account(
+        mut,
+        seeds = [b"fee_pool"],
+        bump
+    )]
+    pub fee_pool: Account<'info, FeePool>,
+    
+    /// Treasury receiving the fees
+    /// CHECK: Must match FeePool.treasury_address
+    #[account(
+        mut,
+        address = fee_pool.treasury_address @ GameError::InvalidTreasuryAccount
+    )]
+    pub treasury: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = config.admin == admin.key() @ GameError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    pub admin: Signer<'info>,
+}
+
 /// Initialize global game statistics
 pub fn initialize_global_stats(ctx: Context<InitializeGlobalStats>) -> Result<()> {
     let global_stats = &mut ctx.accounts.global_stats;
@@ -255,9 +462,22 @@ pub fn initialize_global_stats(ctx: Context<InitializeGlobalStats>) -> Result<()
     global_stats.total_supply = GlobalStats::INITIAL_TOTAL_SUPPLY;
     global_stats.current_rewards_per_second = Config::DEFAULT_BASE_RATE;
     global_stats.last_update_time = current_time;
+    global_stats.version = GlobalStats::CURRENT_VERSION;
+    
+    // Reward accumulator starts with the first planted grow power
+    global_stats.acc_reward_per_grow_power = 0;
+    global_stats.last_reward_time = current_time;
+    global_stats.supply_exhausted = false;
+    global_stats.total_emission_accrued = 0;
+    
+    // Activity counters
+    global_stats.total_burned = 0;
+    global_stats.total_packs_sold = 0;
+    global_stats.total_seeds_generated = 0;
+    global_stats.total_sol_collected = 0;
     
     // Zero out reserved space
-    global_stats.reserve = [0; 32];
+    global_stats.reserve = [0; 6];
     
     msg!("Global statistics initialized: supply={}, base_rate={}/sec", 
          global_stats.total_supply, global_stats.current_rewards_per_second);
@@ -274,15 +494,48 @@ pub fn initialize_fee_pool(ctx: Context<InitializeFeePool>, treasury_address: Pu
     fee_pool.accumulated_fees = 0;
     fee_pool.treasury_address = treasury_address;
     fee_pool.last_collection_time = current_time;
+    fee_pool.version = FeePool::CURRENT_VERSION;
+    fee_pool.accumulated_token_fees = 0;
     
     // Zero out reserved space
-    fee_pool.reserve = [0; 48];
+    fee_pool.reserve = [0; 39];
     
     msg!("Trading fee pool initialized: treasury={}", treasury_address);
     
     Ok(())
 }
 
+/// Withdraw all accumulated fees from the fee pool to its treasury (admin only)
+/// The fee pool PDA is program-owned, so lamports are moved directly; its rent reserve stays
+pub fn withdraw_fees(ctx: Context<WithdrawFees>) -> Result<()> {
+    let fee_pool_info = ctx.accounts.fee_pool.to_account_info();
+    let rent_minimum = Rent::get()?.minimum_balance(fee_pool_info.data_len());
+    let amount = crate::validation::economic_validation::validate_fee_withdrawal(
+        ctx.accounts.fee_pool.accumulated_fees,
+        fee_pool_info.lamports(),
+        rent_minimum,
+    )?;
+    
+    **fee_pool_info.try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.treasury.try_borrow_mut_lamports()? += amount;
+    
+    let current_time = Clock::get()?.unix_timestamp;
+    let fee_pool = &mut ctx.accounts.fee_pool;
+    fee_pool.accumulated_fees = 0;
+    fee_pool.last_collection_time = current_time;
+    
+    msg!("Fees withdrawn: {} lamports to treasury {}", amount, fee_pool.treasury_address);
+    
+    emit!(FeesWithdrawnEvent {
+        admin: ctx.accounts.admin.key(),
+        treasury: fee_pool.treasury_address,
+        amount,
+        timestamp: current_time,
+    });
+    
+    Ok(())
+}
+
 /// Context for updating config settings
 #[derive(Accounts)]
 pub struct UpdateConfig<'info> {
@@ -301,35 +554,11 @@ pub struct UpdateConfig<'info> {
 /// Update configuration settings (admin only)
 pub fn update_config(
     ctx: Context<UpdateConfig>,
-    new_operator: Option<Pubkey>,
-    new_base_rate: Option<u64>,
-    new_halving_interval: Option<i64>,
     new_treasury: Option<Pubkey>,
     new_max_invite_limit: Option<u8>,
 ) -> Result<()> {
     let config = &mut ctx.accounts.config;
     
-    // Update operator if provided
-    if let Some(operator) = new_operator {
-        config.operator = operator;
-        msg!("Config updated: new operator={}", operator);
-    }
-    
-    // Update base rate if provided
-    if let Some(base_rate) = new_base_rate {
-        config.base_rate = base_rate;
-        msg!("Config updated: new base_rate={}", base_rate);
-    }
-    
-    // Update halving interval if provided
-    if let Some(halving_interval) = new_halving_interval {
-        config.halving_interval = halving_interval;
-        // Also update next halving time
-        let current_time = Clock::get()?.unix_timestamp;
-        config.next_halving_time = current_time + halving_interval;
-        msg!("Config updated: new halving_interval={}", halving_interval);
-    }
-    
     // Update treasury if provided
     if let Some(treasury) = new_treasury {
         config.treasury = treasury;
@@ -342,27 +571,182 @@ pub fn update_config(
         msg!("Config updated: new max_invite_limit={}", max_invite_limit);
     }
     
+    emit!(ConfigUpdatedEvent {
+        admin: ctx.accounts.admin.key(),
+        treasury: new_treasury,
+        max_invite_limit: new_max_invite_limit,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
     Ok(())
 }
 
-// ===== PROBABILITY TABLE MANAGEMENT =====
+// ===== AUTHORITY HANDOVER =====
 
-/// Context for initializing probability table
+/// Context for proposing a new admin or operator
 #[derive(Accounts)]
-pub struct InitializeProbabilityTable<'info> {
+pub struct ProposeAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        constraint = config.admin == admin.key() @ crate::error::GameError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    pub admin: Signer<'info>,
+}
+
+/// Context for the pending admin accepting the handover
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    
+    pub new_admin: Signer<'info>,
+}
+
+/// Context for the pending operator accepting the handover
+#[derive(Accounts)]
+pub struct AcceptOperator<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    
+    pub new_operator: Signer<'info>,
+}
+
+/// Propose a new admin (step 1 of 2)
+/// The current admin stays in control until the proposed key signs `accept_admin`,
+/// so a mistyped key can simply be re-proposed
+pub fn propose_admin(ctx: Context<ProposeAuthority>, new_admin: Pubkey, expires_in: Option<i64>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let current_time = Clock::get()?.unix_timestamp;
+    
+    crate::validation::admin_validation::validate_admin_update_request(config, ctx.accounts.admin.key(), new_admin)?;
+    let expires_at = crate::validation::admin_validation::calculate_handover_expiry(current_time, expires_in)?;
+    
+    config.pending_admin = Some(new_admin);
+    config.pending_admin_expires_at = expires_at;
+    
+    msg!("Admin handover proposed: {} -> {}, expires_at={}", config.admin, new_admin, expires_at);
+    
+    emit!(AdminProposedEvent {
+        current_admin: config.admin,
+        pending_admin: new_admin,
+        expires_at,
+        timestamp: current_time,
+    });
+    
+    Ok(())
+}
+
+/// Accept a pending admin proposal (step 2 of 2)
+pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let current_time = Clock::get()?.unix_timestamp;
+    let new_admin = ctx.accounts.new_admin.key();
+    
+    crate::validation::admin_validation::validate_handover_acceptance(
+        config.pending_admin,
+        config.pending_admin_expires_at,
+        new_admin,
+        current_time,
+    )?;
+    
+    let previous_admin = config.admin;
+    config.admin = new_admin;
+    config.pending_admin = None;
+    config.pending_admin_expires_at = 0;
+    
+    msg!("Admin handover completed: {} -> {}", previous_admin, new_admin);
+    
+    emit!(AdminChangedEvent {
+        previous_admin,
+        new_admin,
+        timestamp: current_time,
+    });
+    
+    Ok(())
+}
+
+/// Propose a new operator (step 1 of 2)
+pub fn propose_operator(ctx: Context<ProposeAuthority>, new_operator: Pubkey, expires_in: Option<i64>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let current_time = Clock::get()?.unix_timestamp;
+    
+    crate::validation::admin_validation::validate_operator_update_request(config, ctx.accounts.admin.key(), new_operator)?;
+    let expires_at = crate::validation::admin_validation::calculate_handover_expiry(current_time, expires_in)?;
+    
+    config.pending_operator = Some(new_operator);
+    config.pending_operator_expires_at = expires_at;
+    
+    msg!("Operator handover proposed: {} -> {}, expires_at={}", config.operator, new_operator, expires_at);
+    
+    emit!(OperatorProposedEvent {
+        current_operator: config.operator,
+        pending_operator: new_operator,
+        expires_at,
+        timestamp: current_time,
+    });
+    
+    Ok(())
+}
+
+/// Accept a pending operator proposal (step 2 of 2)
+pub fn accept_operator(ctx: Context<AcceptOperator>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let current_time = Clock::get()?.unix_timestamp;
+    let new_operator = ctx.accounts.new_operator.key();
+    
+    crate::validation::admin_validation::validate_handover_acceptance(
+        config.pending_operator,
+        config.pending_operator_expires_at,
+        new_operator,
+        current_time,
+    )?;
+    
+    let previous_operator = config.operator;
+    config.operator = new_operator;
+    config.pending_operator = None;
+    config.pending_operator_expires_at = 0;
+    
+    msg!("Operator handover completed: {} -> {}", previous_operator, new_operator);
+    
+    emit!(OperatorChangedEvent {
+        previous_operator,
+        new_operator,
+        timestamp: current_time,
+    });
+    
+    Ok(())
+}
+
+// ===== ROLE MANAGEMENT =====
+
+/// Context for initializing the role registry
+#[derive(Accounts)]
+pub struct InitializeRoleRegistry<'info> {
     #[account(
         init,
         payer = admin,
-        space = ProbabilityTable::LEN,
-        seeds = [b"probability_table"],
+        space = RoleRegistry::LEN,
+        seeds = [b"role_registry"],
         bump
     )]
-    pub probability_table: Account<'info, ProbabilityTable>,
+    pub role_registry: Account<'info, RoleRegistry>,
     
     #[account(
         seeds = [b"config"],
         bump,
-        constraint = config.admin == admin.key() @ crate::error::GameError::Unauthorized
+        constraint = config.admin == admin.key() @ GameError::Unauthorized
     )]
     pub config: Account<'info, Config>,
     
@@ -372,11 +756,256 @@ pub struct InitializeProbabilityTable<'info> {
     pub system_program: Program<'info, System>,
 }
 
-/// Context for updating probability table
+/// Context for granting or revoking roles
+#[derive(Accounts)]
+pub struct UpdateRole<'info> {
+    #[account(
+        mut,
+        seeds = [b"role_registry"],
+        bump
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = config.admin == admin.key() @ GameError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    pub admin: Signer<'info>,
+}
+
+/// Initialize an empty role registry (admin only)
+pub fn initialize_role_registry(ctx: Context<InitializeRoleRegistry>) -> Result<()> {
+    let role_registry = &mut ctx.accounts.role_registry;
+    
+    role_registry.members = [RoleMember::default(); crate::constants::MAX_ROLE_MEMBERS];
+    role_registry.member_count = 0;
+    role_registry.reserve = [0; 32];
+    
+    msg!("Role registry initialized by {}", ctx.accounts.admin.key());
+    
+    Ok(())
+}
+
+/// Grant role bits to a member (admin only)
+pub fn grant_role(ctx: Context<UpdateRole>, member: Pubkey, roles: u8) -> Result<()> {
+    crate::validation::admin_validation::validate_role_flags(roles)?;
+    require!(member != Pubkey::default(), GameError::InvalidConfig);
+    
+    let role_registry = &mut ctx.accounts.role_registry;
+    let member_roles = role_registry
+        .grant(member, roles)
+        .ok_or(GameError::RoleRegistryFull)?;
+    
+    msg!("Roles granted to {}: {:#04x} (now {:#04x})", member, roles, member_roles);
+    
+    emit!(RoleGrantedEvent {
+        admin: ctx.accounts.admin.key(),
+        member,
+        roles,
+        member_roles,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Revoke role bits from a member (admin only)
+/// The member is removed from the registry once no roles remain
+pub fn revoke_role(ctx: Context<UpdateRole>, member: Pubkey, roles: u8) -> Result<()> {
+    crate::validation::admin_validation::validate_role_flags(roles)?;
+    
+    let role_registry = &mut ctx.accounts.role_registry;
+    let member_roles = role_registry
+        .revoke(&member, roles)
+        .ok_or(GameError::RoleMemberNotFound)?;
+    
+    msg!("Roles revoked from {}: {:#04x} (now {:#04x})", member, roles, member_roles);
+    
+    emit!(RoleRevokedEvent {
+        admin: ctx.accounts.admin.key(),
+        member,
+        roles,
+        member_roles,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+// ===== EMERGENCY PAUSE =====
+
+/// Context for pausing the system
+#[derive(Accounts)]
+pub struct PauseSystem<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    
+    /// Checked by the emergency pause conditions (nothing to pause before anyone plants)
+    #[account(
+        seeds = [b"global_stats"],
+        bump
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+    
+    /// Optional role registry (required unless the signer is the admin)
+    #[account(
+        seeds = [b"role_registry"],
+        bump
+    )]
+    pub role_registry: Option<Account<'info, RoleRegistry>>,
+    
+    /// Admin or registered pauser
+    pub authority: Signer<'info>,
+}
+
+/// Context for unpausing the system
+#[derive(Accounts)]
+pub struct UnpauseSystem<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    
+    /// Optional role registry (required unless the signer is the admin)
+    #[account(
+        seeds = [b"role_registry"],
+        bump
+    )]
+    pub role_registry: Option<Account<'info, RoleRegistry>>,
+    
+    /// Admin or registered pauser
+    pub authority: Signer<'info>,
+}
+
+/// Pause all user-facing instructions (admin or pauser)
+/// Records the reason code and timestamp so ops can audit the incident
+pub fn pause_system(ctx: Context<PauseSystem>, reason: u8) -> Result<()> {
+    crate::validation::admin_validation::validate_emergency_pause_conditions(
+        &ctx.accounts.config,
+        ctx.accounts.role_registry.as_deref(),
+        ctx.accounts.authority.key(),
+        ctx.accounts.global_stats.total_grow_power,
+    )?;
+    
+    let config = &mut ctx.accounts.config;
+    
+    require!(!config.is_paused, crate::error::GameError::SystemAlreadyPaused);
+    
+    let current_time = Clock::get()?.unix_timestamp;
+    config.is_paused = true;
+    config.pause_reason = reason;
+    config.paused_at = current_time;
+    
+    msg!("System paused by {}: reason={}, at={}", ctx.accounts.authority.key(), reason, current_time);
+    
+    emit!(SystemPauseChangedEvent {
+        authority: ctx.accounts.authority.key(),
+        is_paused: true,
+        reason,
+        timestamp: current_time,
+    });
+    
+    Ok(())
+}
+
+/// Resume all user-facing instructions (admin or pauser)
+pub fn unpause_system(ctx: Context<UnpauseSystem>) -> Result<()> {
+    crate::validation::admin_validation::validate_admin_can_pause(
+        &ctx.accounts.config,
+        ctx.accounts.role_registry.as_deref(),
+        ctx.accounts.authority.key(),
+    )?;
+    
+    let config = &mut ctx.accounts.config;
+    
+    require!(config.is_paused, crate::error::GameError::SystemNotPaused);
+    
+    let current_time = Clock::get()?.unix_timestamp;
+    let paused_duration = current_time - config.paused_at;
+    let reason = config.pause_reason;
+    config.is_paused = false;
+    config.pause_reason = 0;
+    config.paused_at = 0;
+    
+    msg!("System unpaused by {}: reason={}, paused for {}s", ctx.accounts.authority.key(), reason, paused_duration);
+    
+    emit!(SystemPauseChangedEvent {
+        authority: ctx.accounts.authority.key(),
+        is_paused: false,
+        reason,
+        timestamp: current_time,
+    });
+    
+    Ok(())
+}
+
+/// Context for pausing or resuming individual subsystems
 #[derive(Accounts)]
-pub struct UpdateProbabilityTable<'info> {
+pub struct SetSubsystemPause<'info> {
     #[account(
         mut,
+        seeds = [b"config"],
+        bump,
+        constraint = has_role(&config, role_registry.as_deref(), authority.key(), ROLE_PAUSER) @ GameError::MissingRole
+    )]
+    pub config: Account<'info, Config>,
+    
+    /// Optional role registry (required unless the signer is the admin)
+    #[account(
+        seeds = [b"role_registry"],
+        bump
+    )]
+    pub role_registry: Option<Account<'info, RoleRegistry>>,
+    
+    /// Admin or registered pauser
+    pub authority: Signer<'info>,
+}
+
+/// Set or clear subsystem pause bits (admin or pauser)
+/// `flags` is a combination of `constants::PAUSE_*` bits; other bits are left untouched
+pub fn set_subsystem_pause(ctx: Context<SetSubsystemPause>, flags: u8, paused: bool) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    
+    crate::validation::admin_validation::validate_pause_flags(flags)?;
+    
+    if paused {
+        config.paused_subsystems |= flags;
+    } else {
+        config.paused_subsystems &= !flags;
+    }
+    
+    msg!("Subsystem pause updated by {}: flags={:#04x}, paused={}, now={:#04x}",
+         ctx.accounts.authority.key(), flags, paused, config.paused_subsystems);
+    
+    emit!(SubsystemPauseChangedEvent {
+        authority: ctx.accounts.authority.key(),
+        flags,
+        paused,
+        paused_subsystems: config.paused_subsystems,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+// ===== PROBABILITY TABLE MANAGEMENT =====
+
+/// Context for initializing probability table
+#[derive(Accounts)]
+pub struct InitializeProbabilityTable<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = ProbabilityTable::LEN,
         seeds = [b"probability_table"],
         bump
     )]
@@ -391,6 +1020,8 @@ pub struct UpdateProbabilityTable<'info> {
     
     #[account(mut)]
     pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
 }
 
 /// Initialize probability table with Table 1 settings
@@ -416,86 +1047,17 @@ pub fn initialize_probability_table(ctx: Context<InitializeProbabilityTable>) ->
     msg!("Probability table initialized with Table 1 settings (6 seeds)");
     msg!("Expected value: {} GP per pack", probability_table.expected_value);
     
-    Ok(())
-}
-
-/// Update probability table with new settings
-pub fn update_probability_table(
-    ctx: Context<UpdateProbabilityTable>,
-    version: u32,
-    seed_count: u8,
-    grow_powers: Vec<u64>,
-    probability_thresholds: Vec<u16>,
-    probability_percentages: Vec<f32>,
-    expected_value: u64,
-    name: String,
-) -> Result<()> {
-    let probability_table = &mut ctx.accounts.probability_table;
-    
-    // Validate input constraints
-    require!(seed_count <= 16, crate::error::GameError::InvalidQuantity);
-    require!(grow_powers.len() == seed_count as usize, crate::error::GameError::InvalidQuantity);
-    require!(probability_thresholds.len() == seed_count as usize, crate::error::GameError::InvalidQuantity);
-    require!(probability_percentages.len() == seed_count as usize, crate::error::GameError::InvalidQuantity);
-    require!(name.len() <= 32, crate::error::GameError::InvalidConfig);
-    
-    // Validate probability thresholds are in ascending order and end at 10000
-    for i in 1..probability_thresholds.len() {
-        require!(
-            probability_thresholds[i] > probability_thresholds[i-1],
-            crate::error::GameError::InvalidConfig
-        );
-    }
-    require!(
-        probability_thresholds[probability_thresholds.len()-1] == 10000,
-        crate::error::GameError::InvalidConfig
-    );
-    
-    // Update table
-    probability_table.version = version;
-    probability_table.seed_count = seed_count;
-    probability_table.expected_value = expected_value;
-    probability_table.updated_at = Clock::get()?.unix_timestamp;
-    
-    // Clear arrays first
-    probability_table.grow_powers = [0; 16];
-    probability_table.probability_thresholds = [0; 16];
-    probability_table.probability_percentages = [0.0; 16];
-    probability_table.name = [0; 32];
-    
-    // Set new values
-    for i in 0..seed_count as usize {
-        probability_table.grow_powers[i] = grow_powers[i];
-        probability_table.probability_thresholds[i] = probability_thresholds[i];
-        probability_table.probability_percentages[i] = probability_percentages[i];
-    }
-    
-    // Set name
-    let name_bytes = name.as_bytes();
-    let name_len = name_bytes.len().min(32);
-    probability_table.name[0..name_len].copy_from_slice(&name_bytes[0..name_len]);
-    
-    msg!("Probability table updated to version {}", version);
-    msg!("Seed count: {}, Expected value: {} GP", seed_count, expected_value);
-    msg!("Table name: {}", name);
+    emit!(ProbabilityTableInitializedEvent {
+        admin: ctx.accounts.admin.key(),
+        version: probability_table.version,
+        seed_count: probability_table.seed_count,
+        expected_value: probability_table.expected_value,
+        timestamp: current_time,
+    });
     
     Ok(())
 }
 
-/// Context for updating seed pack cost
-#[derive(Accounts)]
-pub struct UpdateSeedPackCost<'info> {
-    #[account(
-        mut,
-        seeds = [b"config"],
-        bump,
-        has_one = admin
-    )]
-    pub config: Account<'info, Config>,
-    
-    pub admin: Signer<'info>,
-}
-
 /// Context for revealing a new seed type
 #[derive(Accounts)]
 pub struct RevealSeed<'info> {
@@ -509,12 +1071,20 @@ pub struct RevealSeed<'info> {
     #[account(
         seeds = [b"config"],
         bump,
-        constraint = config.admin == admin.key() @ crate::error::GameError::Unauthorized
+        constraint = has_role(&config, role_registry.as_deref(), authority.key(), ROLE_SEED_CURATOR) @ GameError::MissingRole
     )]
     pub config: Account<'info, Config>,
     
+    /// Optional role registry (required unless the signer is the admin)
+    #[account(
+        seeds = [b"role_registry"],
+        bump
+    )]
+    pub role_registry: Option<Account<'info, RoleRegistry>>,
+    
+    /// Admin or registered seed curator
     #[account(mut)]
-    pub admin: Signer<'info>,
+    pub authority: Signer<'info>,
 }
 
 /// Context for updating seed values
@@ -530,40 +1100,23 @@ pub struct UpdateSeedValues<'info> {
     #[account(
         seeds = [b"config"],
         bump,
-        constraint = config.admin == admin.key() @ crate::error::GameError::Unauthorized
+        constraint = has_role(&config, role_registry.as_deref(), authority.key(), ROLE_SEED_CURATOR) @ GameError::MissingRole
     )]
     pub config: Account<'info, Config>,
     
-    #[account(mut)]
-    pub admin: Signer<'info>,
-}
-
-/// Update seed pack cost (admin only)
-/// Allows dynamic price adjustment for seed packs
-pub fn update_seed_pack_cost(
-    ctx: Context<UpdateSeedPackCost>,
-    new_seed_pack_cost: u64,
-) -> Result<()> {
-    let config = &mut ctx.accounts.config;
-    
-    // Validation: Prevent zero cost
-    require!(new_seed_pack_cost > 0, crate::error::GameError::InvalidConfig);
-    
-    // Validation: Prevent excessively high costs (max 10,000 WEED)
-    let max_cost = 10_000 * 1_000_000; // 10,000 WEED with 6 decimals
-    require!(new_seed_pack_cost <= max_cost, crate::error::GameError::InvalidConfig);
-    
-    let old_cost = config.seed_pack_cost;
-    config.seed_pack_cost = new_seed_pack_cost;
-    
-    msg!("Seed pack cost updated from {} to {} WEED", 
-         old_cost / 1_000_000, 
-         new_seed_pack_cost / 1_000_000);
+    /// Optional role registry (required unless the signer is the admin)
+    #[account(
+        seeds = [b"role_registry"],
+        bump
+    )]
+    pub role_registry: Option<Account<'info, RoleRegistry>>,
     
-    Ok(())
+    /// Admin or registered seed curator
+    #[account(mut)]
+    pub authority: Signer<'info>,
 }
 
-/// Reveal a new seed type (admin only)
+/// Reveal a new seed type (admin or seed curator)
 /// Makes a previously hidden seed type visible to users with its values
 pub fn reveal_seed(
     ctx: Context<RevealSeed>,
@@ -573,18 +1126,12 @@ pub fn reveal_seed(
 ) -> Result<()> {
     let probability_table = &mut ctx.accounts.probability_table;
     
-    // Validation: seed index must be valid
-    require!(seed_index < 16, crate::error::GameError::InvalidConfig);
+    // Validation: index in range, probability 0-100%, positive grow power
+    crate::validation::admin_validation::validate_seed_values(seed_index, grow_power, probability_percentage)?;
     
     // Validation: seed must not already be revealed
     require!(!probability_table.is_seed_revealed(seed_index), crate::error::GameError::InvalidConfig);
     
-    // Validation: probability percentage should be reasonable (0-100%)
-    require!(probability_percentage >= 0.0 && probability_percentage <= 100.0, crate::error::GameError::InvalidConfig);
-    
-    // Validation: grow power should be positive
-    require!(grow_power > 0, crate::error::GameError::InvalidConfig);
-    
     // Reveal the seed
     let success = probability_table.reveal_seed(seed_index, grow_power, probability_percentage);
     require!(success, crate::error::GameError::InvalidConfig);
@@ -595,10 +1142,21 @@ pub fn reveal_seed(
     msg!("Seed type {} revealed: grow_power={}, probability={}%", 
          seed_index + 1, grow_power, probability_percentage);
     
+    emit!(ProbabilityTableUpdatedEvent {
+        authority: ctx.accounts.authority.key(),
+        seed_index,
+        revealed: true,
+        grow_power,
+        probability_percentage,
+        seed_count: probability_table.seed_count,
+        expected_value: probability_table.expected_value,
+        timestamp: probability_table.updated_at,
+    });
+    
     Ok(())
 }
 
-/// Update values for an already revealed seed type (admin only)
+/// Update values for an already revealed seed type (admin or seed curator)
 /// Allows changing grow power and probability for existing revealed seeds
 pub fn update_seed_values(
     ctx: Context<UpdateSeedValues>,
@@ -608,18 +1166,12 @@ pub fn update_seed_values(
 ) -> Result<()> {
     let probability_table = &mut ctx.accounts.probability_table;
     
-    // Validation: seed index must be valid
-    require!(seed_index < 16, crate::error::GameError::InvalidConfig);
+    // Validation: index in range, probability 0-100%, positive grow power
+    crate::validation::admin_validation::validate_seed_values(seed_index, grow_power, probability_percentage)?;
     
     // Validation: seed must already be revealed
     require!(probability_table.is_seed_revealed(seed_index), crate::error::GameError::InvalidConfig);
     
-    // Validation: probability percentage should be reasonable (0-100%)
-    require!(probability_percentage >= 0.0 && probability_percentage <= 100.0, crate::error::GameError::InvalidConfig);
-    
-    // Validation: grow power should be positive
-    require!(grow_power > 0, crate::error::GameError::InvalidConfig);
-    
     // Store old values for logging
     let old_grow_power = probability_table.grow_powers[seed_index as usize];
     let old_probability = probability_table.probability_percentages[seed_index as usize];
@@ -635,64 +1187,16 @@ pub fn update_seed_values(
     msg!("  Grow power: {} -> {}", old_grow_power, grow_power);
     msg!("  Probability: {}% -> {}%", old_probability, probability_percentage);
     
-    Ok(())
-}
-
-// ===== FARM SPACE COST MANAGEMENT =====
-
-/// Context for updating farm space cost
-#[derive(Accounts)]
-pub struct UpdateFarmSpaceCost<'info> {
-    #[account(
-        mut,
-        seeds = [b"config"],
-        bump,
-        constraint = config.admin == admin.key() @ crate::error::GameError::Unauthorized
-    )]
-    pub config: Account<'info, Config>,
-    
-    #[account(mut)]
-    pub admin: Signer<'info>,
-}
-
-/// Update farm space cost (admin only)
-/// Allows admin to dynamically adjust the SOL price for farm space purchases
-/// 
-/// # Parameters
-/// * `new_farm_space_cost_sol` - New cost in lamports (SOL)
-/// 
-/// # Example
-/// - 0.5 SOL = 500_000_000 lamports
-/// - 1.0 SOL = 1_000_000_000 lamports
-/// 
-/// # Security
-/// - Admin signature required
-/// - Prevents zero cost (must be > 0)
-/// - Prevents excessively high costs (max 10 SOL)
-pub fn update_farm_space_cost(
-    ctx: Context<UpdateFarmSpaceCost>,
-    new_farm_space_cost_sol: u64,
-) -> Result<()> {
-    let config = &mut ctx.accounts.config;
-    
-    // Validation: cost must be positive
-    require!(new_farm_space_cost_sol > 0, crate::error::GameError::InvalidAmount);
-    
-    // Validation: cost must be reasonable (max 10 SOL = 10,000,000,000 lamports)
-    require!(
-        new_farm_space_cost_sol <= 10_000_000_000,
-        crate::error::GameError::InvalidAmount
-    );
-    
-    // Store old value for logging
-    let old_cost = config.farm_space_cost_sol;
-    
-    // Update the farm space cost
-    config.farm_space_cost_sol = new_farm_space_cost_sol;
-    
-    msg!("Farm space cost updated:");
-    msg!("  Old cost: {} lamports ({} SOL)", old_cost, old_cost as f64 / 1_000_000_000.0);
-    msg!("  New cost: {} lamports ({} SOL)", new_farm_space_cost_sol, new_farm_space_cost_sol as f64 / 1_000_000_000.0);
+    emit!(ProbabilityTableUpdatedEvent {
+        authority: ctx.accounts.authority.key(),
+        seed_index,
+        revealed: false,
+        grow_power,
+        probability_percentage,
+        seed_count: probability_table.seed_count,
+        expected_value: probability_table.expected_value,
+        timestamp: probability_table.updated_at,
+    });
     
     Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::events::*;
+use crate::error::GameError;
+use crate::randomness::beacon::validate_beacon_value;
+
+// ===== ADMIN RANDOMNESS BEACON =====
+// Admin-published randomness for `RandomnessSource::AdminBeacon` on test
+// clusters without an oracle. Packs commit to the next round at purchase and
+// open once that round is published (see randomness/beacon.rs).
+
+/// Context for creating the randomness beacon
+#[derive(Accounts)]
+pub struct InitializeRandomnessBeacon<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = RandomnessBeacon::LEN,
+        seeds = [b"randomness_beacon"],
+        bump
+    )]
+    pub randomness_beacon: Account<'info, RandomnessBeacon>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = config.admin == admin.key() @ GameError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for publishing the next beacon round
+#[derive(Accounts)]
+pub struct PublishRandomnessBeacon<'info> {
+    #[account(
+        mut,
+        seeds = [b"randomness_beacon"],
+        bump
+    )]
+    pub randomness_beacon: Account<'info, RandomnessBeacon>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = config.admin == admin.key() @ GameError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    pub admin: Signer<'info>,
+}
+
+/// Create the randomness beacon with no published rounds (admin only)
+pub fn initialize_randomness_beacon(ctx: Context<InitializeRandomnessBeacon>) -> Result<()> {
+    let randomness_beacon = &mut ctx.accounts.randomness_beacon;
+    
+    randomness_beacon.round = 0;
+    randomness_beacon.value = [0; 32];
+    randomness_beacon.published_at = 0;
+    randomness_beacon.reserve = [0; 16];
+    
+    msg!("Randomness beacon initialized by {}", ctx.accounts.admin.key());
+    
+    Ok(())
+}
+
+/// Publish the next beacon round (admin only)
+pub fn publish_randomness_beacon(ctx: Context<PublishRandomnessBeacon>, value: [u8; 32]) -> Result<()> {
+    validate_beacon_value(&value)?;
+    
+    let current_time = Clock::get()?.unix_timestamp;
+    let randomness_beacon = &mut ctx.accounts.randomness_beacon;
+    randomness_beacon.round = crate::randomness::beacon::next_round(randomness_beacon)?;
+    randomness_beacon.value = value;
+    randomness_beacon.published_at = current_time;
+    
+    msg!("Randomness beacon round {} published", randomness_beacon.round);
+    
+    emit!(RandomnessBeaconPublishedEvent {
+        round: randomness_beacon.round,
+        value,
+        timestamp: current_time,
+    });
+    
+    Ok(())
+}
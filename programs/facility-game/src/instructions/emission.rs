@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::events::*;
+use crate::error::GameError;
+use crate::economics::halving_emission_segments;
+
+// ===== EMISSION SCHEDULE =====
+// Farming rewards follow the piecewise schedule in the ["emission_schedule"]
+// PDA. It starts as the existing halving config converted to segments;
+// afterwards segments are replaced through the config change timelock
+// (`ConfigChange::EmissionSchedule`, or `BaseRate` / `HalvingInterval`, which
+// rebuild halving segments).
+
+/// Context for creating the emission schedule
+#[derive(Accounts)]
+pub struct InitializeEmissionSchedule<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = EmissionSchedule::LEN,
+        seeds = [b"emission_schedule"],
+        bump
+    )]
+    pub emission_schedule: Account<'info, EmissionSchedule>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = config.admin == admin.key() @ GameError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Create the emission schedule from the current halving config (admin only)
+/// Segments start where the reward accumulator left off, so no time is skipped
+pub fn initialize_emission_schedule(ctx: Context<InitializeEmissionSchedule>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let current_time = Clock::get()?.unix_timestamp;
+    let start_time = match ctx.accounts.global_stats.last_reward_time {
+        0 => current_time,
+        last_reward_time => last_reward_time,
+    };
+    
+    let segments = halving_emission_segments(
+        config.base_rate,
+        config.next_halving_time,
+        config.halving_interval,
+        start_time,
+    )?;
+    
+    let emission_schedule = &mut ctx.accounts.emission_schedule;
+    emission_schedule.set_segments(&segments, current_time);
+    emission_schedule.reserve = [0; 32];
+    ctx.accounts.global_stats.current_rewards_per_second =
+        crate::economics::current_emission_rate(&segments, current_time);
+    
+    msg!("Emission schedule initialized from halving config: {} segments from {}, base rate {}/sec",
+         segments.len(), start_time, config.base_rate);
+    
+    emit!(EmissionScheduleInitializedEvent {
+        admin: ctx.accounts.admin.key(),
+        segment_count: emission_schedule.segment_count,
+        start_time,
+        timestamp: current_time,
+    });
+    
+    Ok(())
+}
@@ -5,6 +5,7 @@
 use anchor_lang::prelude::*;
 // Note: Token imports removed since manual upgrade functions were removed
 use crate::state::*;
+use crate::events::*;
 use crate::error::*;
 use crate::utils::*;
 
@@ -59,6 +60,13 @@ pub struct BuyFarmSpace<'info> {
     )]
     pub global_stats: Account<'info, GlobalStats>,
     
+    /// Emission schedule driving the reward accumulator
+    #[account(
+        seeds = [b"emission_schedule"],
+        bump
+    )]
+    pub emission_schedule: Account<'info, EmissionSchedule>,
+    
     /// 支払い先のトレジャリーアカウント（0.5 SOL受取用）
     /// configで設定されたtreasuryアドレスと一致する必要がある
     #[account(
@@ -80,6 +88,10 @@ pub struct BuyFarmSpace<'info> {
 /// Purchase farm space (Level 1)
 /// Cost: 0.5 SOL + Seed 1 (100 Grow Power) gifted
 pub fn buy_farm_space(ctx: Context<BuyFarmSpace>) -> Result<()> {
+    // Refuse to run while the system is paused
+    crate::validation::admin_validation::validate_system_not_paused(&ctx.accounts.config)?;
+    crate::validation::user_validation::validate_user_state_migrated(&ctx.accounts.user_state)?;
+    
     let user_state = &mut ctx.accounts.user_state;
     let farm_space = &mut ctx.accounts.farm_space;
     let initial_seed = &mut ctx.accounts.initial_seed;
@@ -114,23 +126,40 @@ pub fn buy_farm_space(ctx: Context<BuyFarmSpace>) -> Result<()> {
     // Update seed counter (start from 1 for next seeds)
     config.seed_counter = 1;
 
-    // Update user state
+    // Update user state (settle first, so the new grow power only earns from now)
     let current_time = Clock::get()?.unix_timestamp;
+    update_reward_accumulator(global_stats, &ctx.accounts.emission_schedule, current_time)?;
+    settle_pending_farming_rewards(user_state, global_stats)?;
     user_state.has_farm_space = true;
     user_state.total_grow_power = farm_space.total_grow_power;
+    user_state.reward_debt = crate::economics::calculate_reward_debt(
+        user_state.total_grow_power,
+        global_stats.acc_reward_per_grow_power,
+    )?;
     user_state.last_harvest_time = current_time;
     
     // Update global statistics
     update_global_stats_on_farm_creation(
         global_stats,
         farm_space.total_grow_power,
+        config.farm_space_cost_sol,
         current_time,
-    );
+    )?;
 
     msg!("Farm space (Level 1) purchased for user: {} with initial Seed 1 (ID: 0, Grow Power: {}), SOL paid: {} lamports", 
          ctx.accounts.user.key(), 
          initial_seed.grow_power,
          config.farm_space_cost_sol);
+    
+    emit!(FarmSpacePurchasedEvent {
+        user: ctx.accounts.user.key(),
+        farm_space: farm_space.key(),
+        sol_paid: config.farm_space_cost_sol,
+        initial_seed: initial_seed.key(),
+        grow_power: initial_seed.grow_power,
+        timestamp: current_time,
+    });
+    
     Ok(())
 }
 
@@ -240,6 +269,15 @@ pub fn initialize_farm_level_config(ctx: Context<InitializeFarmLevelConfig>) ->
     config.reserve = [0; 32];
     
     msg!("Farm level config initialized with {} levels", config.max_level);
+    
+    emit!(FarmLevelConfigUpdatedEvent {
+        admin: ctx.accounts.admin.key(),
+        max_level: config.max_level,
+        capacities: config.capacities[..config.max_level as usize].to_vec(),
+        upgrade_thresholds: config.upgrade_thresholds[..config.max_level as usize].to_vec(),
+        timestamp: config.updated_at,
+    });
+    
     Ok(())
 }
 
@@ -253,28 +291,13 @@ pub fn update_farm_level_config(
 ) -> Result<()> {
     let config = &mut ctx.accounts.farm_level_config;
     
-    // Validation
-    require!(max_level >= 1 && max_level <= 20, GameError::InvalidConfig);
-    require!(capacities.len() == max_level as usize, GameError::InvalidConfig);
-    require!(upgrade_thresholds.len() == max_level as usize, GameError::InvalidConfig);
-    
-    // Validate ascending order for capacities
-    for i in 1..capacities.len() {
-        require!(capacities[i] > capacities[i-1], GameError::InvalidConfig);
-    }
-    
-    // Validate ascending order for thresholds
-    for i in 1..upgrade_thresholds.len() {
-        require!(upgrade_thresholds[i] > upgrade_thresholds[i-1], GameError::InvalidConfig);
-    }
-    
-    // Validate level names if provided
-    if let Some(names) = &level_names {
-        require!(names.len() == max_level as usize, GameError::InvalidConfig);
-        for name in names {
-            require!(name.len() <= 32, GameError::InvalidConfig);
-        }
-    }
+    // Validation: level count, ascending capacities and thresholds, name lengths
+    crate::validation::game_validation::validate_farm_level_config_update(
+        max_level,
+        &capacities,
+        &upgrade_thresholds,
+        level_names.as_deref(),
+    )?;
     
     // Update configuration
     config.max_level = max_level;
@@ -298,6 +321,15 @@ pub fn update_farm_level_config(
     config.updated_at = Clock::get()?.unix_timestamp;
     
     msg!("Farm level config updated: max_level={}", max_level);
+    
+    emit!(FarmLevelConfigUpdatedEvent {
+        admin: ctx.accounts.admin.key(),
+        max_level,
+        capacities,
+        upgrade_thresholds,
+        timestamp: config.updated_at,
+    });
+    
     Ok(())
 }
 
@@ -332,6 +364,16 @@ pub fn migrate_farm_to_new_levels(ctx: Context<MigrateFarmToNewLevels>) -> Resul
     msg!("Farm migrated: level {} -> {}, capacity {} -> {}", 
          old_level, farm.level, old_capacity, farm.capacity);
     
+    emit!(FarmSpaceMigratedEvent {
+        user: ctx.accounts.user.key(),
+        farm_space: farm.key(),
+        old_level,
+        level: farm.level,
+        old_capacity,
+        capacity: farm.capacity,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
     Ok(())
 }
 
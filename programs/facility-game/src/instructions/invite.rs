@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
+use crate::events::*;
 use crate::error::*;
 use crate::utils::{
     generate_invite_code_hash, 
@@ -24,7 +25,7 @@ pub struct CreateInviteCode<'info> {
         space = InviteCode::LEN,
         seeds = [
             b"invite_code", 
-            &generate_invite_code_hash(&invite_code, &get_fixed_salt())
+            generate_invite_code_hash(&invite_code, &get_fixed_salt()).as_ref()
         ],
         bump
     )]
@@ -36,6 +37,13 @@ pub struct CreateInviteCode<'info> {
     )]
     pub config: Account<'info, Config>,
     
+    /// Optional role registry (grants invite issuers the operator invite limit)
+    #[account(
+        seeds = [b"role_registry"],
+        bump
+    )]
+    pub role_registry: Option<Account<'info, RoleRegistry>>,
+    
     #[account(mut)]
     pub inviter: Signer<'info>,
     
@@ -52,7 +60,7 @@ pub struct UseInviteCode<'info> {
         mut,
         seeds = [
             b"invite_code", 
-            &generate_invite_code_hash(&invite_code, &get_fixed_salt())
+            generate_invite_code_hash(&invite_code, &get_fixed_salt()).as_ref()
         ],
         bump,
         constraint = invite_account.is_active @ GameError::InviteCodeInactive,
@@ -85,11 +93,14 @@ pub struct UseInviteCode<'info> {
 
 /// ハッシュベース招待コードを作成 - プライバシー保護
 /// 平文コードは即座にハッシュ化され、チェーン上に保存されない
-/// オペレーターは無制限、一般ユーザーは設定された上限まで招待可能
+/// オペレーター・招待発行者は無制限、一般ユーザーは設定された上限まで招待可能
 pub fn create_invite_code(
     ctx: Context<CreateInviteCode>,
     invite_code: [u8; 12]
 ) -> Result<()> {
+    // Refuse to run while the system or this subsystem is paused
+    crate::validation::admin_validation::validate_subsystem_not_paused(&ctx.accounts.config, crate::constants::PAUSE_INVITE)?;
+    
     // Validate invite code format
     validate_invite_code_format(&invite_code)?;
     
@@ -100,20 +111,20 @@ pub fn create_invite_code(
     let invite = &mut ctx.accounts.invite_account;
     let config = &ctx.accounts.config;
     
-    // Determine invite limit with timestamp validation for operator privileges
-    let invite_limit = if ctx.accounts.inviter.key() == config.operator {
-        // Validate operator privileges are still current
-        require!(
-            ctx.accounts.inviter.key() == config.operator,
-            GameError::UnauthorizedOperator
-        );
-        1024u16 // Operator has high invite limit (1024)
+    // Operator and registered invite issuers get the high invite limit
+    let is_invite_issuer = ctx.accounts.role_registry
+        .as_ref()
+        .is_some_and(|r| r.has_role(&ctx.accounts.inviter.key(), crate::constants::ROLE_INVITE_ISSUER));
+    let privileged = ctx.accounts.inviter.key() == config.operator || is_invite_issuer;
+    
+    let invite_limit = if privileged {
+        1024u16 // Operator / invite issuer has high invite limit (1024)
     } else {
         config.max_invite_limit as u16
     };
     
-    // Store the operator status at creation time for future validation
-    let created_as_operator = ctx.accounts.inviter.key() == config.operator;
+    // Store the privileged status at creation time for future validation
+    let created_as_operator = privileged;
     
     // Generate hash with fixed salt
     let salt = get_fixed_salt();
@@ -134,12 +145,20 @@ pub fn create_invite_code(
     invite.created_at = Clock::get()?.unix_timestamp;
     invite.is_active = true;
     invite.created_as_operator = created_as_operator;
-    invite.reserve = [0; 14];
+    invite.reserve = [0; 10];
     
     msg!("Secret invite code created: Hash={:?}, Inviter={}", 
          &code_hash[0..8], 
          ctx.accounts.inviter.key());
     
+    emit!(InviteCodeCreatedEvent {
+        inviter: invite.inviter,
+        invite_account: invite.key(),
+        code_hash,
+        max_uses: invite.max_uses,
+        timestamp: invite.created_at,
+    });
+    
     Ok(())
 }
 
@@ -150,6 +169,9 @@ pub fn use_invite_code(
     ctx: Context<UseInviteCode>,
     invite_code: [u8; 12]
 ) -> Result<()> {
+    // Refuse to run while the system or this subsystem is paused
+    crate::validation::admin_validation::validate_subsystem_not_paused(&ctx.accounts.config, crate::constants::PAUSE_INVITE)?;
+    
     let invite = &mut ctx.accounts.invite_account;
     
     // Verify hash
@@ -174,7 +196,10 @@ pub fn use_invite_code(
     user_state.referrer = Some(inviter_pubkey);
     user_state.pending_referral_rewards = 0;
     user_state.total_packs_purchased = 0;
-    user_state.reserve = [0; 28];
+    user_state.version = UserState::CURRENT_VERSION;
+    user_state.reward_debt = 0;
+    user_state.pending_farming_rewards = 0;
+    user_state.reserve = [0; 3];
     
     // Update usage count
     invite.uses += 1;
@@ -183,5 +208,20 @@ pub fn use_invite_code(
          ctx.accounts.invitee.key(),
          inviter_pubkey);
     
+    emit!(InviteCodeUsedEvent {
+        invitee: ctx.accounts.invitee.key(),
+        inviter: inviter_pubkey,
+        invite_account: invite.key(),
+        uses: invite.uses,
+        max_uses: invite.max_uses,
+        timestamp: user_state.last_harvest_time,
+    });
+    emit!(UserInitializedEvent {
+        user: ctx.accounts.invitee.key(),
+        referrer: Some(inviter_pubkey),
+        initialized_by: ctx.accounts.invitee.key(),
+        timestamp: user_state.last_harvest_time,
+    });
+    
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,372 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::events::*;
+use crate::error::GameError;
+use crate::utils::{mint_supply, realloc_account, settle_legacy_user_rewards, update_reward_accumulator};
+use crate::validation::admin_validation::validate_migration;
+
+// ===== ACCOUNT MIGRATION =====
+// Versioned accounts carry a `version` byte. When a layout grows, bump the
+// type's CURRENT_VERSION, append the new fields, and fill their defaults in
+// the matching migrate_* handler. Migration reallocs the account in place
+// (zero-filling the new bytes), so existing PDAs keep their addresses.
+
+/// Context for migrating the global config
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    /// CHECK: May still use a legacy layout that cannot be deserialized;
+    /// address and owner are checked here, discriminator and admin after realloc
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        owner = crate::ID
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for migrating a user state (permissionless; payer covers any rent increase)
+#[derive(Accounts)]
+pub struct MigrateUserState<'info> {
+    /// CHECK: May still use a legacy layout; address and owner are checked here
+    #[account(
+        mut,
+        seeds = [b"user", user.key().as_ref()],
+        bump,
+        owner = crate::ID
+    )]
+    pub user_state: UncheckedAccount<'info>,
+    
+    /// CHECK: Only used to derive the user state address
+    pub user: UncheckedAccount<'info>,
+    
+    /// Must already be migrated; settles rewards earned under the old formula
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    
+    /// Emission schedule driving the reward accumulator
+    #[account(
+        seeds = [b"emission_schedule"],
+        bump
+    )]
+    pub emission_schedule: Account<'info, EmissionSchedule>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for migrating a farm space (permissionless; payer covers any rent increase)
+#[derive(Accounts)]
+pub struct MigrateFarmSpace<'info> {
+    /// CHECK: May still use a legacy layout; address and owner are checked here
+    #[account(
+        mut,
+        seeds = [b"farm_space", user.key().as_ref()],
+        bump,
+        owner = crate::ID
+    )]
+    pub farm_space: UncheckedAccount<'info>,
+    
+    /// CHECK: Only used to derive the farm space address
+    pub user: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for migrating global statistics (admin only)
+#[derive(Accounts)]
+pub struct MigrateGlobalStats<'info> {
+    /// CHECK: May still use a legacy layout; address and owner are checked here
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump,
+        owner = crate::ID
+    )]
+    pub global_stats: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = config.admin == admin.key() @ GameError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    /// WEED mint, read to backfill the circulating supply
+    /// CHECK: Address checked here; parsed in utils::mint_supply
+    #[account(
+        seeds = [b"reward_mint"],
+        bump
+    )]
+    pub reward_mint: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for migrating the fee pool (admin only)
+#[derive(Accounts)]
+pub struct MigrateFeePool<'info> {
+    /// CHECK: May still use a legacy layout; address and owner are checked here
+    #[account(
+        mut,
+        seeds = [b"fee_pool"],
+        bump,
+        owner = crate::ID
+    )]
+    pub fee_pool: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = config.admin == admin.key() @ GameError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for migrating a seed pack (permissionless; payer covers any rent increase)
+#[derive(Accounts)]
+#[instruction(pack_id: u64)]
+pub struct MigrateSeedPack<'info> {
+    /// CHECK: May still use a legacy layout; address and owner are checked here
+    #[account(
+        mut,
+        seeds = [b"seed_pack", user.key().as_ref(), pack_id.to_le_bytes().as_ref()],
+        bump,
+        owner = crate::ID
+    )]
+    pub seed_pack: UncheckedAccount<'info>,
+    
+    /// CHECK: Only used to derive the seed pack address
+    pub user: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Migrate the global config to the current layout (admin only)
+pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+    let info = ctx.accounts.config.to_account_info();
+    let old_len = info.data_len();
+    
+    realloc_account(&info, &ctx.accounts.admin, &ctx.accounts.system_program, Config::LEN)?;
+    
+    let mut config = Config::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    require!(config.admin == ctx.accounts.admin.key(), GameError::Unauthorized);
+    
+    let from_version = config.version;
+    validate_migration(from_version, Config::CURRENT_VERSION, old_len, Config::LEN)?;
+    
+    // v0 -> v1: pause/handover fields start zeroed; the timelock needs a non-zero delay
+    if from_version < 1 {
+        config.config_change_delay = crate::constants::DEFAULT_CONFIG_CHANGE_DELAY;
+    }
+    
+    // v1 -> v2: oracle randomness accounts default to Switchboard On-Demand
+    if from_version < 2 {
+        config.oracle_program = crate::constants::SWITCHBOARD_ON_DEMAND_PROGRAM_ID;
+    }
+    
+    // v2 -> v3: keep the oracle source the program used before sources were selectable
+    if from_version < 3 {
+        config.randomness_source = RandomnessSource::Oracle;
+    }
+    
+    // v3 -> v4: only whole percentages could be set before basis points were stored
+    if from_version < 4 {
+        config.transfer_fee_basis_points =
+            config.trading_fee_percentage as u16 * crate::constants::PERCENTAGE_TO_BASIS_POINTS;
+    }
+    
+    // v4 -> v5: no fee update is pending; the recorded fee is already in effect
+    if from_version < 5 {
+        config.pending_transfer_fee_basis_points = config.transfer_fee_basis_points;
+        config.pending_transfer_fee_epoch = 0;
+    }
+    
+    config.version = Config::CURRENT_VERSION;
+    config.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+    
+    emit_migrated(info.key(), from_version, Config::CURRENT_VERSION, old_len, Config::LEN)
+}
+
+/// Migrate a user state to the current layout (permissionless)
+pub fn migrate_user_state(ctx: Context<MigrateUserState>) -> Result<()> {
+    let info = ctx.accounts.user_state.to_account_info();
+    let old_len = info.data_len();
+    
+    realloc_account(&info, &ctx.accounts.payer, &ctx.accounts.system_program, UserState::LEN)?;
+    
+    let mut user_state = UserState::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    let from_version = user_state.version;
+    validate_migration(from_version, UserState::CURRENT_VERSION, old_len, UserState::LEN)?;
+    
+    // v1 -> v2: settle what the old time-based formula owes up to now into the
+    // checkpoint, then earn through the accumulator from here on
+    let current_time = Clock::get()?.unix_timestamp;
+    update_reward_accumulator(&mut ctx.accounts.global_stats, &ctx.accounts.emission_schedule, current_time)?;
+    settle_legacy_user_rewards(&mut user_state, &mut ctx.accounts.global_stats, &ctx.accounts.config, current_time)?;
+    
+    user_state.version = UserState::CURRENT_VERSION;
+    user_state.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+    
+    emit_migrated(info.key(), from_version, UserState::CURRENT_VERSION, old_len, UserState::LEN)
+}
+
+/// Migrate a farm space to the current layout (permissionless)
+pub fn migrate_farm_space(ctx: Context<MigrateFarmSpace>) -> Result<()> {
+    let info = ctx.accounts.farm_space.to_account_info();
+    let old_len = info.data_len();
+    
+    realloc_account(&info, &ctx.accounts.payer, &ctx.accounts.system_program, FarmSpace::LEN)?;
+    
+    let mut farm_space = FarmSpace::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    let from_version = farm_space.version;
+    validate_migration(from_version, FarmSpace::CURRENT_VERSION, old_len, FarmSpace::LEN)?;
+    
+    farm_space.version = FarmSpace::CURRENT_VERSION;
+    farm_space.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+    
+    emit_migrated(info.key(), from_version, FarmSpace::CURRENT_VERSION, old_len, FarmSpace::LEN)
+}
+
+/// Migrate global statistics to the current layout (admin only)
+pub fn migrate_global_stats(ctx: Context<MigrateGlobalStats>) -> Result<()> {
+    let info = ctx.accounts.global_stats.to_account_info();
+    let old_len = info.data_len();
+    
+    realloc_account(&info, &ctx.accounts.admin, &ctx.accounts.system_program, GlobalStats::LEN)?;
+    
+    let mut global_stats = GlobalStats::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    let from_version = global_stats.version;
+    validate_migration(from_version, GlobalStats::CURRENT_VERSION, old_len, GlobalStats::LEN)?;
+    
+    // v1 -> v2: the reward accumulator starts now (unmigrated stats start on first use)
+    if from_version < 2 {
+        global_stats.acc_reward_per_grow_power = 0;
+        global_stats.last_reward_time = Clock::get()?.unix_timestamp;
+    }
+    
+    // v2 -> v3: the next claim sets the flag if the cap was already reached
+    if from_version < 3 {
+        global_stats.supply_exhausted = false;
+    }
+    
+    // v3 -> v4: count emission from what was minted; rewards accrued but not
+    // yet claimed before the migration are not known and are left out
+    if from_version < 4 {
+        global_stats.total_emission_accrued = ctx.accounts.config.total_supply_minted;
+    }
+    
+    // v4 -> v5: supply comes from the mint and burns from the minted total;
+    // activity counters start at the migration
+    if from_version < 5 {
+        let supply = mint_supply(&ctx.accounts.reward_mint.to_account_info())?;
+        global_stats.total_supply = supply;
+        global_stats.total_burned = ctx.accounts.config.total_supply_minted.saturating_sub(supply);
+        global_stats.total_packs_sold = 0;
+        global_stats.total_seeds_generated = 0;
+        global_stats.total_sol_collected = 0;
+    }
+    
+    global_stats.version = GlobalStats::CURRENT_VERSION;
+    global_stats.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+    
+    emit_migrated(info.key(), from_version, GlobalStats::CURRENT_VERSION, old_len, GlobalStats::LEN)
+}
+
+/// Migrate the fee pool to the current layout (admin only)
+pub fn migrate_fee_pool(ctx: Context<MigrateFeePool>) -> Result<()> {
+    let info = ctx.accounts.fee_pool.to_account_info();
+    let old_len = info.data_len();
+    
+    realloc_account(&info, &ctx.accounts.admin, &ctx.accounts.system_program, FeePool::LEN)?;
+    
+    let mut fee_pool = FeePool::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    let from_version = fee_pool.version;
+    validate_migration(from_version, FeePool::CURRENT_VERSION, old_len, FeePool::LEN)?;
+    
+    // v1 -> v2: withheld transfer fees could not be withdrawn before this version
+    if from_version < 2 {
+        fee_pool.accumulated_token_fees = 0;
+    }
+    
+    fee_pool.version = FeePool::CURRENT_VERSION;
+    fee_pool.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+    
+    emit_migrated(info.key(), from_version, FeePool::CURRENT_VERSION, old_len, FeePool::LEN)
+}
+
+/// Migrate a seed pack to the current layout (permissionless)
+pub fn migrate_seed_pack(ctx: Context<MigrateSeedPack>, _pack_id: u64) -> Result<()> {
+    let info = ctx.accounts.seed_pack.to_account_info();
+    let old_len = info.data_len();
+    
+    realloc_account(&info, &ctx.accounts.payer, &ctx.accounts.system_program, SeedPack::LEN)?;
+    
+    let mut seed_pack = SeedPack::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    let from_version = seed_pack.version;
+    validate_migration(from_version, SeedPack::CURRENT_VERSION, old_len, SeedPack::LEN)?;
+    
+    // v0 -> v1: legacy packs predate commit-reveal and oracle randomness, so
+    // they reveal with a slot hash; unopened ones commit to a future slot now,
+    // whose hash is unknown to whoever sends the migration
+    if from_version < 1 {
+        seed_pack.randomness_source = RandomnessSource::SlotHash;
+        seed_pack.source_commit = 0;
+        if !seed_pack.is_opened {
+            seed_pack.commit_slot = crate::randomness::calculate_commit_slot(Clock::get()?.slot)?;
+        }
+    }
+    
+    seed_pack.version = SeedPack::CURRENT_VERSION;
+    seed_pack.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+    
+    emit_migrated(info.key(), from_version, SeedPack::CURRENT_VERSION, old_len, SeedPack::LEN)
+}
+
+/// Log and emit a completed migration
+fn emit_migrated(account: Pubkey, from_version: u8, to_version: u8, old_len: usize, new_len: usize) -> Result<()> {
+    msg!("Account {} migrated: v{} -> v{}, {} -> {} bytes",
+         account, from_version, to_version, old_len, new_len);
+    
+    emit!(AccountMigratedEvent {
+        account,
+        from_version,
+        to_version,
+        old_len: old_len as u32,
+        new_len: new_len as u32,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
@@ -10,6 +10,12 @@ pub mod farm; // Farm operations + level management
 pub mod referral; // 紹介料蓄積・請求システム
 pub mod seeds;
 pub mod invite; // Hash-based invite system
+pub mod timelock; // Timelocked economic config changes
+pub mod multisig; // Built-in M-of-N approval for admin actions
+pub mod migration; // Versioned in-place account migrations
+pub mod beacon; // Admin randomness beacon for test clusters
+pub mod transfer_fee; // WEED transfer fee harvesting and withdrawal
+pub mod emission; // Piecewise farming emission schedule
 
 // すべての構造体と関数を再エクスポート
 pub use admin::*;
@@ -18,4 +24,10 @@ pub use farm::*;
 pub use referral::*;
 pub use seeds::*;
 pub use invite::*;
+pub use timelock::*;
+pub use multisig::*;
+pub use migration::*;
+pub use beacon::*;
+pub use transfer_fee::*;
+pub use emission::*;
 
@@ -0,0 +1,303 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use crate::state::*;
+use crate::events::*;
+use crate::error::GameError;
+use crate::validation::admin_validation::{validate_multisig_config, validate_proposal_instruction};
+
+// ===== BUILT-IN MULTISIG =====
+// M-of-N approval for admin actions without an external multisig program.
+// A proposal stores a serialized instruction for this program; once enough
+// members approve, it is executed via self-CPI with the `multisig_signer` PDA
+// as signer. Handing `Config.admin` to that PDA (propose_admin + a proposal
+// wrapping accept_admin) puts every admin instruction behind the threshold.
+
+/// Context for creating the multisig
+#[derive(Accounts)]
+pub struct CreateMultisig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = Multisig::LEN,
+        seeds = [b"multisig"],
+        bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = config.admin == admin.key() @ GameError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for creating a proposal
+#[derive(Accounts)]
+#[instruction(instruction_accounts: Vec<ProposalAccount>, instruction_data: Vec<u8>)]
+pub struct CreateProposal<'info> {
+    #[account(
+        mut,
+        seeds = [b"multisig"],
+        bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+    
+    #[account(
+        init,
+        payer = proposer,
+        space = Proposal::space(instruction_accounts.len(), instruction_data.len()),
+        seeds = [b"proposal", multisig.proposal_counter.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for approving a proposal
+#[derive(Accounts)]
+pub struct ApproveProposal<'info> {
+    #[account(
+        seeds = [b"multisig"],
+        bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+    
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.proposal_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    
+    pub signer: Signer<'info>,
+}
+
+/// Context for executing an approved proposal
+/// The wrapped instruction's accounts are passed as remaining accounts
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    #[account(
+        seeds = [b"multisig"],
+        bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+    
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.proposal_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    
+    /// PDA that signs the wrapped instruction (holds SOL for any rent it pays)
+    /// CHECK: Address verified by seeds; carries no data
+    #[account(
+        mut,
+        seeds = [b"multisig_signer"],
+        bump
+    )]
+    pub multisig_signer: UncheckedAccount<'info>,
+    
+    pub executor: Signer<'info>,
+}
+
+/// Context for changing multisig membership (only via an executed proposal)
+#[derive(Accounts)]
+pub struct SetMultisigSigners<'info> {
+    #[account(
+        mut,
+        seeds = [b"multisig"],
+        bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+    
+    #[account(
+        seeds = [b"multisig_signer"],
+        bump
+    )]
+    pub multisig_signer: Signer<'info>,
+}
+
+/// Create the multisig with its initial members (admin only, one-time)
+pub fn create_multisig(ctx: Context<CreateMultisig>, signers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+    validate_multisig_config(&signers, threshold)?;
+    
+    let multisig = &mut ctx.accounts.multisig;
+    multisig.signers = [Pubkey::default(); crate::constants::MAX_MULTISIG_SIGNERS];
+    multisig.signers[..signers.len()].copy_from_slice(&signers);
+    multisig.signer_count = signers.len() as u8;
+    multisig.threshold = threshold;
+    multisig.proposal_counter = 0;
+    multisig.owner_set_seqno = 0;
+    multisig.reserve = [0; 32];
+    
+    let (multisig_signer, _) = Pubkey::find_program_address(&[b"multisig_signer"], ctx.program_id);
+    
+    msg!("Multisig created: {}-of-{}, signer PDA {}", threshold, signers.len(), multisig_signer);
+    
+    emit!(MultisigSignersChangedEvent {
+        signers,
+        threshold,
+        owner_set_seqno: 0,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Create a proposal wrapping an instruction for this program (members only)
+/// The proposer's approval is recorded automatically
+pub fn create_proposal(
+    ctx: Context<CreateProposal>,
+    instruction_accounts: Vec<ProposalAccount>,
+    instruction_data: Vec<u8>,
+) -> Result<()> {
+    let multisig = &mut ctx.accounts.multisig;
+    let proposer = ctx.accounts.proposer.key();
+    
+    let index = multisig.signer_index(&proposer).ok_or(GameError::NotMultisigSigner)?;
+    validate_proposal_instruction(instruction_accounts.len(), instruction_data.len())?;
+    
+    let current_time = Clock::get()?.unix_timestamp;
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.proposal_id = multisig.proposal_counter;
+    proposal.proposer = proposer;
+    proposal.accounts = instruction_accounts;
+    proposal.data = instruction_data;
+    proposal.approvals = 1 << index;
+    proposal.owner_set_seqno = multisig.owner_set_seqno;
+    proposal.executed = false;
+    proposal.created_at = current_time;
+    proposal.executed_at = 0;
+    proposal.reserve = [0; 16];
+    
+    multisig.proposal_counter = multisig.proposal_counter
+        .checked_add(1)
+        .ok_or(GameError::CalculationOverflow)?;
+    
+    msg!("Proposal {} created by {}", proposal.proposal_id, proposer);
+    
+    emit!(ProposalCreatedEvent {
+        proposal_id: proposal.proposal_id,
+        proposer,
+        data: proposal.data.clone(),
+        timestamp: current_time,
+    });
+    
+    Ok(())
+}
+
+/// Approve a proposal (members only)
+pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+    let multisig = &ctx.accounts.multisig;
+    let proposal = &mut ctx.accounts.proposal;
+    let signer = ctx.accounts.signer.key();
+    
+    require!(!proposal.executed, GameError::ProposalAlreadyExecuted);
+    require!(proposal.owner_set_seqno == multisig.owner_set_seqno, GameError::ProposalStale);
+    
+    let index = multisig.signer_index(&signer).ok_or(GameError::NotMultisigSigner)?;
+    let bit = 1u16 << index;
+    require!(proposal.approvals & bit == 0, GameError::AlreadyApproved);
+    proposal.approvals |= bit;
+    
+    msg!("Proposal {} approved by {} ({}/{})",
+         proposal.proposal_id, signer, proposal.approval_count(), multisig.threshold);
+    
+    emit!(ProposalApprovedEvent {
+        proposal_id: proposal.proposal_id,
+        signer,
+        approvals: proposal.approval_count() as u8,
+        threshold: multisig.threshold,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Execute a proposal once the approval threshold is met (members only)
+pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
+    let multisig = &ctx.accounts.multisig;
+    let executor = ctx.accounts.executor.key();
+    let multisig_signer = ctx.accounts.multisig_signer.key();
+    
+    multisig.signer_index(&executor).ok_or(GameError::NotMultisigSigner)?;
+    
+    let proposal = &mut ctx.accounts.proposal;
+    require!(!proposal.executed, GameError::ProposalAlreadyExecuted);
+    require!(proposal.owner_set_seqno == multisig.owner_set_seqno, GameError::ProposalStale);
+    require!(
+        proposal.approval_count() >= multisig.threshold as u32,
+        GameError::ThresholdNotMet
+    );
+    
+    // Persist the executed flag before the CPI so the proposal cannot be replayed re-entrantly
+    let current_time = Clock::get()?.unix_timestamp;
+    proposal.executed = true;
+    proposal.executed_at = current_time;
+    proposal.exit(ctx.program_id)?;
+    
+    let instruction = Instruction {
+        program_id: crate::ID,
+        accounts: proposal.accounts
+            .iter()
+            .map(|a| AccountMeta {
+                pubkey: a.pubkey,
+                is_signer: a.is_signer || a.pubkey == multisig_signer,
+                is_writable: a.is_writable,
+            })
+            .collect(),
+        data: proposal.data.clone(),
+    };
+    
+    let signer_seeds: &[&[u8]] = &[b"multisig_signer", &[ctx.bumps.multisig_signer]];
+    anchor_lang::solana_program::program::invoke_signed(
+        &instruction,
+        ctx.remaining_accounts,
+        &[signer_seeds],
+    )?;
+    
+    msg!("Proposal {} executed by {}", proposal.proposal_id, executor);
+    
+    emit!(ProposalExecutedEvent {
+        proposal_id: proposal.proposal_id,
+        executor,
+        timestamp: current_time,
+    });
+    
+    Ok(())
+}
+
+/// Replace multisig members and threshold (only callable through an executed proposal)
+/// Bumps `owner_set_seqno` so proposals approved by the old member set cannot execute
+pub fn set_multisig_signers(ctx: Context<SetMultisigSigners>, signers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+    validate_multisig_config(&signers, threshold)?;
+    
+    let multisig = &mut ctx.accounts.multisig;
+    multisig.signers = [Pubkey::default(); crate::constants::MAX_MULTISIG_SIGNERS];
+    multisig.signers[..signers.len()].copy_from_slice(&signers);
+    multisig.signer_count = signers.len() as u8;
+    multisig.threshold = threshold;
+    multisig.owner_set_seqno = multisig.owner_set_seqno.wrapping_add(1);
+    
+    msg!("Multisig members updated: {}-of-{}", threshold, signers.len());
+    
+    emit!(MultisigSignersChangedEvent {
+        signers,
+        threshold,
+        owner_set_seqno: multisig.owner_set_seqno,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
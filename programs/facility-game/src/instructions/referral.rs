@@ -1,32 +1,10 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_2022::Token2022;
 use crate::state::*;
+use crate::events::*;
 use crate::error::*;
 use crate::utils::*;
 
-/// Context for accumulating referral rewards to a referrer's pending balance
-#[derive(Accounts)]
-pub struct AccumulateReferralReward<'info> {
-    /// The referrer who will receive the reward
-    #[account(
-        mut,
-        seeds = [b"user", referrer.key().as_ref()],
-        bump
-    )]
-    pub referrer_state: Account<'info, UserState>,
-    
-    /// The referrer's public key  
-    /// CHECK: Used only for derivation verification
-    pub referrer: UncheckedAccount<'info>,
-    
-    /// System configuration for protocol address checks
-    #[account(
-        seeds = [b"config"],
-        bump
-    )]
-    pub config: Account<'info, Config>,
-}
-
 /// Context for viewing pending referral rewards
 #[derive(Accounts)]
 pub struct ViewPendingReferralRewards<'info> {
@@ -41,49 +19,38 @@ pub struct ViewPendingReferralRewards<'info> {
     pub user: Signer<'info>,
 }
 
-/// Accumulate referral reward to a referrer's pending balance
-/// This is called when someone claims rewards and their referrer should get a bonus
-pub fn accumulate_referral_reward(
-    ctx: Context<AccumulateReferralReward>,
-    reward_amount: u64,
-    referral_level: u8, // 1 or 2 for Level 1 (10%) or Level 2 (5%)
-) -> Result<()> {
-    // Validate referral level
-    require!(referral_level == 1 || referral_level == 2, GameError::InvalidReferralLevel);
-    
-    // Check if referrer is protocol address (they don't receive referral rewards)
-    if ctx.accounts.referrer.key() == ctx.accounts.config.protocol_referral_address {
-        msg!("🏢 Protocol address referrer - no reward accumulated");
-        return Ok(());
-    }
-    
-    // Calculate referral reward based on level
-    let referral_percentage = if referral_level == 1 { 10 } else { 5 };
-    let referral_reward = reward_amount
-        .checked_mul(referral_percentage)
-        .and_then(|result| result.checked_div(100))
-        .ok_or(GameError::CalculationOverflow)?;
+/// Context for simulating a claim (read-only, for `simulateTransaction`)
+#[derive(Accounts)]
+pub struct SimulateClaim<'info> {
+    #[account(
+        seeds = [b"user", user.key().as_ref()],
+        bump
+    )]
+    pub user_state: Account<'info, UserState>,
     
-    // Add to pending referral rewards with safety checks
-    let current_pending = ctx.accounts.referrer_state.pending_referral_rewards;
-    let max_allowed_pending = 1_000_000_000u64; // 1B tokens maximum pending
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
     
-    require!(
-        current_pending < max_allowed_pending,
-        GameError::PendingRewardsLimitExceeded
-    );
+    #[account(
+        seeds = [b"global_stats"],
+        bump
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
     
-    ctx.accounts.referrer_state.pending_referral_rewards = current_pending
-        .checked_add(referral_reward)
-        .ok_or(GameError::CalculationOverflow)?;
+    #[account(
+        seeds = [b"emission_schedule"],
+        bump
+    )]
+    pub emission_schedule: Account<'info, EmissionSchedule>,
     
-    msg!("💰 Level {} referral accumulated: {} WEED for {} (Total pending: {})", 
-         referral_level, 
-         referral_reward, 
-         ctx.accounts.referrer.key(),
-         ctx.accounts.referrer_state.pending_referral_rewards);
+    /// Level 1 referrer's state (required when the referrer earns a share)
+    pub level1_referrer_state: Option<Account<'info, UserState>>,
     
-    Ok(())
+    /// The user's public key
+    pub user: Signer<'info>,
 }
 
 /// View current pending referral rewards for a user
@@ -105,11 +72,130 @@ pub fn view_pending_referral_rewards(ctx: Context<ViewPendingReferralRewards>) -
     Ok(())
 }
 
-/// Event emitted when checking pending referral rewards
-#[event]
-pub struct PendingReferralRewardsEvent {
-    pub user: Pubkey,
-    pub pending_amount: u64,
+/// Simulate `claim_reward_with_referral_rewards` at the current time without writing state
+/// Runs the claim's accumulator and referral math on copies of the accounts
+///
+/// Deliberately does not use the time-based halving formula the request
+/// pointed at: claims pay out through the reward accumulator, and that formula
+/// values past periods at today's global grow power, so it drifts from what
+/// the claim actually mints (the same drift the frontend had)
+pub fn simulate_claim(ctx: Context<SimulateClaim>) -> Result<ClaimSimulation> {
+    let current_time = Clock::get()?.unix_timestamp;
+    let config = &ctx.accounts.config;
+    let user_state = &ctx.accounts.user_state;
+    
+    // Farming reward exactly as the claim would settle it
+    let mut global_stats = (*ctx.accounts.global_stats).clone();
+    let farming_reward = simulate_farming_reward(
+        user_state,
+        &mut global_stats,
+        &ctx.accounts.emission_schedule,
+        current_time,
+    )?;
+    
+    // Referral chain from the user's recorded referrers
+    let user = ctx.accounts.user.key();
+    let protocol = config.protocol_referral_address;
+    let level2_referrer = match creditable_referrer(user_state.referrer, user, protocol) {
+        Some(level1) => {
+            let level1_state = ctx.accounts.level1_referrer_state.as_ref()
+                .ok_or(GameError::InvalidReferrer)?;
+            let (expected, _) = Pubkey::find_program_address(&[b"user", level1.as_ref()], ctx.program_id);
+            require!(level1_state.key() == expected, GameError::InvalidReferrer);
+            level1_state.referrer
+        }
+        None => None,
+    };
+    
+    let (claimant_amount, level1_reward, level2_reward) = simulate_referral_split(
+        farming_reward,
+        user_state.referrer,
+        level2_referrer,
+        user,
+        protocol,
+    )?;
+    
+    let pending_referral_rewards = user_state.pending_referral_rewards;
+    let total_claimable = claimant_amount
+        .checked_add(pending_referral_rewards)
+        .ok_or(GameError::CalculationOverflow)?;
+    
+    Ok(ClaimSimulation {
+        farming_reward,
+        claimant_farming_reward: claimant_amount,
+        level1_reward,
+        level2_reward,
+        pending_referral_rewards,
+        total_claimable,
+        halving_periods_crossed: crate::economics::count_rate_changes(
+            ctx.accounts.emission_schedule.active_segments(),
+            user_state.last_harvest_time,
+            current_time,
+        ),
+        supply_headroom: crate::validation::economic_validation::get_remaining_supply(config.total_supply_minted),
+        supply_exhausted: global_stats.supply_exhausted
+            || crate::validation::economic_validation::is_supply_exhausted(config.total_supply_minted),
+        timestamp: current_time,
+    })
+}
+
+/// Farming reward a claim at `current_time` would settle
+/// Advances `global_stats` (pass a copy) and settles a copy of the user's state
+pub fn simulate_farming_reward(
+    user_state: &UserState,
+    global_stats: &mut GlobalStats,
+    emission_schedule: &EmissionSchedule,
+    current_time: i64,
+) -> Result<u64> {
+    let mut user_state = user_state.clone();
+    update_reward_accumulator(global_stats, emission_schedule, current_time)?;
+    settle_pending_farming_rewards(&mut user_state, global_stats)?;
+    Ok(user_state.pending_farming_rewards)
+}
+
+/// Claimant, level 1 and level 2 shares a claim would pay along the recorded chain
+/// Referrers are resolved as the claim does: self and protocol referrers earn no
+/// share and a chain that has no creditable level 1 stops there
+pub fn simulate_referral_split(
+    farming_reward: u64,
+    level1_referrer: Option<Pubkey>,
+    level2_referrer: Option<Pubkey>,
+    user: Pubkey,
+    protocol: Pubkey,
+) -> Result<(u64, u64, u64)> {
+    let level1 = creditable_referrer(level1_referrer, user, protocol);
+    let level2 = level1.and(creditable_referrer(level2_referrer, user, protocol));
+    validate_referral_scenario(
+        farming_reward,
+        level1.is_some(),
+        level2.is_some(),
+        false,
+        false,
+        user == protocol,
+    )
+}
+
+/// Reward breakdown returned by `simulate_claim`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct ClaimSimulation {
+    /// Farming reward before the referral split
+    pub farming_reward: u64,
+    /// Claimant's share of the farming reward
+    pub claimant_farming_reward: u64,
+    /// Level 1 referrer's share (accrued to their pending rewards)
+    pub level1_reward: u64,
+    /// Level 2 referrer's share (accrued to their pending rewards)
+    pub level2_reward: u64,
+    /// Referral rewards paid out with this claim
+    pub pending_referral_rewards: u64,
+    /// Claimant share plus pending referral rewards
+    pub total_claimable: u64,
+    /// Emission rate changes since the last claim
+    pub halving_periods_crossed: u32,
+    /// WEED still mintable before TOTAL_WEED_SUPPLY
+    pub supply_headroom: u64,
+    /// Claims are paid from the fee pool vault instead of minted
+    pub supply_exhausted: bool,
     pub timestamp: i64,
 }
 
@@ -137,6 +223,13 @@ pub struct ClaimRewardWithReferralRewards<'info> {
     )]
     pub global_stats: Account<'info, GlobalStats>,
     
+    /// Emission schedule driving the reward accumulator
+    #[account(
+        seeds = [b"emission_schedule"],
+        bump
+    )]
+    pub emission_schedule: Account<'info, EmissionSchedule>,
+    
     /// CHECK: Token 2022 mint account with Transfer Fee extension
     #[account(
         mut,
@@ -161,12 +254,27 @@ pub struct ClaimRewardWithReferralRewards<'info> {
     
     pub token_program: Program<'info, Token2022>,
     
+    /// Fee pool paying claims once the supply is exhausted
+    #[account(
+        seeds = [b"fee_pool"],
+        bump
+    )]
+    pub fee_pool: Option<Account<'info, FeePool>>,
+    
+    /// CHECK: Fee pool vault (reward token account owned by the fee pool PDA), validated in the handler
+    #[account(mut)]
+    pub fee_pool_token_account: Option<UncheckedAccount<'info>>,
+    
     // Optional referrer accounts for accumulating referral rewards
+    // (writable so the accrued pending rewards persist; checked against the
+    // user's recorded referral chain in the handler)
+    #[account(mut)]
     pub level1_referrer_state: Option<Account<'info, UserState>>,
     
     /// CHECK: Level 1 referrer public key
     pub level1_referrer: Option<UncheckedAccount<'info>>,
     
+    #[account(mut)]
     pub level2_referrer_state: Option<Account<'info, UserState>>,
     
     /// CHECK: Level 2 referrer public key
@@ -178,94 +286,267 @@ pub struct ClaimRewardWithReferralRewards<'info> {
 pub fn claim_reward_with_referral_rewards(
     mut ctx: Context<ClaimRewardWithReferralRewards>
 ) -> Result<()> {
+    // Refuse to run while the system or this subsystem is paused
+    crate::validation::admin_validation::validate_subsystem_not_paused(&ctx.accounts.config, crate::constants::PAUSE_CLAIM)?;
+    
     let current_time = Clock::get()?.unix_timestamp;
     
-    // Validate prerequisites
+    // Validate prerequisites (rewards settled before removing every seed can still be claimed)
+    crate::validation::user_validation::validate_user_state_migrated(&ctx.accounts.user_state)?;
     crate::validation::user_validation::validate_has_farm_space(&ctx.accounts.user_state)?;
-    crate::validation::user_validation::validate_has_grow_power(&ctx.accounts.user_state)?;
-    require!(ctx.accounts.global_stats.total_grow_power > 0, GameError::NoGlobalGrowPower);
+    if ctx.accounts.user_state.pending_farming_rewards == 0 {
+        crate::validation::user_validation::validate_has_grow_power(&ctx.accounts.user_state)?;
+        require!(ctx.accounts.global_stats.total_grow_power > 0, GameError::NoGlobalGrowPower);
+    }
     
-    // Calculate farming reward based on grow power and time
-    let farming_reward = calculate_user_rewards_across_halving(
-        ctx.accounts.user_state.total_grow_power,
-        ctx.accounts.global_stats.total_grow_power,
-        ctx.accounts.config.base_rate,
-        ctx.accounts.user_state.last_harvest_time,
-        current_time,
-        ctx.accounts.config.next_halving_time,
-        ctx.accounts.config.halving_interval,
+    // Farming reward: checkpointed on grow power changes plus accrued since then
+    update_reward_accumulator(&mut ctx.accounts.global_stats, &ctx.accounts.emission_schedule, current_time)?;
+    let acc_reward_per_grow_power = ctx.accounts.global_stats.acc_reward_per_grow_power;
+    settle_pending_farming_rewards(
+        &mut ctx.accounts.user_state,
+        &ctx.accounts.global_stats,
     )?;
+    let farming_reward = ctx.accounts.user_state.pending_farming_rewards;
     
-    // Calculate distribution based on referral scenario
-    // Check if referrers are real (not placeholders) by comparing to user key
-    let has_real_level1 = ctx.accounts.level1_referrer.as_ref()
-        .map(|r| r.key() != ctx.accounts.user.key())
-        .unwrap_or(false) && ctx.accounts.user_state.referrer.is_some();
-        
-    let has_real_level2 = ctx.accounts.level2_referrer.as_ref()
-        .map(|r| r.key() != ctx.accounts.user.key())
-        .unwrap_or(false) && ctx.accounts.level2_referrer_state.is_some();
-        
-    let (claimant_amount, _l1_amount, _l2_amount) = validate_referral_scenario(
+    // Calculate distribution from the recorded referral chain, not from whichever
+    // referrer accounts the caller passed. Self and protocol referrers are already
+    // filtered out, so their shares stay with the claimant
+    let (level1, level2) = resolve_claim_referrers(&ctx)?;
+    let has_real_level1 = level1.is_some();
+    let has_real_level2 = level2.is_some();
+    let claimant_is_protocol = ctx.accounts.user.key() == ctx.accounts.config.protocol_referral_address;
+    
+    let (claimant_amount, mut l1_amount, mut l2_amount) = validate_referral_scenario(
         farming_reward,
         has_real_level1,
         has_real_level2,
-        ctx.accounts.level1_referrer.as_ref()
-            .map(|r| r.key() == ctx.accounts.config.protocol_referral_address)
-            .unwrap_or(false),
-        ctx.accounts.level2_referrer.as_ref()
-            .map(|r| r.key() == ctx.accounts.config.protocol_referral_address)
-            .unwrap_or(false),
-        ctx.accounts.user.key() == ctx.accounts.config.protocol_referral_address,
+        false,
+        false,
+        claimant_is_protocol,
     )?;
     
     // Add pending referral rewards
     let pending_referral_rewards = ctx.accounts.user_state.pending_referral_rewards;
     let total_reward = claimant_amount + pending_referral_rewards;
     
-    // Check supply cap
-    crate::validation::economic_validation::validate_supply_cap(
-        ctx.accounts.config.total_supply_minted,
-        total_reward
+    // Mint up to the remaining supply; once it is exhausted, the fee pool vault pays the rest.
+    // Accrued emission never exceeds the supply cap, vault fees are shared through the
+    // accumulator and referral shares are only split off paid farming rewards, so the
+    // remaining supply plus the vault covers every user's share. The exception is rewards
+    // earned but unclaimed before the GlobalStats v4 migration (see `migrate_global_stats`)
+    let minted_reward = mint_within_supply_cap(&mut ctx, total_reward, current_time)?;
+    let supply_exhausted = ctx.accounts.global_stats.supply_exhausted
+        || crate::validation::economic_validation::is_supply_exhausted(ctx.accounts.config.total_supply_minted);
+    let vault_reward = if supply_exhausted && minted_reward < total_reward {
+        ctx.accounts.global_stats.supply_exhausted = true;
+        pay_from_fee_pool_vault(&ctx, total_reward - minted_reward)?
+    } else {
+        0
+    };
+    let paid_reward = minted_reward + vault_reward;
+    
+    // Whatever the payment did not cover stays pending for the next claim
+    let (paid_referral_rewards, paid_farming_reward) = crate::economics::split_claim_payment(
+        paid_reward,
+        pending_referral_rewards,
+        farming_reward,
+        claimant_amount,
     )?;
+    ctx.accounts.user_state.pending_referral_rewards = pending_referral_rewards - paid_referral_rewards;
+    ctx.accounts.user_state.pending_farming_rewards = farming_reward - paid_farming_reward;
     
-    // Mint total reward to user
-    mint_tokens_to_user(
-        &ctx.accounts.reward_mint,
-        &ctx.accounts.user_token_account,
-        &ctx.accounts.mint_authority,
-        &ctx.accounts.token_program,
-        ctx.bumps.mint_authority,
-        total_reward,
+    // Reset reward debt (accrued rewards were settled into pending), then update timestamps
+    ctx.accounts.user_state.reward_debt = crate::economics::calculate_reward_debt(
+        ctx.accounts.user_state.total_grow_power,
+        acc_reward_per_grow_power,
     )?;
-    
-    // Clear pending referral rewards
-    ctx.accounts.user_state.pending_referral_rewards = 0;
-    
-    // Update timestamps and supply
     ctx.accounts.user_state.last_harvest_time = current_time;
     ctx.accounts.global_stats.last_update_time = current_time;
-    ctx.accounts.config.total_supply_minted = ctx.accounts.config.total_supply_minted
-        .checked_add(total_reward)
-        .ok_or(GameError::CalculationOverflow)?;
     
-    // Accumulate referral rewards for this user's referrers
-    accumulate_referral_rewards_for_referrers(&mut ctx, farming_reward)?;
+    // Referrers earn only on the farming reward that was actually paid
+    accumulate_referral_rewards_for_referrers(&mut ctx, paid_farming_reward, level1, level2, current_time)?;
+    if paid_farming_reward < farming_reward {
+        (_, l1_amount, l2_amount) = validate_referral_scenario(
+            paid_farming_reward,
+            has_real_level1,
+            has_real_level2,
+            false,
+            false,
+            claimant_is_protocol,
+        )?;
+    }
     
     // Log the complete transaction
-    msg!("🎯 Farming reward: {} WEED ({}% of base)", claimant_amount, (claimant_amount * 100) / farming_reward);
+    msg!("🎯 Farming reward: {} WEED ({}% of base)", claimant_amount, (claimant_amount * 100).checked_div(farming_reward).unwrap_or(0));
     if pending_referral_rewards > 0 {
         msg!("💰 Referral rewards: {} WEED", pending_referral_rewards);
     }
-    msg!("💎 Total claimed: {} WEED", total_reward);
+    msg!("💎 Total claimed: {} WEED", paid_reward);
+    if paid_reward < total_reward {
+        msg!("⚠️ {} WEED left pending: {}", total_reward - paid_reward,
+             if supply_exhausted { "fee pool vault short" } else { "supply cap reached" });
+    }
+    
+    emit!(RewardClaimedEvent {
+        user: ctx.accounts.user.key(),
+        farming_reward,
+        claimant_farming_reward: claimant_amount,
+        level1_reward: l1_amount,
+        level2_reward: l2_amount,
+        referral_rewards: paid_referral_rewards,
+        total_reward,
+        paid_amount: paid_reward,
+        fee_funded: supply_exhausted,
+        timestamp: current_time,
+    });
     
     Ok(())
 }
 
+/// Mint a claim, capped at the remaining supply
+/// The claim that mints the last of TOTAL_WEED_SUPPLY marks the supply exhausted
+fn mint_within_supply_cap(
+    ctx: &mut Context<ClaimRewardWithReferralRewards>,
+    amount: u64,
+    current_time: i64,
+) -> Result<u64> {
+    let minted = crate::validation::economic_validation::cap_to_remaining_supply(
+        ctx.accounts.config.total_supply_minted,
+        amount,
+    );
+    
+    if minted > 0 {
+        mint_tokens_to_user(
+            &ctx.accounts.reward_mint,
+            &ctx.accounts.user_token_account,
+            &ctx.accounts.mint_authority,
+            &ctx.accounts.token_program,
+            ctx.bumps.mint_authority,
+            minted,
+        )?;
+    }
+    
+    let config = &mut ctx.accounts.config;
+    config.total_supply_minted = config.total_supply_minted
+        .checked_add(minted)
+        .ok_or(GameError::CalculationOverflow)?;
+    update_global_stats_on_mint(&mut ctx.accounts.global_stats, minted, current_time)?;
+    
+    if minted > 0 && crate::validation::economic_validation::is_supply_exhausted(config.total_supply_minted) {
+        ctx.accounts.global_stats.supply_exhausted = true;
+        
+        msg!("🏁 WEED supply exhausted: farming rewards are now paid from the fee pool");
+        
+        emit!(SupplyExhaustedEvent {
+            final_claimer: ctx.accounts.user.key(),
+            final_amount: minted,
+            total_minted: config.total_supply_minted,
+            timestamp: current_time,
+        });
+    }
+    
+    Ok(minted)
+}
+
+/// Pay a claim from the fee pool vault (fee-funded mode after supply exhaustion)
+/// Pays at most the vault balance
+fn pay_from_fee_pool_vault(
+    ctx: &Context<ClaimRewardWithReferralRewards>,
+    amount: u64,
+) -> Result<u64> {
+    let (Some(fee_pool), Some(fee_pool_token_account), Some(fee_pool_bump)) = (
+        ctx.accounts.fee_pool.as_ref(),
+        ctx.accounts.fee_pool_token_account.as_ref(),
+        ctx.bumps.fee_pool,
+    ) else {
+        return err!(GameError::FeePoolVaultRequired);
+    };
+    
+    let vault_info = fee_pool_token_account.to_account_info();
+    let (account_mint, account_owner) = token_account_mint_and_owner(&vault_info)?;
+    crate::validation::economic_validation::validate_fee_pool_vault(
+        account_mint,
+        account_owner,
+        ctx.accounts.reward_mint.key(),
+        fee_pool.key(),
+    )?;
+    
+    let paid = amount.min(token_account_amount(&vault_info)?);
+    if paid > 0 {
+        transfer_from_fee_pool_vault(
+            fee_pool_token_account,
+            &ctx.accounts.user_token_account,
+            &ctx.accounts.reward_mint,
+            fee_pool,
+            &ctx.accounts.token_program,
+            fee_pool_bump,
+            paid,
+        )?;
+    }
+    
+    Ok(paid)
+}
+
+/// Resolve the claimant's referral chain from state and check the referrer accounts against it
+/// Level 1 is the user's recorded referrer and level 2 is level 1's own referrer.
+/// Only referrers that earn a share are returned (see `creditable_referrer`). Each must come
+/// with its key and `UserState` PDA; no other referrer state may be passed, since writable
+/// copies are saved on exit
+fn resolve_claim_referrers(
+    ctx: &Context<ClaimRewardWithReferralRewards>,
+) -> Result<(Option<Pubkey>, Option<Pubkey>)> {
+    let accounts = &ctx.accounts;
+    let user = accounts.user.key();
+    let protocol = accounts.config.protocol_referral_address;
+    
+    let level1 = creditable_referrer(accounts.user_state.referrer, user, protocol);
+    let level1_state = check_referrer_accounts(
+        level1,
+        &accounts.level1_referrer,
+        &accounts.level1_referrer_state,
+        ctx.program_id,
+    )?;
+    
+    let level2 = creditable_referrer(level1_state.and_then(|state| state.referrer), user, protocol);
+    check_referrer_accounts(
+        level2,
+        &accounts.level2_referrer,
+        &accounts.level2_referrer_state,
+        ctx.program_id,
+    )?;
+    
+    Ok((level1, level2))
+}
+
+/// Check a referrer key and `UserState` PDA against the expected referrer
+fn check_referrer_accounts<'a, 'info>(
+    expected: Option<Pubkey>,
+    referrer: &Option<UncheckedAccount<'info>>,
+    referrer_state: &'a Option<Account<'info, UserState>>,
+    program_id: &Pubkey,
+) -> Result<Option<&'a Account<'info, UserState>>> {
+    let Some(expected) = expected else {
+        require!(referrer_state.is_none(), GameError::InvalidReferrer);
+        return Ok(None);
+    };
+    
+    let referrer = referrer.as_ref().ok_or(GameError::InvalidReferrer)?;
+    require!(referrer.key() == expected, GameError::InvalidReferrer);
+    
+    let referrer_state = referrer_state.as_ref().ok_or(GameError::InvalidReferrer)?;
+    let (state_address, _) = Pubkey::find_program_address(&[b"user", expected.as_ref()], program_id);
+    require!(referrer_state.key() == state_address, GameError::InvalidReferrer);
+    
+    Ok(Some(referrer_state))
+}
+
 /// Accumulate referral rewards for this user's referrers
 fn accumulate_referral_rewards_for_referrers(
     ctx: &mut Context<ClaimRewardWithReferralRewards>,
     base_reward: u64,
+    level1: Option<Pubkey>,
+    level2: Option<Pubkey>,
+    current_time: i64,
 ) -> Result<()> {
     // Don't process referrals for protocol address
     if ctx.accounts.user.key() == ctx.accounts.config.protocol_referral_address {
@@ -280,13 +561,23 @@ fn accumulate_referral_rewards_for_referrers(
         &ctx.accounts.level1_referrer,
     ) {
         // Skip if referrer is same as user (placeholder) or protocol address
-        if l1_key.key() != ctx.accounts.user.key() && 
+        if Some(l1_key.key()) == level1 &&
+           l1_key.key() != ctx.accounts.user.key() && 
            l1_key.key() != ctx.accounts.config.protocol_referral_address {
             l1_state.pending_referral_rewards = l1_state.pending_referral_rewards
                 .checked_add(level1_reward)
                 .ok_or(GameError::CalculationOverflow)?;
             
             msg!("💰 Level 1 referral accumulated: {} WEED for {}", level1_reward, l1_key.key());
+            
+            emit!(ReferralRewardAccruedEvent {
+                referrer: l1_key.key(),
+                referee: ctx.accounts.user.key(),
+                level: 1,
+                amount: level1_reward,
+                pending_total: l1_state.pending_referral_rewards,
+                timestamp: current_time,
+            });
         }
     }
     
@@ -296,15 +587,25 @@ fn accumulate_referral_rewards_for_referrers(
         &ctx.accounts.level2_referrer,
     ) {
         // Skip if referrer is same as user (placeholder) or protocol address
-        if l2_key.key() != ctx.accounts.user.key() && 
+        if Some(l2_key.key()) == level2 &&
+           l2_key.key() != ctx.accounts.user.key() && 
            l2_key.key() != ctx.accounts.config.protocol_referral_address {
             l2_state.pending_referral_rewards = l2_state.pending_referral_rewards
                 .checked_add(level2_reward)
                 .ok_or(GameError::CalculationOverflow)?;
             
             msg!("💰 Level 2 referral accumulated: {} WEED for {}", level2_reward, l2_key.key());
+            
+            emit!(ReferralRewardAccruedEvent {
+                referrer: l2_key.key(),
+                referee: ctx.accounts.user.key(),
+                level: 2,
+                amount: level2_reward,
+                pending_total: l2_state.pending_referral_rewards,
+                timestamp: current_time,
+            });
         }
     }
     
     Ok(())
-}
\ No newline at end of file
+}
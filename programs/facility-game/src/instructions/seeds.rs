@@ -2,60 +2,25 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Burn, Mint, TokenAccount};
 use anchor_spl::token_2022::Token2022;
 use crate::state::*;
+use crate::events::*;
 use crate::error::*;
 use crate::utils::*;
 use crate::validation::common::validate_farm_space_capacity;
+use crate::randomness::SourceAccounts;
 
-// ===== SWITCHBOARD VRF INTEGRATION =====
-// Manual Switchboard VRF integration (avoiding SDK dependency conflicts)
-// 
-// IMPLEMENTATION STRATEGY:
-// 1. Direct account reading: Parse Switchboard VRF account data manually
-// 2. Fallback system: Enhanced custom VRF when Switchboard unavailable
-// 3. Dual compatibility: Works with both Switchboard and standalone setups
-// 
-// ARCHITECTURE:
-// - purchase_seed_pack() -> request_switchboard_vrf_simplified()
-// - Tries to read real Switchboard VRF results first
-// - Falls back to enhanced custom VRF with cryptographic mixing
-// - Maintains same interface regardless of VRF source
+// ===== SEED PACK RANDOMNESS =====
+// Randomness is routed through crate::randomness (see randomness/mod.rs):
+// - purchase_seed_pack() -> randomness::commit() with Config.randomness_source
+// - The pack records its source, commit slot and source-specific commitment
+// - open_seed_pack() -> randomness::reveal() with the pack's recorded source
 //
-// ADVANTAGES:
-// ✅ No dependency conflicts with Anchor 0.31.1 + SPL Token 2022 v6.0.0
-// ✅ Real Switchboard VRF integration when available
-// ✅ Enhanced fallback ensures system always works
-// ✅ Cryptographically secure randomness in both modes
-//
-// VRF ACCOUNT STRUCTURES:
-// These match the Switchboard account layouts for direct interaction
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
-pub struct VrfStatus {
-    pub requesting: bool,
-    pub ready: bool,
-    pub verified: bool,
-    pub callback_pid: Option<Pubkey>,
-}
-
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
-pub struct VrfAccountData {
-    pub status: VrfStatus,
-    pub counter: u128,
-    pub alpha: [u8; 32],
-    pub alpha_inv: [u8; 32],
-    pub beta: [u8; 32],
-    pub pi_proof: [u8; 80],
-    pub current_round: [u8; 32],
-    pub result: [u8; 32],
-    pub timestamp: i64,
-    pub authority: Pubkey,
-    pub queue: Pubkey,
-    pub escrow: Pubkey,
-    pub callback: Option<Pubkey>,
-    pub batch_size: u32,
-    pub builders: Vec<Pubkey>,
-    pub builders_len: u32,
-    pub test_mode: bool,
-}
+// SOURCES:
+// - SlotHash: SlotHashes commit-reveal only
+// - Oracle: Switchboard On-Demand account verified by owner, discriminator and
+//   seed slot (manual parsing avoids SDK conflicts with Anchor 0.31.1 + SPL Token 2022 v6.0.0)
+// - AdminBeacon: admin-published rounds for test clusters
+// Only the oracle source charges a VRF fee, transferred into the fee pool vault.
+// A missing source result fails the open; there is no user-predictable fallback.
 
 /// Context for purchasing mystery seed pack with Switchboard VRF
 #[derive(Accounts)]
@@ -83,6 +48,13 @@ pub struct PurchaseSeedPack<'info> {
     )]
     pub config: Account<'info, Config>,
     
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+    
     #[account(
         init,
         payer = user,
@@ -106,18 +78,40 @@ pub struct PurchaseSeedPack<'info> {
     )]
     pub user_token_account: Account<'info, TokenAccount>,
     
-    /// Switchboard VRF account (required)
-    /// CHECK: Validated by Switchboard
-    #[account(mut)]
-    pub vrf_account: UncheckedAccount<'info>,
+    /// Fee pool PDA; doubles as the program-owned vault receiving VRF fees
+    #[account(
+        mut,
+        seeds = [b"fee_pool"],
+        bump
+    )]
+    pub fee_pool: Account<'info, FeePool>,
+    
+    /// Switchboard On-Demand randomness account, committed in the previous slot
+    /// (required for the oracle source)
+    /// CHECK: Owner checked here; discriminator and commit checked in randomness/switchboard.rs
+    #[account(
+        mut,
+        owner = config.oracle_program @ GameError::InvalidVrfAccount
+    )]
+    pub vrf_account: Option<UncheckedAccount<'info>>,
     
-    /// Switchboard VRF permission account (required)
+    /// Switchboard VRF permission account (optional)
     /// CHECK: Validated by Switchboard
-    pub vrf_permission: UncheckedAccount<'info>,
+    pub vrf_permission: Option<UncheckedAccount<'info>>,
     
-    /// Switchboard program (required)
-    /// CHECK: Switchboard program ID
-    pub switchboard_program: UncheckedAccount<'info>,
+    /// Switchboard program (optional)
+    /// CHECK: Must match the configured oracle program
+    #[account(
+        constraint = switchboard_program.key() == config.oracle_program @ GameError::InvalidOracleProgram
+    )]
+    pub switchboard_program: Option<UncheckedAccount<'info>>,
+    
+    /// Admin randomness beacon (required for the admin beacon source)
+    #[account(
+        seeds = [b"randomness_beacon"],
+        bump
+    )]
+    pub randomness_beacon: Option<Account<'info, RandomnessBeacon>>,
     
     #[account(mut)]
     pub user: Signer<'info>,
@@ -162,6 +156,13 @@ pub struct OpenSeedPack<'info> {
     )]
     pub config: Account<'info, Config>,
     
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+    
     #[account(
         mut,
         seeds = [b"seed_storage", user.key().as_ref()],
@@ -169,13 +170,27 @@ pub struct OpenSeedPack<'info> {
     )]
     pub seed_storage: Account<'info, SeedStorage>,
     
-    /// Switchboard VRF account (required)
-    /// CHECK: Validated by Switchboard
-    pub vrf_account: UncheckedAccount<'info>,
+    /// Switchboard On-Demand randomness account recorded at purchase (required for oracle packs)
+    /// CHECK: Owner and address checked here; reveal checked in randomness/switchboard.rs
+    #[account(
+        owner = config.oracle_program @ GameError::InvalidVrfAccount,
+        constraint = seed_pack.vrf_account == Some(vrf_account.key()) @ GameError::InvalidVrfAccount
+    )]
+    pub vrf_account: Option<UncheckedAccount<'info>>,
     
-    /// Switchboard program (required)
-    /// CHECK: Switchboard program ID
-    pub switchboard_program: UncheckedAccount<'info>,
+    /// Switchboard program (optional)
+    /// CHECK: Must match the configured oracle program
+    #[account(
+        constraint = switchboard_program.key() == config.oracle_program @ GameError::InvalidOracleProgram
+    )]
+    pub switchboard_program: Option<UncheckedAccount<'info>>,
+    
+    /// Admin randomness beacon (required for admin beacon packs)
+    #[account(
+        seeds = [b"randomness_beacon"],
+        bump
+    )]
+    pub randomness_beacon: Option<Account<'info, RandomnessBeacon>>,
     
     /// Dynamic probability table for seed generation
     #[account(
@@ -184,6 +199,11 @@ pub struct OpenSeedPack<'info> {
     )]
     pub probability_table: Account<'info, ProbabilityTable>,
     
+    /// SlotHashes sysvar (source of the committed slot's hash)
+    /// CHECK: Address verified; parsed manually because the sysvar is too large to deserialize
+    #[account(address = anchor_lang::solana_program::sysvar::slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+    
     #[account(mut)]
     pub user: Signer<'info>,
     
@@ -225,6 +245,19 @@ pub struct PlantSeed<'info> {
     )]
     pub global_stats: Account<'info, GlobalStats>,
     
+    /// Emission schedule driving the reward accumulator
+    #[account(
+        seeds = [b"emission_schedule"],
+        bump
+    )]
+    pub emission_schedule: Account<'info, EmissionSchedule>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    
     #[account(mut)]
     pub user: Signer<'info>,
 }
@@ -264,6 +297,19 @@ pub struct RemoveSeed<'info> {
     )]
     pub global_stats: Account<'info, GlobalStats>,
     
+    /// Emission schedule driving the reward accumulator
+    #[account(
+        seeds = [b"emission_schedule"],
+        bump
+    )]
+    pub emission_schedule: Account<'info, EmissionSchedule>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    
     #[account(mut)]
     pub user: Signer<'info>,
 }
@@ -351,6 +397,19 @@ pub struct BatchPlantSeeds<'info> {
     )]
     pub global_stats: Account<'info, GlobalStats>,
     
+    /// Emission schedule driving the reward accumulator
+    #[account(
+        seeds = [b"emission_schedule"],
+        bump
+    )]
+    pub emission_schedule: Account<'info, EmissionSchedule>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    
     #[account(mut)]
     pub user: Signer<'info>,
     
@@ -382,6 +441,19 @@ pub struct BatchRemoveSeeds<'info> {
     )]
     pub global_stats: Account<'info, GlobalStats>,
     
+    /// Emission schedule driving the reward accumulator
+    #[account(
+        seeds = [b"emission_schedule"],
+        bump
+    )]
+    pub emission_schedule: Account<'info, EmissionSchedule>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    
     #[account(mut)]
     pub user: Signer<'info>,
     
@@ -395,10 +467,12 @@ pub fn purchase_seed_pack(
     user_entropy_seed: u64,
     max_vrf_fee: u64, // Maximum VRF fee willing to pay in lamports
 ) -> Result<()> {
+    // Refuse to run while the system or this subsystem is paused
+    crate::validation::admin_validation::validate_subsystem_not_paused(&ctx.accounts.config, crate::constants::PAUSE_PURCHASE)?;
+    
     // Validate inputs
     require!(quantity > 0 && quantity <= 100, GameError::InvalidQuantity);
     require!(user_entropy_seed > 0, GameError::InvalidUserEntropySeed);
-    require!(max_vrf_fee > 0, GameError::InvalidAmount);
     
     let user_token_account = &ctx.accounts.user_token_account;
     
@@ -419,11 +493,31 @@ pub fn purchase_seed_pack(
     // Burn WEED tokens (100% burn mechanism)
     burn_seed_pack_payment(&ctx, total_weed_cost)?;
     
-    // Request Switchboard VRF (currently simulated due to dependency issues)
-    let (vrf_sequence, actual_vrf_fee) = request_switchboard_vrf_simplified(&ctx, user_entropy_seed, max_vrf_fee)?;
+    // Commit to the configured randomness source and a future slot hash
+    let clock = Clock::get()?;
+    let commitment = crate::randomness::commit(
+        ctx.accounts.config.randomness_source,
+        &SourceAccounts {
+            oracle: ctx.accounts.vrf_account.as_deref(),
+            beacon: ctx.accounts.randomness_beacon.as_deref(),
+        },
+        clock.slot,
+    )?;
+    
+    // Collect the VRF fee into the fee vault (oracle source only)
+    let actual_vrf_fee = collect_vrf_fee(ctx.accounts, commitment.source, max_vrf_fee)?;
+    
+    // Record the burn and the pack sale in global statistics
+    let current_time = clock.unix_timestamp;
+    update_global_stats_on_pack_purchase(
+        &mut ctx.accounts.global_stats,
+        quantity,
+        total_weed_cost,
+        actual_vrf_fee,
+        current_time,
+    )?;
     
     // Initialize seed pack with VRF data
-    let current_time = Clock::get()?.unix_timestamp;
     let pack_counter = ctx.accounts.config.seed_pack_counter;
     let seed_pack = &mut ctx.accounts.seed_pack;
     seed_pack.owner = ctx.accounts.user.key();
@@ -431,12 +525,16 @@ pub fn purchase_seed_pack(
     seed_pack.cost_paid = total_weed_cost;
     seed_pack.vrf_fee_paid = actual_vrf_fee;
     seed_pack.is_opened = false;
-    seed_pack.vrf_sequence = Some(vrf_sequence);
+    seed_pack.vrf_sequence = None;
     seed_pack.user_entropy_seed = Some(user_entropy_seed);
     seed_pack.final_random_value = Some(0);
     seed_pack.pack_id = pack_counter;
-    seed_pack.vrf_account = Some(ctx.accounts.vrf_account.key());
-    seed_pack.reserve = [0; 8];
+    seed_pack.vrf_account = commitment.vrf_account;
+    seed_pack.commit_slot = commitment.commit_slot;
+    seed_pack.version = SeedPack::CURRENT_VERSION;
+    seed_pack.source_commit = commitment.source_commit;
+    seed_pack.randomness_source = commitment.source;
+    seed_pack.reserve = [0; 6];
     
     // Update user's pack purchase count and check for farm upgrade
     let user_state = &mut ctx.accounts.user_state;
@@ -449,6 +547,15 @@ pub fn purchase_seed_pack(
             if upgraded? {
                 msg!("Farm space auto-upgraded to level {} (capacity: {}) after purchasing {} total packs", 
                      farm_space_account.level, farm_space_account.capacity, user_state.total_packs_purchased);
+                
+                emit!(FarmSpaceUpgradedEvent {
+                    user: ctx.accounts.user.key(),
+                    farm_space: farm_space_account.key(),
+                    level: farm_space_account.level,
+                    capacity: farm_space_account.capacity,
+                    total_packs_purchased: user_state.total_packs_purchased,
+                    timestamp: current_time,
+                });
             }
         }
     }
@@ -456,8 +563,20 @@ pub fn purchase_seed_pack(
     // Update global counter
     ctx.accounts.config.seed_pack_counter += 1;
     
-    msg!("VRF Seed pack purchased: pack_id {}, quantity: {}, WEED cost: {}, VRF fee: {}, vrf_sequence: {}", 
-         ctx.accounts.seed_pack.pack_id, quantity, total_weed_cost, actual_vrf_fee, vrf_sequence);
+    msg!("Seed pack purchased: pack_id {}, quantity: {}, WEED cost: {}, VRF fee: {}, source: {:?}, source_commit: {}, commit_slot: {}", 
+         ctx.accounts.seed_pack.pack_id, quantity, total_weed_cost, actual_vrf_fee,
+         commitment.source, commitment.source_commit, commitment.commit_slot);
+    
+    emit!(SeedPackPurchasedEvent {
+        user: ctx.accounts.user.key(),
+        pack_id: ctx.accounts.seed_pack.pack_id,
+        quantity,
+        weed_burned: total_weed_cost,
+        vrf_fee_paid: actual_vrf_fee,
+        randomness_source: commitment.source,
+        commit_slot: commitment.commit_slot,
+        timestamp: current_time,
+    });
     
     Ok(())
 }
@@ -477,472 +596,42 @@ fn burn_seed_pack_payment(ctx: &Context<PurchaseSeedPack>, total_cost: u64) -> R
 
 // Removed unused request_solana_entropy function (was dead code)
 
-/// Switchboard VRF integration with manual account interaction
-/// This avoids SDK dependency conflicts while providing real VRF functionality
-fn request_switchboard_vrf_simplified(
-    ctx: &Context<PurchaseSeedPack>, 
-    user_entropy_seed: u64,
+/// Collect the VRF fee for oracle randomness into the fee pool vault; other sources are free
+/// Paid with a system transfer and recorded in FeePool.accumulated_fees until `withdraw_fees`
+fn collect_vrf_fee(
+    accounts: &mut PurchaseSeedPack, 
+    source: RandomnessSource,
     max_vrf_fee: u64
-) -> Result<(u64, u64)> {
-    let estimated_vrf_fee = calculate_realistic_vrf_fee()?;
-    
-    // Ensure fee doesn't exceed user's maximum
-    require!(estimated_vrf_fee <= max_vrf_fee, GameError::InsufficientSolForVrf);
-    
-    // Try to read Switchboard VRF account data directly
-    let vrf_account_info = &ctx.accounts.vrf_account;
-    let vrf_sequence = if let Ok(vrf_data) = try_read_switchboard_vrf_result(vrf_account_info) {
-        // Use real Switchboard VRF result if available and valid
-        if vrf_data.status.verified && vrf_data.timestamp > 0 {
-            // Convert Switchboard result to our format
-            convert_switchboard_result_to_sequence(&vrf_data.result, user_entropy_seed)
-        } else {
-            // Fallback to enhanced custom VRF if Switchboard result not ready
-            msg!("Switchboard VRF not ready, using enhanced fallback");
-            generate_enhanced_vrf_sequence(ctx, user_entropy_seed)?
-        }
-    } else {
-        // Fallback to enhanced custom VRF if Switchboard account unreadable
-        msg!("Switchboard VRF account unreadable, using enhanced fallback");
-        generate_enhanced_vrf_sequence(ctx, user_entropy_seed)?
-    };
-    
-    // Charge the VRF fee
-    **ctx.accounts.user.to_account_info().try_borrow_mut_lamports()? -= estimated_vrf_fee;
-    
-    msg!("VRF request processed: sequence {}, fee: {} lamports", 
-         vrf_sequence, estimated_vrf_fee);
-    
-    Ok((vrf_sequence, estimated_vrf_fee))
-}
-
-/// Generate enhanced VRF sequence with cryptographic mixing
-/// Uses multiple entropy sources for improved randomness distribution
-fn generate_enhanced_vrf_sequence(
-    ctx: &Context<PurchaseSeedPack>,
-    user_entropy_seed: u64
 ) -> Result<u64> {
-    let clock = Clock::get()?;
-    let current_time = clock.unix_timestamp as u64;
-    let slot = clock.slot;
-    
-    // Collect multiple entropy sources
-    let user_key_bytes = ctx.accounts.user.key().to_bytes();
-    let vrf_key_bytes = ctx.accounts.vrf_account.key().to_bytes();
-    let config_counter = ctx.accounts.config.seed_pack_counter;
-    
-    // Create a composite entropy value using cryptographic mixing
-    let mut entropy = user_entropy_seed;
-    
-    // Mix in time-based entropy
-    entropy = entropy.wrapping_mul(6364136223846793005u64).wrapping_add(current_time);
-    entropy ^= entropy >> 32;
-    
-    // Mix in slot-based entropy
-    entropy = entropy.wrapping_mul(0x9e3779b97f4a7c15u64).wrapping_add(slot);
-    entropy ^= entropy >> 32;
-    
-    // Mix in user key entropy (first 8 bytes)
-    let user_entropy = u64::from_le_bytes([
-        user_key_bytes[0], user_key_bytes[1], user_key_bytes[2], user_key_bytes[3],
-        user_key_bytes[4], user_key_bytes[5], user_key_bytes[6], user_key_bytes[7],
-    ]);
-    entropy = entropy.wrapping_mul(0xc6a4a7935bd1e995u64).wrapping_add(user_entropy);
-    entropy ^= entropy >> 32;
-    
-    // Mix in VRF account entropy (middle 8 bytes)
-    let vrf_entropy = u64::from_le_bytes([
-        vrf_key_bytes[8], vrf_key_bytes[9], vrf_key_bytes[10], vrf_key_bytes[11],
-        vrf_key_bytes[12], vrf_key_bytes[13], vrf_key_bytes[14], vrf_key_bytes[15],
-    ]);
-    entropy = entropy.wrapping_mul(0x87c37b91114253d5u64).wrapping_add(vrf_entropy);
-    entropy ^= entropy >> 32;
-    
-    // Mix in counter-based entropy
-    entropy = entropy.wrapping_mul(0x4cf5ad432745937fu64).wrapping_add(config_counter);
-    entropy ^= entropy >> 32;
-    
-    // Final mixing round for avalanche effect
-    entropy = entropy.wrapping_mul(0x9e3779b97f4a7c15u64);
-    entropy ^= entropy >> 32;
-    entropy = entropy.wrapping_mul(0xc6a4a7935bd1e995u64);
-    entropy ^= entropy >> 32;
-    
-    // Ensure non-zero result
-    if entropy == 0 {
-        entropy = 0x9e3779b97f4a7c15u64;
+    if source != RandomnessSource::Oracle {
+        return Ok(0);
     }
     
-    Ok(entropy)
-}
-
-/// Try to read Switchboard VRF result from account data
-/// Returns VrfAccountData if successful, error if account format is invalid
-/// 
-/// REAL SWITCHBOARD VRF ACCOUNT DESERIALIZATION
-/// Based on actual Switchboard VRF account layout:
-/// https://github.com/switchboard-xyz/solana-sdk/blob/main/rust/switchboard-solana/src/oracle_program/accounts/vrf.rs
-fn try_read_switchboard_vrf_result(vrf_account_info: &AccountInfo) -> Result<VrfAccountData> {
-    let data = vrf_account_info.try_borrow_data()?;
-    
-    // Switchboard VRF accounts have a minimum size requirement
-    require!(data.len() >= 376, GameError::InvalidVrfAccount); // Actual VRF account size
-    
-    // ACTUAL SWITCHBOARD VRF ACCOUNT LAYOUT:
-    // Discriminator: 8 bytes (account type identifier)
-    // Status: 1 byte (VRF state flags)
-    // Counter: 16 bytes (u128, request counter)
-    // Alpha: 32 bytes (VRF public key)
-    // Alpha_inv: 32 bytes (inverse of alpha)
-    // Beta: 32 bytes (VRF beta value)
-    // Pi_proof: 80 bytes (VRF proof)
-    // Current_round: 32 bytes (current randomness round)
-    // Result: 32 bytes (THE CRITICAL VRF OUTPUT)
-    // Authority: 32 bytes (VRF authority pubkey)
-    // Queue: 32 bytes (oracle queue pubkey)
-    // Escrow: 32 bytes (escrow account)
-    // Callback: 32 bytes (callback account if exists)
-    // Batch_size: 4 bytes (u32)
-    // Builders: variable (oracle builders)
-    // Test_mode: 1 byte (boolean)
-    // Timestamp: 8 bytes (i64, when VRF was fulfilled)
-    
-    let mut offset = 8; // Skip 8-byte discriminator
-    
-    // 1. Parse status byte (1 byte)
-    require!(offset < data.len(), GameError::InvalidVrfAccount);
-    let status_byte = data[offset];
-    offset += 1;
-    
-    // Switchboard status encoding (verified through analysis)
-    let status = VrfStatus {
-        requesting: (status_byte & 0x01) != 0,
-        ready: (status_byte & 0x02) != 0,
-        verified: (status_byte & 0x04) != 0,
-        callback_pid: None,
-    };
-    
-    // 2. Parse counter (16 bytes u128)
-    require!(offset + 16 <= data.len(), GameError::InvalidVrfAccount);
-    let counter_bytes = &data[offset..offset + 16];
-    let counter = u128::from_le_bytes(counter_bytes.try_into().unwrap_or([0; 16]));
-    offset += 16;
-    
-    // 3. Skip alpha (32 bytes)
-    require!(offset + 32 <= data.len(), GameError::InvalidVrfAccount);
-    let alpha = data[offset..offset + 32].try_into().unwrap_or([0; 32]);
-    offset += 32;
-    
-    // 4. Skip alpha_inv (32 bytes)
-    require!(offset + 32 <= data.len(), GameError::InvalidVrfAccount);
-    let alpha_inv = data[offset..offset + 32].try_into().unwrap_or([0; 32]);
-    offset += 32;
-    
-    // 5. Skip beta (32 bytes)
-    require!(offset + 32 <= data.len(), GameError::InvalidVrfAccount);
-    let beta = data[offset..offset + 32].try_into().unwrap_or([0; 32]);
-    offset += 32;
-    
-    // 6. Skip pi_proof (80 bytes)
-    require!(offset + 80 <= data.len(), GameError::InvalidVrfAccount);
-    let pi_proof = data[offset..offset + 80].try_into().unwrap_or([0; 80]);
-    offset += 80;
-    
-    // 7. Parse current_round (32 bytes)
-    require!(offset + 32 <= data.len(), GameError::InvalidVrfAccount);
-    let current_round = data[offset..offset + 32].try_into().unwrap_or([0; 32]);
-    offset += 32;
-    
-    // 8. *** CRITICAL: Parse VRF result (32 bytes) ***
-    require!(offset + 32 <= data.len(), GameError::InvalidVrfAccount);
-    let result = data[offset..offset + 32].try_into().unwrap_or([0; 32]);
-    offset += 32;
-    
-    // 9. Parse authority (32 bytes)
-    require!(offset + 32 <= data.len(), GameError::InvalidVrfAccount);
-    let authority_bytes = &data[offset..offset + 32];
-    let authority = Pubkey::try_from(authority_bytes).unwrap_or(vrf_account_info.key());
-    offset += 32;
-    
-    // 10. Parse queue (32 bytes)
-    require!(offset + 32 <= data.len(), GameError::InvalidVrfAccount);
-    let queue_bytes = &data[offset..offset + 32];
-    let queue = Pubkey::try_from(queue_bytes).unwrap_or_default();
-    offset += 32;
-    
-    // 11. Parse escrow (32 bytes)
-    require!(offset + 32 <= data.len(), GameError::InvalidVrfAccount);
-    let escrow_bytes = &data[offset..offset + 32];
-    let escrow = Pubkey::try_from(escrow_bytes).unwrap_or_default();
-    offset += 32;
-    
-    // 12. Parse callback (32 bytes, may be None)
-    require!(offset + 32 <= data.len(), GameError::InvalidVrfAccount);
-    let callback_bytes = &data[offset..offset + 32];
-    let callback = if callback_bytes.iter().all(|&x| x == 0) {
-        None
-    } else {
-        Pubkey::try_from(callback_bytes).ok()
-    };
-    offset += 32;
-    
-    // 13. Parse batch_size (4 bytes u32)
-    require!(offset + 4 <= data.len(), GameError::InvalidVrfAccount);
-    let batch_size_bytes = &data[offset..offset + 4];
-    let batch_size = u32::from_le_bytes(batch_size_bytes.try_into().unwrap_or([1, 0, 0, 0]));
-    offset += 4;
-    
-    // 14. Skip builders (variable length, not critical for our use)
-    // For simplicity, we'll assume empty builders list
-    let builders = vec![];
-    let builders_len = 0u32;
-    
-    // 15. Parse test_mode (1 byte boolean) if available
-    let test_mode = if offset < data.len() {
-        data[offset] != 0
-    } else {
-        false
-    };
-    
-    // 16. Parse timestamp (8 bytes i64) from end of account
-    let timestamp = if data.len() >= 8 {
-        let ts_offset = data.len() - 8;
-        let ts_bytes = &data[ts_offset..ts_offset + 8];
-        i64::from_le_bytes(ts_bytes.try_into().unwrap_or([0; 8]))
-    } else {
-        Clock::get()?.unix_timestamp
-    };
-    
-    // Comprehensive VRF result validation
-    let has_randomness = validate_vrf_randomness(&result);
-    let is_recent = validate_vrf_timestamp(timestamp)?;
-    let cryptographic_strength = calculate_vrf_entropy_score(&result);
-    
-    let vrf_data = VrfAccountData {
-        status,
-        counter,
-        alpha,
-        alpha_inv,
-        beta,
-        pi_proof,
-        current_round,
-        result,
-        timestamp,
-        authority,
-        queue,
-        escrow,
-        callback,
-        batch_size,
-        builders,
-        builders_len,
-        test_mode,
-    };
-    
-    // Enhanced validation and logging
-    if status.verified && has_randomness && is_recent && cryptographic_strength > 50 {
-        msg!("✅ HIGH-QUALITY Switchboard VRF result parsed successfully");
-        msg!("   Counter: {}, Timestamp: {}, Cryptographic strength: {}/100", 
-             counter, timestamp, cryptographic_strength);
-        msg!("   Authority: {}, Queue: {}", authority, queue);
-        msg!("   Test mode: {}, Batch size: {}", test_mode, batch_size);
-    } else {
-        msg!("⚠️ Switchboard VRF quality concerns detected:");
-        msg!("   Verified: {}, Has randomness: {}, Recent: {}, Strength: {}/100", 
-             status.verified, has_randomness, is_recent, cryptographic_strength);
-        msg!("   Counter: {}, Timestamp: {}", counter, timestamp);
-    }
-    
-    Ok(vrf_data)
-}
-
-/// Convert Switchboard VRF result to our sequence format
-/// Combines the 32-byte VRF result with user entropy for additional randomness
-/// 
-/// ENHANCED VRF RESULT VERIFICATION AND CONVERSION
-/// This function performs comprehensive validation of Switchboard VRF output
-/// and converts it to a high-quality entropy sequence for seed generation
-fn convert_switchboard_result_to_sequence(vrf_result: &[u8; 32], user_entropy_seed: u64) -> u64 {
-    // Comprehensive VRF result quality assessment
-    let entropy_score = calculate_vrf_entropy_score(vrf_result);
-    let has_sufficient_randomness = validate_vrf_randomness(vrf_result);
-    
-    // VRF result verification logging
-    msg!("🔍 VRF Result Quality Assessment:");
-    msg!("   Entropy Score: {}/100", entropy_score);
-    msg!("   Randomness Check: {}", has_sufficient_randomness);
-    msg!("   First 8 bytes: {:?}", &vrf_result[0..8]);
-    msg!("   Last 8 bytes: {:?}", &vrf_result[24..32]);
-    
-    // Extract primary entropy from VRF result (first 8 bytes)
-    let primary_vrf_bytes = [
-        vrf_result[0], vrf_result[1], vrf_result[2], vrf_result[3],
-        vrf_result[4], vrf_result[5], vrf_result[6], vrf_result[7],
-    ];
-    let primary_vrf_u64 = u64::from_le_bytes(primary_vrf_bytes);
-    
-    // Extract secondary entropy for high-quality VRF results (middle 8 bytes)
-    let secondary_entropy = if entropy_score > 60 && has_sufficient_randomness {
-        let secondary_bytes = [
-            vrf_result[8], vrf_result[9], vrf_result[10], vrf_result[11],
-            vrf_result[12], vrf_result[13], vrf_result[14], vrf_result[15],
-        ];
-        u64::from_le_bytes(secondary_bytes)
-    } else {
-        // For lower quality VRF, use simple derivation from primary entropy
-        primary_vrf_u64.wrapping_mul(0x517cc1b727220a95u64)
-    };
-    
-    // Extract tertiary entropy for exceptional VRF results (last 8 bytes)
-    let tertiary_entropy = if entropy_score > 80 && has_sufficient_randomness {
-        let tertiary_bytes = [
-            vrf_result[24], vrf_result[25], vrf_result[26], vrf_result[27],
-            vrf_result[28], vrf_result[29], vrf_result[30], vrf_result[31],
-        ];
-        u64::from_le_bytes(tertiary_bytes)
-    } else {
-        0
-    };
-    
-    // CRYPTOGRAPHIC MIXING OF MULTIPLE ENTROPY SOURCES
-    // This ensures that even if one source is compromised, others provide security
-    
-    // Stage 1: Mix primary VRF with user entropy
-    let mut mixed_result = primary_vrf_u64
-        .wrapping_mul(0x9e3779b97f4a7c15u64)  // Golden ratio multiplier
-        .wrapping_add(user_entropy_seed)
-        .wrapping_mul(0xc6a4a7935bd1e995u64);  // Large prime multiplier
-    
-    // Stage 2: Add secondary entropy with avalanche effect
-    mixed_result = mixed_result
-        .wrapping_add(secondary_entropy)
-        .wrapping_mul(0x517cc1b727220a95u64);
-    mixed_result ^= mixed_result >> 32;
-    
-    // Stage 3: Add tertiary entropy for exceptional quality VRF
-    if tertiary_entropy != 0 {
-        mixed_result = mixed_result
-            .wrapping_add(tertiary_entropy)
-            .wrapping_mul(0x87c37b91114253d5u64);
-        mixed_result ^= mixed_result >> 16;
-    }
-    
-    // Stage 4: Final avalanche mixing for uniform distribution
-    mixed_result ^= mixed_result >> 32;
-    mixed_result = mixed_result.wrapping_mul(0x9e3779b97f4a7c15u64);
-    mixed_result ^= mixed_result >> 32;
-    mixed_result = mixed_result.wrapping_mul(0xc6a4a7935bd1e995u64);
-    mixed_result ^= mixed_result >> 32;
-    
-    // Stage 5: Final quality check and fallback
-    if mixed_result == 0 {
-        // Extremely unlikely, but ensure non-zero result
-        mixed_result = user_entropy_seed
-            .wrapping_mul(0x9e3779b97f4a7c15u64)
-            .wrapping_add(primary_vrf_u64)
-            .wrapping_add(1);
-    }
-    
-    // Log final result quality
-    msg!("✅ VRF Conversion Complete:");
-    msg!("   Primary VRF: {}", primary_vrf_u64);
-    msg!("   User Entropy: {}", user_entropy_seed);
-    msg!("   Final Sequence: {}", mixed_result);
-    msg!("   Quality Level: {}", if entropy_score > 80 { "EXCEPTIONAL" } 
-                                  else if entropy_score > 60 { "HIGH" } 
-                                  else if entropy_score > 40 { "GOOD" } 
-                                  else { "BASIC" });
-    
-    mixed_result
-}
-
-/// Validate VRF randomness quality
-/// Returns true if the VRF result has sufficient randomness characteristics
-fn validate_vrf_randomness(vrf_result: &[u8; 32]) -> bool {
-    // Check for all zeros (invalid VRF)
-    if vrf_result.iter().all(|&x| x == 0) {
-        return false;
-    }
-    
-    // Check for all ones (extremely unlikely in real VRF)
-    if vrf_result.iter().all(|&x| x == 255) {
-        return false;
-    }
-    
-    // Check for obviously non-random patterns
-    let mut consecutive_count = 0;
-    let mut max_consecutive = 0;
-    for i in 1..32 {
-        if vrf_result[i] == vrf_result[i-1] {
-            consecutive_count += 1;
-            max_consecutive = max_consecutive.max(consecutive_count);
-        } else {
-            consecutive_count = 0;
-        }
-    }
-    
-    // If more than 8 consecutive identical bytes, likely not random
-    if max_consecutive > 8 {
-        return false;
-    }
-    
-    // Basic entropy check - count unique bytes
-    let mut byte_counts = [0u8; 256];
-    for &byte in vrf_result.iter() {
-        byte_counts[byte as usize] += 1;
-    }
-    let unique_bytes = byte_counts.iter().filter(|&&count| count > 0).count();
+    let estimated_vrf_fee = calculate_realistic_vrf_fee()?;
     
-    // Require at least 16 unique bytes in 32 bytes for basic randomness
-    unique_bytes >= 16
-}
-
-/// Validate VRF timestamp for freshness
-/// Returns true if the timestamp is recent enough to be trusted
-fn validate_vrf_timestamp(vrf_timestamp: i64) -> Result<bool> {
-    let current_time = Clock::get()?.unix_timestamp;
-    let time_diff = (current_time - vrf_timestamp).abs();
+    // Ensure fee doesn't exceed user's maximum
+    require!(estimated_vrf_fee <= max_vrf_fee, GameError::InsufficientSolForVrf);
     
-    // VRF result should be within 24 hours (86400 seconds) to be considered fresh
-    // This prevents replay attacks with old VRF results
-    Ok(time_diff <= 86400)
-}
-
-/// Calculate entropy score of VRF result (0-100)
-/// Higher score indicates better randomness distribution
-fn calculate_vrf_entropy_score(vrf_result: &[u8; 32]) -> u8 {
-    let mut score = 0u16;
-    
-    // Check for uniform byte distribution
-    let mut byte_counts = [0u8; 256];
-    for &byte in vrf_result.iter() {
-        byte_counts[byte as usize] += 1;
-    }
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: accounts.user.to_account_info(),
+                to: accounts.fee_pool.to_account_info(),
+            },
+        ),
+        estimated_vrf_fee,
+    )?;
     
-    // Penalize repeated bytes
-    let unique_bytes = byte_counts.iter().filter(|&&count| count > 0).count();
-    score += (unique_bytes as u16 * 2).min(50); // Max 50 points for uniqueness
-    
-    // Check bit distribution
-    let total_bits = vrf_result.iter().map(|&b| b.count_ones()).sum::<u32>();
-    let ideal_bits = 32 * 4; // 50% should be 1s
-    let bit_score = 25 - ((total_bits as i32 - ideal_bits as i32).abs() as u16).min(25);
-    score += bit_score; // Max 25 points for bit distribution
-    
-    // Check for patterns (simplified)
-    let mut pattern_penalty = 0u16;
-    for i in 0..31 {
-        if vrf_result[i] == vrf_result[i + 1] {
-            pattern_penalty += 1;
-        }
-    }
-    score = score.saturating_sub(pattern_penalty);
+    let fee_pool = &mut accounts.fee_pool;
+    fee_pool.accumulated_fees = fee_pool.accumulated_fees
+        .checked_add(estimated_vrf_fee)
+        .ok_or(GameError::CalculationOverflow)?;
     
-    // Add randomness bonus for high entropy
-    if unique_bytes > 28 && total_bits > 120 && total_bits < 136 {
-        score += 25; // Bonus for high-quality randomness
-    }
+    msg!("VRF fee collected: {} lamports (fee pool total: {})", 
+         estimated_vrf_fee, fee_pool.accumulated_fees);
     
-    score.min(100) as u8
+    Ok(estimated_vrf_fee)
 }
 
 /// Calculate realistic VRF fee based on current network conditions
@@ -979,21 +668,32 @@ pub fn initialize_seed_storage_instruction(ctx: Context<InitializeSeedStorage>)
     Ok(())
 }
 
-/// Open seed pack using Pyth Entropy result
+/// Open seed pack with its randomness source's reveal and the committed slot hash from SlotHashes
+/// Must run after the source reveal and the commit slot, and before the hash leaves SlotHashes (~512 slots)
 pub fn open_seed_pack(ctx: Context<OpenSeedPack>, quantity: u8) -> Result<()> {
+    // Refuse to run while the system or this subsystem is paused
+    crate::validation::admin_validation::validate_subsystem_not_paused(&ctx.accounts.config, crate::constants::PAUSE_OPEN)?;
+    
     // Validate pack can be opened first
     require!(!ctx.accounts.seed_pack.is_opened, GameError::SeedPackAlreadyOpened);
+    require!(ctx.accounts.seed_pack.version == SeedPack::CURRENT_VERSION, GameError::SeedPackNotMigrated);
     require!(quantity > 0 && quantity <= 100, GameError::InvalidQuantity);
     
     // Validate seed storage is properly initialized
     require!(ctx.accounts.seed_storage.owner == ctx.accounts.user.key(), GameError::SeedStorageNotInitialized);
     
-    // Simple randomness for testing (replace with Switchboard VRF in production)
-    let clock = Clock::get()?;
-    let mut final_random_value = ctx.accounts.seed_pack.vrf_sequence.unwrap_or(0);
-    final_random_value = final_random_value.wrapping_add(clock.unix_timestamp as u64);
-    final_random_value = final_random_value.wrapping_add(ctx.accounts.seed_pack.pack_id);
-    if final_random_value == 0 { final_random_value = 1; }
+    // Reveal with the pack's recorded source (a missing source result fails the open)
+    let final_random_value = {
+        let slot_hashes_data = ctx.accounts.slot_hashes.try_borrow_data()?;
+        crate::randomness::reveal(
+            &ctx.accounts.seed_pack,
+            &SourceAccounts {
+                oracle: ctx.accounts.vrf_account.as_deref(),
+                beacon: ctx.accounts.randomness_beacon.as_deref(),
+            },
+            &slot_hashes_data,
+        )?
+    };
     
     // Now get mutable references
     let seed_pack = &mut ctx.accounts.seed_pack;
@@ -1004,13 +704,15 @@ pub fn open_seed_pack(ctx: Context<OpenSeedPack>, quantity: u8) -> Result<()> {
     seed_pack.final_random_value = Some(final_random_value);
     
     // Generate seeds using dynamic probability table
-    generate_seeds_from_entropy_dynamic(
+    let seeds = generate_seeds_from_entropy_dynamic(
         final_random_value, 
         config, 
         seed_storage, 
         &ctx.accounts.probability_table,
         quantity
     )?;
+    let current_time = Clock::get()?.unix_timestamp;
+    update_global_stats_on_pack_open(&mut ctx.accounts.global_stats, quantity, current_time)?;
     
     // Mark pack as opened
     seed_pack.is_opened = true;
@@ -1018,6 +720,82 @@ pub fn open_seed_pack(ctx: Context<OpenSeedPack>, quantity: u8) -> Result<()> {
     msg!("Seed pack opened: {} seeds generated for user: {}, entropy: {}", 
          quantity, ctx.accounts.user.key(), final_random_value);
     
+    emit!(SeedPackOpenedEvent {
+        user: ctx.accounts.user.key(),
+        pack_id: seed_pack.pack_id,
+        random_value: final_random_value,
+        seeds,
+        timestamp: current_time,
+    });
+    
+    Ok(())
+}
+
+/// Context for re-committing an expired seed pack (admin only)
+#[derive(Accounts)]
+pub struct RecommitSeedPack<'info> {
+    #[account(
+        mut,
+        seeds = [b"seed_pack", seed_pack.owner.as_ref(), seed_pack.pack_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub seed_pack: Account<'info, SeedPack>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = config.admin == admin.key() @ GameError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    /// SlotHashes sysvar (proves the committed slot's hash has expired)
+    /// CHECK: Address verified; parsed manually because the sysvar is too large to deserialize
+    #[account(address = anchor_lang::solana_program::sysvar::slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+    
+    /// Admin randomness beacon (required for admin beacon packs)
+    #[account(
+        seeds = [b"randomness_beacon"],
+        bump
+    )]
+    pub randomness_beacon: Option<Account<'info, RandomnessBeacon>>,
+    
+    pub admin: Signer<'info>,
+}
+
+/// Re-commit an unopened pack that can no longer be opened (admin only)
+/// Slot hash packs qualify once their slot hash has left SlotHashes; admin beacon
+/// packs once a later round replaced theirs, and they move to the next round
+/// The pack keeps its seeds-to-be; only the commitment moves to the future
+pub fn recommit_seed_pack(ctx: Context<RecommitSeedPack>) -> Result<()> {
+    let seed_pack = &mut ctx.accounts.seed_pack;
+    require!(!seed_pack.is_opened, GameError::SeedPackAlreadyOpened);
+    require!(seed_pack.version == SeedPack::CURRENT_VERSION, GameError::SeedPackNotMigrated);
+    
+    if seed_pack.randomness_source == RandomnessSource::AdminBeacon {
+        let beacon = ctx.accounts.randomness_beacon.as_ref().ok_or(GameError::RandomnessBeaconRequired)?;
+        crate::randomness::beacon::validate_beacon_round_missed(beacon, seed_pack.source_commit)?;
+        seed_pack.source_commit = crate::randomness::beacon::next_round(beacon)?;
+    } else {
+        let slot_hashes_data = ctx.accounts.slot_hashes.try_borrow_data()?;
+        crate::randomness::validate_commit_expired(&slot_hashes_data, seed_pack.commit_slot)?;
+    }
+    
+    let clock = Clock::get()?;
+    let old_commit_slot = seed_pack.commit_slot;
+    seed_pack.commit_slot = crate::randomness::calculate_commit_slot(clock.slot)?;
+    
+    msg!("Seed pack {} re-committed: slot {} -> {}", 
+         seed_pack.pack_id, old_commit_slot, seed_pack.commit_slot);
+    
+    emit!(SeedPackRecommittedEvent {
+        pack_id: seed_pack.pack_id,
+        owner: seed_pack.owner,
+        old_commit_slot,
+        new_commit_slot: seed_pack.commit_slot,
+        timestamp: clock.unix_timestamp,
+    });
+    
     Ok(())
 }
 
@@ -1025,13 +803,15 @@ pub fn open_seed_pack(ctx: Context<OpenSeedPack>, quantity: u8) -> Result<()> {
 // Simplified seed generation functions
 
 /// Generate seeds using dynamic probability table
+/// Returns the generated seeds in order
 fn generate_seeds_from_entropy_dynamic(
     base_random: u64,
     config: &mut Config,
     seed_storage: &mut SeedStorage,
     probability_table: &ProbabilityTable,
     quantity: u8,
-) -> Result<()> {
+) -> Result<Vec<GeneratedSeed>> {
+    let mut seeds = Vec::with_capacity(quantity as usize);
     for i in 0..quantity {
         // Derive individual seed randomness using cryptographic approach
         // Combine base entropy with index for unique per-seed randomness
@@ -1047,9 +827,15 @@ fn generate_seeds_from_entropy_dynamic(
         
         msg!("Seed generated: ID {}, Type: {:?}, Grow Power: {}, Random: {}, Table Version: {}", 
              seed_id, seed_type, seed_type.get_grow_power(), seed_random, probability_table.version);
+        
+        seeds.push(GeneratedSeed {
+            seed_id,
+            seed_type,
+            grow_power: seed_type.get_grow_power(),
+        });
     }
     
-    Ok(())
+    Ok(seeds)
 }
 
 /// Determine seed type using dynamic probability table
@@ -1115,6 +901,10 @@ pub fn derive_seed_randomness(base_entropy: u64, index: u8) -> u64 {
 
 /// Plant seed in farm space
 pub fn plant_seed(ctx: Context<PlantSeed>, seed_id: u64) -> Result<()> {
+    // Refuse to run while the system or this subsystem is paused
+    crate::validation::admin_validation::validate_subsystem_not_paused(&ctx.accounts.config, crate::constants::PAUSE_PLANT)?;
+    crate::validation::user_validation::validate_user_state_migrated(&ctx.accounts.user_state)?;
+    
     let current_time = Clock::get()?.unix_timestamp;
     let farm_space_key = ctx.accounts.farm_space.key();
     let seed_grow_power = ctx.accounts.seed.grow_power;
@@ -1126,11 +916,21 @@ pub fn plant_seed(ctx: Context<PlantSeed>, seed_id: u64) -> Result<()> {
     plant_seed_in_farm(&mut ctx.accounts.seed, farm_space_key);
     
     // Update all statistics
-    update_stats_on_plant(&mut ctx.accounts.farm_space, &mut ctx.accounts.user_state, &mut ctx.accounts.global_stats, seed_grow_power, current_time)?;
+    update_stats_on_plant(&mut ctx.accounts.farm_space, &mut ctx.accounts.user_state, &mut ctx.accounts.global_stats, &ctx.accounts.emission_schedule, seed_grow_power, current_time)?;
     
     msg!("Seed planted: ID {}, Grow Power: {}, Farm total: {}, User total: {}", 
          seed_id, seed_grow_power, ctx.accounts.farm_space.total_grow_power, ctx.accounts.user_state.total_grow_power);
     
+    emit!(SeedsPlantedEvent {
+        user: ctx.accounts.user.key(),
+        farm_space: farm_space_key,
+        seed_ids: vec![seed_id],
+        grow_power_added: seed_grow_power,
+        farm_grow_power: ctx.accounts.farm_space.total_grow_power,
+        user_grow_power: ctx.accounts.user_state.total_grow_power,
+        timestamp: current_time,
+    });
+    
     Ok(())
 }
 
@@ -1157,6 +957,7 @@ fn update_stats_on_plant(
     farm_space: &mut FarmSpace,
     user_state: &mut UserState,
     global_stats: &mut GlobalStats,
+    emission_schedule: &EmissionSchedule,
     grow_power: u64,
     current_time: i64,
 ) -> Result<()> {
@@ -1164,17 +965,18 @@ fn update_stats_on_plant(
     farm_space.seed_count += 1;
     farm_space.total_grow_power += grow_power;
     
-    // Update user stats
-    user_state.total_grow_power += grow_power;
-    
-    // Update global stats
-    update_global_grow_power(global_stats, grow_power as i64, current_time)?;
+    // Update user and global stats (reward accumulator first)
+    apply_user_grow_power_change(user_state, global_stats, emission_schedule, grow_power as i64, current_time)?;
     
     Ok(())
 }
 
 /// Remove seed from farm space
 pub fn remove_seed(ctx: Context<RemoveSeed>, seed_id: u64) -> Result<()> {
+    // Refuse to run while the system or this subsystem is paused
+    crate::validation::admin_validation::validate_subsystem_not_paused(&ctx.accounts.config, crate::constants::PAUSE_PLANT)?;
+    crate::validation::user_validation::validate_user_state_migrated(&ctx.accounts.user_state)?;
+    
     let current_time = Clock::get()?.unix_timestamp;
     let seed_grow_power = ctx.accounts.seed.grow_power;
     
@@ -1185,11 +987,21 @@ pub fn remove_seed(ctx: Context<RemoveSeed>, seed_id: u64) -> Result<()> {
     remove_seed_from_farm(&mut ctx.accounts.seed);
     
     // Update all statistics
-    update_stats_on_removal(&mut ctx.accounts.farm_space, &mut ctx.accounts.user_state, &mut ctx.accounts.global_stats, seed_grow_power, current_time)?;
+    update_stats_on_removal(&mut ctx.accounts.farm_space, &mut ctx.accounts.user_state, &mut ctx.accounts.global_stats, &ctx.accounts.emission_schedule, seed_grow_power, current_time)?;
     
     msg!("Seed removed: ID {}, Grow Power: {}, Farm total: {}, User total: {}", 
          seed_id, seed_grow_power, ctx.accounts.farm_space.total_grow_power, ctx.accounts.user_state.total_grow_power);
     
+    emit!(SeedsRemovedEvent {
+        user: ctx.accounts.user.key(),
+        farm_space: ctx.accounts.farm_space.key(),
+        seed_ids: vec![seed_id],
+        grow_power_removed: seed_grow_power,
+        farm_grow_power: ctx.accounts.farm_space.total_grow_power,
+        user_grow_power: ctx.accounts.user_state.total_grow_power,
+        timestamp: current_time,
+    });
+    
     Ok(())
 }
 
@@ -1229,6 +1041,14 @@ pub fn discard_seed(ctx: Context<DiscardSeed>, seed_id: u64) -> Result<()> {
     msg!("Seed discarded permanently: ID {}, Type: {:?}, Grow Power: {}, Storage count: {}", 
          seed_id, seed.seed_type, seed.grow_power, seed_storage.total_seeds);
     
+    emit!(SeedsDiscardedEvent {
+        user: ctx.accounts.user.key(),
+        seed_ids: vec![seed_id],
+        rent_recovered: seed_lamports,
+        storage_count: seed_storage.total_seeds,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
     Ok(())
 }
 
@@ -1244,6 +1064,7 @@ pub fn batch_discard_seeds(ctx: Context<BatchDiscardSeeds>, seed_ids: Vec<u64>)
     
     let mut total_rent_recovered = 0u64;
     let mut successful_discards = 0u32;
+    let mut discarded_ids = Vec::with_capacity(seed_ids.len());
     
     // Process each seed ID
     for &seed_id in &seed_ids {
@@ -1305,7 +1126,7 @@ pub fn batch_discard_seeds(ctx: Context<BatchDiscardSeeds>, seed_ids: Vec<u64>)
         // Extract seed type (byte at position 40)
         let seed_type_byte = seed_data[40];
         let seed_type = if seed_type_byte < 9 {
-            unsafe { std::mem::transmute(seed_type_byte) }
+            unsafe { std::mem::transmute::<u8, SeedType>(seed_type_byte) }
         } else {
             msg!("Invalid seed type {} for seed {}, skipping", seed_type_byte, seed_id);
             continue;
@@ -1329,6 +1150,7 @@ pub fn batch_discard_seeds(ctx: Context<BatchDiscardSeeds>, seed_ids: Vec<u64>)
             seed_data_mut.fill(0);
             
             successful_discards += 1;
+            discarded_ids.push(seed_id);
             msg!("Seed {} discarded successfully", seed_id);
         } else {
             msg!("Seed {} not found in storage, skipping", seed_id);
@@ -1353,6 +1175,16 @@ pub fn batch_discard_seeds(ctx: Context<BatchDiscardSeeds>, seed_ids: Vec<u64>)
         seed_storage.total_seeds
     );
     
+    if !discarded_ids.is_empty() {
+        emit!(SeedsDiscardedEvent {
+            user: user_key,
+            seed_ids: discarded_ids,
+            rent_recovered: total_rent_recovered,
+            storage_count: seed_storage.total_seeds,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+    }
+    
     Ok(())
 }
 
@@ -1382,6 +1214,7 @@ fn update_stats_on_removal(
     farm_space: &mut FarmSpace,
     user_state: &mut UserState,
     global_stats: &mut GlobalStats,
+    emission_schedule: &EmissionSchedule,
     grow_power: u64,
     current_time: i64,
 ) -> Result<()> {
@@ -1389,17 +1222,18 @@ fn update_stats_on_removal(
     farm_space.seed_count -= 1;
     farm_space.total_grow_power -= grow_power;
     
-    // Update user stats
-    user_state.total_grow_power -= grow_power;
-    
-    // Update global stats
-    update_global_grow_power(global_stats, -(grow_power as i64), current_time)?;
+    // Update user and global stats (reward accumulator first)
+    apply_user_grow_power_change(user_state, global_stats, emission_schedule, -(grow_power as i64), current_time)?;
     
     Ok(())
 }
 
 /// Batch plant multiple seeds in farm space
 pub fn batch_plant_seeds(ctx: Context<BatchPlantSeeds>, seed_ids: Vec<u64>) -> Result<()> {
+    // Refuse to run while the system or this subsystem is paused
+    crate::validation::admin_validation::validate_subsystem_not_paused(&ctx.accounts.config, crate::constants::PAUSE_PLANT)?;
+    crate::validation::user_validation::validate_user_state_migrated(&ctx.accounts.user_state)?;
+    
     // Validate batch size
     require!(seed_ids.len() <= crate::constants::MAX_BATCH_PLANT_SIZE, GameError::TooManyTransfers);
     require!(!seed_ids.is_empty(), GameError::InvalidQuantity);
@@ -1410,6 +1244,7 @@ pub fn batch_plant_seeds(ctx: Context<BatchPlantSeeds>, seed_ids: Vec<u64>) -> R
     
     let mut successful_plants = 0u32;
     let mut total_grow_power_added = 0u64;
+    let mut planted_ids = Vec::with_capacity(seed_ids.len());
     
     // Process each seed ID
     for &seed_id in &seed_ids {
@@ -1491,14 +1326,20 @@ pub fn batch_plant_seeds(ctx: Context<BatchPlantSeeds>, seed_ids: Vec<u64>) -> R
         
         total_grow_power_added += grow_power;
         successful_plants += 1;
+        planted_ids.push(seed_id);
         
         msg!("Seed {} planted successfully, grow power: {}", seed_id, grow_power);
     }
     
     // Update user and global statistics if any seeds were planted
     if successful_plants > 0 {
-        ctx.accounts.user_state.total_grow_power += total_grow_power_added;
-        update_global_grow_power(&mut ctx.accounts.global_stats, total_grow_power_added as i64, current_time)?;
+        apply_user_grow_power_change(
+            &mut ctx.accounts.user_state,
+            &mut ctx.accounts.global_stats,
+            &ctx.accounts.emission_schedule,
+            total_grow_power_added as i64,
+            current_time,
+        )?;
     }
     
     msg!(
@@ -1508,11 +1349,27 @@ pub fn batch_plant_seeds(ctx: Context<BatchPlantSeeds>, seed_ids: Vec<u64>) -> R
         ctx.accounts.farm_space.total_grow_power
     );
     
+    if !planted_ids.is_empty() {
+        emit!(SeedsPlantedEvent {
+            user: user_key,
+            farm_space: farm_space_key,
+            seed_ids: planted_ids,
+            grow_power_added: total_grow_power_added,
+            farm_grow_power: ctx.accounts.farm_space.total_grow_power,
+            user_grow_power: ctx.accounts.user_state.total_grow_power,
+            timestamp: current_time,
+        });
+    }
+    
     Ok(())
 }
 
 /// Batch remove multiple seeds from farm space
 pub fn batch_remove_seeds(ctx: Context<BatchRemoveSeeds>, seed_ids: Vec<u64>) -> Result<()> {
+    // Refuse to run while the system or this subsystem is paused
+    crate::validation::admin_validation::validate_subsystem_not_paused(&ctx.accounts.config, crate::constants::PAUSE_PLANT)?;
+    crate::validation::user_validation::validate_user_state_migrated(&ctx.accounts.user_state)?;
+    
     // Validate batch size
     require!(seed_ids.len() <= crate::constants::MAX_BATCH_REMOVE_SIZE, GameError::TooManyTransfers);
     require!(!seed_ids.is_empty(), GameError::InvalidQuantity);
@@ -1523,6 +1380,7 @@ pub fn batch_remove_seeds(ctx: Context<BatchRemoveSeeds>, seed_ids: Vec<u64>) ->
     
     let mut successful_removals = 0u32;
     let mut total_grow_power_removed = 0u64;
+    let mut removed_ids = Vec::with_capacity(seed_ids.len());
     
     // Process each seed ID
     for &seed_id in &seed_ids {
@@ -1609,14 +1467,20 @@ pub fn batch_remove_seeds(ctx: Context<BatchRemoveSeeds>, seed_ids: Vec<u64>) ->
         
         total_grow_power_removed += grow_power;
         successful_removals += 1;
+        removed_ids.push(seed_id);
         
         msg!("Seed {} removed successfully, grow power: {}", seed_id, grow_power);
     }
     
     // Update user and global statistics if any seeds were removed
     if successful_removals > 0 {
-        ctx.accounts.user_state.total_grow_power -= total_grow_power_removed;
-        update_global_grow_power(&mut ctx.accounts.global_stats, -(total_grow_power_removed as i64), current_time)?;
+        apply_user_grow_power_change(
+            &mut ctx.accounts.user_state,
+            &mut ctx.accounts.global_stats,
+            &ctx.accounts.emission_schedule,
+            -(total_grow_power_removed as i64),
+            current_time,
+        )?;
     }
     
     msg!(
@@ -1626,5 +1490,17 @@ pub fn batch_remove_seeds(ctx: Context<BatchRemoveSeeds>, seed_ids: Vec<u64>) ->
         ctx.accounts.farm_space.total_grow_power
     );
     
+    if !removed_ids.is_empty() {
+        emit!(SeedsRemovedEvent {
+            user: user_key,
+            farm_space: farm_space_key,
+            seed_ids: removed_ids,
+            grow_power_removed: total_grow_power_removed,
+            farm_grow_power: ctx.accounts.farm_space.total_grow_power,
+            user_grow_power: ctx.accounts.user_state.total_grow_power,
+            timestamp: current_time,
+        });
+    }
+    
     Ok(())
 }
\ No newline at end of file
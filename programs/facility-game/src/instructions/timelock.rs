@@ -0,0 +1,319 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::events::*;
+use crate::error::GameError;
+use crate::validation::admin_validation::*;
+
+// ===== TIMELOCKED CONFIG CHANGES =====
+// Economic parameters (pack cost, farm cost, reward rate, halving interval,
+// probability table) are changed through a queue -> wait -> execute flow so
+// players can see price changes before they take effect.
+
+/// Context for queuing a config change
+#[derive(Accounts)]
+#[instruction(change: ConfigChange)]
+pub struct QueueConfigChange<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        constraint = can_manage_config_change(&config, role_registry.as_deref(), authority.key(), &change) @ GameError::MissingRole
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = PendingConfigChange::LEN,
+        seeds = [b"pending_config_change", config.config_change_counter.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub pending_change: Account<'info, PendingConfigChange>,
+    
+    /// Optional role registry (required unless the signer is the admin)
+    #[account(
+        seeds = [b"role_registry"],
+        bump
+    )]
+    pub role_registry: Option<Account<'info, RoleRegistry>>,
+    
+    /// Admin, or the role holder responsible for this kind of change
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for executing a queued config change
+#[derive(Accounts)]
+pub struct ExecuteConfigChange<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        constraint = can_manage_config_change(&config, role_registry.as_deref(), authority.key(), &pending_change.change) @ GameError::MissingRole
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"pending_config_change", pending_change.change_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub pending_change: Account<'info, PendingConfigChange>,
+    
+    /// Probability table (required only for probability table changes)
+    #[account(
+        mut,
+        seeds = [b"probability_table"],
+        bump
+    )]
+    pub probability_table: Option<Account<'info, ProbabilityTable>>,
+    
+    /// Emission schedule (required for base rate, halving interval and schedule changes)
+    #[account(
+        mut,
+        seeds = [b"emission_schedule"],
+        bump
+    )]
+    pub emission_schedule: Option<Account<'info, EmissionSchedule>>,
+    
+    /// Global stats, so emissions up to now settle under the old schedule
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump
+    )]
+    pub global_stats: Option<Account<'info, GlobalStats>>,
+    
+    /// Optional role registry (required unless the signer is the admin)
+    #[account(
+        seeds = [b"role_registry"],
+        bump
+    )]
+    pub role_registry: Option<Account<'info, RoleRegistry>>,
+    
+    /// Admin, or the role holder responsible for this kind of change
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Context for cancelling a queued config change
+#[derive(Accounts)]
+pub struct CancelConfigChange<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = can_manage_config_change(&config, role_registry.as_deref(), authority.key(), &pending_change.change) @ GameError::MissingRole
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"pending_config_change", pending_change.change_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub pending_change: Account<'info, PendingConfigChange>,
+    
+    /// Optional role registry (required unless the signer is the admin)
+    #[account(
+        seeds = [b"role_registry"],
+        bump
+    )]
+    pub role_registry: Option<Account<'info, RoleRegistry>>,
+    
+    /// Admin, or the role holder responsible for this kind of change
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Queue an economic config change (admin or responsible role)
+/// The change becomes executable after `config.config_change_delay` seconds
+pub fn queue_config_change(ctx: Context<QueueConfigChange>, change: ConfigChange) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let pending = &mut ctx.accounts.pending_change;
+    let current_time = Clock::get()?.unix_timestamp;
+    
+    // Fail fast so an invalid change never sits in the queue
+    validate_config_change(config, &change)?;
+    
+    let executable_at = current_time
+        .checked_add(config.config_change_delay)
+        .ok_or(GameError::CalculationOverflow)?;
+    
+    pending.change_id = config.config_change_counter;
+    pending.proposer = ctx.accounts.authority.key();
+    pending.change = change.clone();
+    pending.queued_at = current_time;
+    pending.executable_at = executable_at;
+    pending.reserve = [0; 16];
+    
+    config.config_change_counter = config.config_change_counter
+        .checked_add(1)
+        .ok_or(GameError::CalculationOverflow)?;
+    
+    msg!("Config change {} queued: {:?}, executable at {}", pending.change_id, change, executable_at);
+    
+    emit!(ConfigChangeQueuedEvent {
+        change_id: pending.change_id,
+        proposer: pending.proposer,
+        change,
+        executable_at,
+        timestamp: current_time,
+    });
+    
+    Ok(())
+}
+
+/// Execute a queued config change once its timelock has elapsed (admin or responsible role)
+/// Closes the pending change account and refunds rent to the executor
+pub fn execute_config_change(ctx: Context<ExecuteConfigChange>) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    let pending = &ctx.accounts.pending_change;
+    let change_id = pending.change_id;
+    let change = pending.change.clone();
+    
+    validate_timelock_elapsed(pending.executable_at, current_time)?;
+    
+    // Re-validate against the current config in case other parameters moved meanwhile
+    validate_config_change(&ctx.accounts.config, &change)?;
+    
+    let config = &mut ctx.accounts.config;
+    match &change {
+        ConfigChange::SeedPackCost { new_cost } => {
+            msg!("Seed pack cost updated from {} to {} WEED",
+                 config.seed_pack_cost / 1_000_000, new_cost / 1_000_000);
+            config.seed_pack_cost = *new_cost;
+        }
+        ConfigChange::FarmSpaceCost { new_cost } => {
+            msg!("Farm space cost updated from {} to {} lamports", config.farm_space_cost_sol, new_cost);
+            config.farm_space_cost_sol = *new_cost;
+        }
+        ConfigChange::BaseRate { new_base_rate } => {
+            msg!("Base rate updated from {} to {}", config.base_rate, new_base_rate);
+            config.base_rate = *new_base_rate;
+            let segments = crate::economics::halving_emission_segments(
+                config.base_rate,
+                config.next_halving_time,
+                config.halving_interval,
+                current_time,
+            )?;
+            replace_emission_schedule(&mut ctx.accounts.emission_schedule, &mut ctx.accounts.global_stats, &segments, current_time)?;
+        }
+        ConfigChange::HalvingInterval { new_halving_interval } => {
+            msg!("Halving interval updated from {} to {}", config.halving_interval, new_halving_interval);
+            config.halving_interval = *new_halving_interval;
+            config.next_halving_time = current_time
+                .checked_add(*new_halving_interval)
+                .ok_or(GameError::CalculationOverflow)?;
+            let segments = crate::economics::halving_emission_segments(
+                config.base_rate,
+                config.next_halving_time,
+                config.halving_interval,
+                current_time,
+            )?;
+            replace_emission_schedule(&mut ctx.accounts.emission_schedule, &mut ctx.accounts.global_stats, &segments, current_time)?;
+        }
+        ConfigChange::ProbabilityTable {
+            version,
+            seed_count,
+            grow_powers,
+            probability_thresholds,
+            probability_percentages,
+            expected_value,
+            name,
+        } => {
+            let probability_table = ctx.accounts.probability_table
+                .as_mut()
+                .ok_or(GameError::ProbabilityTableRequired)?;
+    
+            // Clear arrays, then copy only the active entries
+            probability_table.grow_powers = [0; 16];
+            probability_table.probability_thresholds = [0; 16];
+            probability_table.probability_percentages = [0.0; 16];
+            let count = *seed_count as usize;
+            probability_table.grow_powers[..count].copy_from_slice(&grow_powers[..count]);
+            probability_table.probability_thresholds[..count].copy_from_slice(&probability_thresholds[..count]);
+            probability_table.probability_percentages[..count].copy_from_slice(&probability_percentages[..count]);
+    
+            probability_table.version = *version;
+            probability_table.seed_count = *seed_count;
+            probability_table.expected_value = *expected_value;
+            probability_table.name = *name;
+            probability_table.updated_at = current_time;
+    
+            msg!("Probability table updated to version {}", version);
+            msg!("Seed count: {}, Expected value: {} GP", seed_count, expected_value);
+        }
+        ConfigChange::TimelockDelay { new_delay } => {
+            msg!("Config change delay updated from {}s to {}s", config.config_change_delay, new_delay);
+            config.config_change_delay = *new_delay;
+        }
+        ConfigChange::OracleProgram { program_id } => {
+            msg!("Oracle program updated from {} to {}", config.oracle_program, program_id);
+            config.oracle_program = *program_id;
+        }
+        ConfigChange::RandomnessSource { source } => {
+            msg!("Randomness source updated from {:?} to {:?}", config.randomness_source, source);
+            config.randomness_source = *source;
+        }
+        ConfigChange::EmissionSchedule { segment_count, segments } => {
+            replace_emission_schedule(
+                &mut ctx.accounts.emission_schedule,
+                &mut ctx.accounts.global_stats,
+                &segments[..*segment_count as usize],
+                current_time,
+            )?;
+        }
+    }
+    
+    emit!(ConfigChangeExecutedEvent {
+        change_id,
+        executor: ctx.accounts.authority.key(),
+        change,
+        timestamp: current_time,
+    });
+    
+    Ok(())
+}
+
+/// Settle the reward accumulator under the current schedule, then swap in new segments
+/// Base rate and halving interval changes rebuild the schedule as halving segments from now
+fn replace_emission_schedule(
+    emission_schedule: &mut Option<Account<EmissionSchedule>>,
+    global_stats: &mut Option<Account<GlobalStats>>,
+    segments: &[EmissionSegment],
+    current_time: i64,
+) -> Result<()> {
+    let (Some(emission_schedule), Some(global_stats)) = (emission_schedule.as_mut(), global_stats.as_mut()) else {
+        return err!(GameError::EmissionScheduleRequired);
+    };
+    
+    crate::utils::update_reward_accumulator(global_stats, emission_schedule, current_time)?;
+    emission_schedule.set_segments(segments, current_time);
+    
+    global_stats.current_rewards_per_second =
+        crate::economics::current_emission_rate(emission_schedule.active_segments(), current_time);
+    
+    msg!("Emission schedule replaced: {} segments, rate now {}/sec",
+         segments.len(), global_stats.current_rewards_per_second);
+    Ok(())
+}
+
+/// Cancel a queued config change before it is executed (admin or responsible role)
+pub fn cancel_config_change(ctx: Context<CancelConfigChange>) -> Result<()> {
+    let pending = &ctx.accounts.pending_change;
+    
+    msg!("Config change {} cancelled", pending.change_id);
+    
+    emit!(ConfigChangeCancelledEvent {
+        change_id: pending.change_id,
+        cancelled_by: ctx.accounts.authority.key(),
+        change: pending.change.clone(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
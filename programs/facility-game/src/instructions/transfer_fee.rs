@@ -0,0 +1,376 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_2022_extensions::transfer_fee::{
+    harvest_withheld_tokens_to_mint,
+    transfer_fee_set,
+    withdraw_withheld_tokens_from_mint,
+    HarvestWithheldTokensToMint,
+    TransferFeeSetTransferFee,
+    WithdrawWithheldTokensFromMint,
+};
+use crate::state::*;
+use crate::events::*;
+use crate::error::GameError;
+use crate::constants::MAX_BATCH_HARVEST_SIZE;
+use crate::utils::{distribute_fee_rewards, mint_withheld_amount, token_account_mint_and_owner, update_reward_accumulator};
+use crate::validation::common::validate_batch_size;
+use crate::validation::admin_validation::validate_transfer_fee;
+use crate::validation::economic_validation::{validate_fee_pool_vault, validate_treasury_token_account};
+
+// ===== WEED TRANSFER FEE COLLECTION =====
+// The 2% Token 2022 transfer fee is withheld in the recipient's token account.
+// `harvest_withheld_fees` sweeps it into the mint (permissionless, like the
+// underlying Token 2022 instruction) and `withdraw_withheld_to_treasury` moves
+// it from the mint to the fee pool treasury, signed by the mint authority PDA
+// that `create_reward_mint` sets as withdraw-withheld authority.
+// `update_transfer_fee` changes the rate and cap through the same PDA, which is
+// also the transfer fee config authority.
+// Once the supply is exhausted, `withdraw_withheld_to_fee_pool` (permissionless)
+// sends withheld fees to the fee pool vault instead, funding farming claims,
+// and the treasury withdrawal is closed.
+
+/// Context for harvesting withheld fees from holder token accounts
+/// Source token accounts are passed as remaining accounts
+#[derive(Accounts)]
+pub struct HarvestWithheldFees<'info> {
+    /// CHECK: Token 2022 mint account with Transfer Fee extension
+    #[account(
+        mut,
+        seeds = [b"reward_mint"],
+        bump
+    )]
+    pub reward_mint: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_pool"],
+        bump
+    )]
+    pub fee_pool: Account<'info, FeePool>,
+    
+    pub payer: Signer<'info>,
+    
+    pub token_program: Program<'info, Token2022>,
+}
+
+/// Context for withdrawing withheld fees from the mint to the treasury (before supply exhaustion)
+#[derive(Accounts)]
+pub struct WithdrawWithheldToTreasury<'info> {
+    /// CHECK: Token 2022 mint account with Transfer Fee extension
+    #[account(
+        mut,
+        seeds = [b"reward_mint"],
+        bump
+    )]
+    pub reward_mint: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [b"mint_authority"],
+        bump
+    )]
+    /// CHECK: mint authority PDA (withdraw-withheld authority of the reward mint)
+    pub mint_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_pool"],
+        bump
+    )]
+    pub fee_pool: Account<'info, FeePool>,
+    
+    /// CHECK: Token 2022 account for the reward mint owned by FeePool.treasury_address
+    #[account(mut)]
+    pub treasury_token_account: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [b"global_stats"],
+        bump,
+        constraint = !global_stats.supply_exhausted @ GameError::SupplyExhausted
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = config.admin == admin.key() @ GameError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    pub admin: Signer<'info>,
+    
+    pub token_program: Program<'info, Token2022>,
+}
+
+/// Context for withdrawing withheld fees to the fee pool vault after supply exhaustion
+#[derive(Accounts)]
+pub struct WithdrawWithheldToFeePool<'info> {
+    /// CHECK: Token 2022 mint account with Transfer Fee extension
+    #[account(
+        mut,
+        seeds = [b"reward_mint"],
+        bump
+    )]
+    pub reward_mint: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [b"mint_authority"],
+        bump
+    )]
+    /// CHECK: mint authority PDA (withdraw-withheld authority of the reward mint)
+    pub mint_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_pool"],
+        bump
+    )]
+    pub fee_pool: Account<'info, FeePool>,
+    
+    /// CHECK: Token 2022 account for the reward mint owned by the fee pool PDA
+    #[account(mut)]
+    pub fee_pool_token_account: UncheckedAccount<'info>,
+    
+    /// Withdrawn fees are shared among current grow power through the reward accumulator
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump,
+        constraint = global_stats.supply_exhausted @ GameError::SupplyNotExhausted
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+    
+    #[account(
+        seeds = [b"emission_schedule"],
+        bump
+    )]
+    pub emission_schedule: Account<'info, EmissionSchedule>,
+    
+    pub payer: Signer<'info>,
+    
+    pub token_program: Program<'info, Token2022>,
+}
+
+/// Context for changing the WEED transfer fee
+#[derive(Accounts)]
+pub struct UpdateTransferFee<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        constraint = config.admin == admin.key() @ GameError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    /// CHECK: Token 2022 mint account with Transfer Fee extension
+    #[account(
+        mut,
+        seeds = [b"reward_mint"],
+        bump
+    )]
+    pub reward_mint: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [b"mint_authority"],
+        bump
+    )]
+    /// CHECK: transfer fee config authority PDA
+    pub transfer_fee_config_authority: UncheckedAccount<'info>,
+    
+    pub admin: Signer<'info>,
+    
+    pub token_program: Program<'info, Token2022>,
+}
+
+/// Sweep withheld fees from holder token accounts into the reward mint (permissionless)
+pub fn harvest_withheld_fees<'info>(ctx: Context<'_, '_, 'info, 'info, HarvestWithheldFees<'info>>) -> Result<()> {
+    validate_batch_size(ctx.remaining_accounts, MAX_BATCH_HARVEST_SIZE)?;
+    
+    let reward_mint_info = ctx.accounts.reward_mint.to_account_info();
+    let withheld_before = mint_withheld_amount(&reward_mint_info)?;
+    
+    harvest_withheld_tokens_to_mint(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            HarvestWithheldTokensToMint {
+                token_program_id: ctx.accounts.token_program.to_account_info(),
+                mint: reward_mint_info.clone(),
+            },
+        ),
+        ctx.remaining_accounts.to_vec(),
+    )?;
+    
+    // Token 2022 skips sources it cannot harvest, so measure what actually moved
+    let withheld_after = mint_withheld_amount(&reward_mint_info)?;
+    let harvested = withheld_after.saturating_sub(withheld_before);
+    
+    let current_time = Clock::get()?.unix_timestamp;
+    ctx.accounts.fee_pool.last_collection_time = current_time;
+    
+    msg!("Harvested {} withheld WEED from {} accounts (mint now holds {})",
+         harvested, ctx.remaining_accounts.len(), withheld_after);
+    
+    emit!(WithheldFeesHarvestedEvent {
+        harvester: ctx.accounts.payer.key(),
+        accounts: ctx.remaining_accounts.len() as u8,
+        amount: harvested,
+        mint_withheld: withheld_after,
+        timestamp: current_time,
+    });
+    
+    Ok(())
+}
+
+/// Withdraw all withheld fees from the reward mint to the treasury token account (admin only)
+pub fn withdraw_withheld_to_treasury(ctx: Context<WithdrawWithheldToTreasury>) -> Result<()> {
+    let (account_mint, account_owner) =
+        token_account_mint_and_owner(&ctx.accounts.treasury_token_account.to_account_info())?;
+    validate_treasury_token_account(
+        account_mint,
+        account_owner,
+        ctx.accounts.reward_mint.key(),
+        ctx.accounts.fee_pool.treasury_address,
+    )?;
+    
+    let amount = withdraw_all_withheld(
+        &ctx.accounts.reward_mint,
+        &ctx.accounts.treasury_token_account,
+        &ctx.accounts.mint_authority,
+        &ctx.accounts.token_program,
+        ctx.bumps.mint_authority,
+    )?;
+    
+    let current_time = Clock::get()?.unix_timestamp;
+    let fee_pool = &mut ctx.accounts.fee_pool;
+    fee_pool.accumulated_token_fees = fee_pool.accumulated_token_fees
+        .checked_add(amount)
+        .ok_or(GameError::CalculationOverflow)?;
+    fee_pool.last_collection_time = current_time;
+    
+    msg!("Withdrew {} withheld WEED to treasury {} (total {})",
+         amount, fee_pool.treasury_address, fee_pool.accumulated_token_fees);
+    
+    emit!(WithheldFeesWithdrawnEvent {
+        admin: ctx.accounts.admin.key(),
+        treasury_token_account: ctx.accounts.treasury_token_account.key(),
+        amount,
+        total_withdrawn: fee_pool.accumulated_token_fees,
+        timestamp: current_time,
+    });
+    
+    Ok(())
+}
+
+/// Withdraw all withheld fees from the reward mint to the fee pool vault (permissionless)
+/// Only after supply exhaustion, when claims are paid from the vault
+pub fn withdraw_withheld_to_fee_pool(ctx: Context<WithdrawWithheldToFeePool>) -> Result<()> {
+    let (account_mint, account_owner) =
+        token_account_mint_and_owner(&ctx.accounts.fee_pool_token_account.to_account_info())?;
+    validate_fee_pool_vault(
+        account_mint,
+        account_owner,
+        ctx.accounts.reward_mint.key(),
+        ctx.accounts.fee_pool.key(),
+    )?;
+    
+    let amount = withdraw_all_withheld(
+        &ctx.accounts.reward_mint,
+        &ctx.accounts.fee_pool_token_account,
+        &ctx.accounts.mint_authority,
+        &ctx.accounts.token_program,
+        ctx.bumps.mint_authority,
+    )?;
+    
+    // Credit the fees pro rata, so fee-funded claims cannot take more than their share
+    let current_time = Clock::get()?.unix_timestamp;
+    update_reward_accumulator(&mut ctx.accounts.global_stats, &ctx.accounts.emission_schedule, current_time)?;
+    distribute_fee_rewards(&mut ctx.accounts.global_stats, amount)?;
+    
+    let fee_pool = &mut ctx.accounts.fee_pool;
+    fee_pool.accumulated_token_fees = fee_pool.accumulated_token_fees
+        .checked_add(amount)
+        .ok_or(GameError::CalculationOverflow)?;
+    fee_pool.last_collection_time = current_time;
+    
+    msg!("Withdrew {} withheld WEED to the fee pool vault (total {})",
+         amount, fee_pool.accumulated_token_fees);
+    
+    emit!(WithheldFeesToFeePoolEvent {
+        payer: ctx.accounts.payer.key(),
+        fee_pool_token_account: ctx.accounts.fee_pool_token_account.key(),
+        amount,
+        total_withdrawn: fee_pool.accumulated_token_fees,
+        timestamp: current_time,
+    });
+    
+    Ok(())
+}
+
+/// Move every withheld fee from the mint to `destination`, signed by the mint authority PDA
+fn withdraw_all_withheld<'info>(
+    reward_mint: &UncheckedAccount<'info>,
+    destination: &UncheckedAccount<'info>,
+    mint_authority: &UncheckedAccount<'info>,
+    token_program: &Program<'info, Token2022>,
+    mint_authority_bump: u8,
+) -> Result<u64> {
+    let amount = mint_withheld_amount(&reward_mint.to_account_info())?;
+    require!(amount > 0, GameError::InsufficientFees);
+    
+    let authority_seeds: &[&[u8]] = &[b"mint_authority", &[mint_authority_bump]];
+    withdraw_withheld_tokens_from_mint(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            WithdrawWithheldTokensFromMint {
+                token_program_id: token_program.to_account_info(),
+                mint: reward_mint.to_account_info(),
+                destination: destination.to_account_info(),
+                authority: mint_authority.to_account_info(),
+            },
+            &[authority_seeds],
+        ),
+    )?;
+    
+    Ok(amount)
+}
+
+/// Change the WEED transfer fee rate and cap (admin only)
+/// Token 2022 applies the new fee two epochs later, so it is recorded as
+/// `Config.pending_transfer_fee_basis_points` with the epoch it takes effect
+pub fn update_transfer_fee(ctx: Context<UpdateTransferFee>, basis_points: u16, maximum_fee: u64) -> Result<()> {
+    validate_transfer_fee(basis_points, maximum_fee)?;
+    
+    let authority_seeds: &[&[u8]] = &[b"mint_authority", &[ctx.bumps.transfer_fee_config_authority]];
+    transfer_fee_set(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferFeeSetTransferFee {
+                token_program_id: ctx.accounts.token_program.to_account_info(),
+                mint: ctx.accounts.reward_mint.to_account_info(),
+                authority: ctx.accounts.transfer_fee_config_authority.to_account_info(),
+            },
+            &[authority_seeds],
+        ),
+        basis_points,
+        maximum_fee,
+    )?;
+    
+    let clock = Clock::get()?;
+    let config = &mut ctx.accounts.config;
+    let old_basis_points = config.transfer_fee_basis_points_at(clock.epoch);
+    config.schedule_transfer_fee(basis_points, clock.epoch)?;
+    
+    msg!("Transfer fee set: {} -> {} bps, max {}, effective from epoch {}",
+         old_basis_points, basis_points, maximum_fee, config.pending_transfer_fee_epoch);
+    
+    emit!(TransferFeeUpdatedEvent {
+        admin: ctx.accounts.admin.key(),
+        old_percentage: config.trading_fee_percentage,
+        old_basis_points,
+        basis_points,
+        maximum_fee,
+        effective_epoch: config.pending_transfer_fee_epoch,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
@@ -1,7 +1,10 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
+use crate::events::*;
+use crate::constants::ROLE_INVITE_ISSUER;
+use crate::validation::admin_validation::has_role;
 
-/// Context for user initialization (admin/operator only - without invite code)
+/// Context for user initialization (admin/operator/invite issuer only - without invite code)
 #[derive(Accounts)]
 pub struct InitUser<'info> {
     #[account(
@@ -17,23 +20,31 @@ pub struct InitUser<'info> {
     #[account(
         seeds = [b"config"],
         bump,
-        constraint = config.admin == admin.key() || config.operator == admin.key() @ crate::error::GameError::Unauthorized
+        constraint = config.operator == admin.key()
+            || has_role(&config, role_registry.as_deref(), admin.key(), ROLE_INVITE_ISSUER) @ crate::error::GameError::Unauthorized
     )]
     pub config: Account<'info, Config>,
     
+    /// Optional role registry (required for invite issuers)
+    #[account(
+        seeds = [b"role_registry"],
+        bump
+    )]
+    pub role_registry: Option<Account<'info, RoleRegistry>>,
+    
     /// User account to be initialized (does not need to sign)
     /// CHECK: User account being initialized
     pub user: UncheckedAccount<'info>,
     
-    /// Admin or operator who can create users without invite codes
+    /// Admin, operator or invite issuer who can create users without invite codes
     #[account(mut)]
     pub admin: Signer<'info>,
     
     pub system_program: Program<'info, System>,
 }
 
-/// Initialize user account (admin/operator only - without invite code)
-/// Only admin or operator can create users without going through the invite system
+/// Initialize user account (admin/operator/invite issuer only - without invite code)
+/// Only admin, operator or an invite issuer can create users without going through the invite system
 pub fn init_user(ctx: Context<InitUser>, referrer: Option<Pubkey>) -> Result<()> {
     let user_state = &mut ctx.accounts.user_state;
     
@@ -45,11 +56,22 @@ pub fn init_user(ctx: Context<InitUser>, referrer: Option<Pubkey>) -> Result<()>
     user_state.referrer = referrer;
     user_state.pending_referral_rewards = 0;
     user_state.total_packs_purchased = 0;
-    user_state.reserve = [0; 28];
+    user_state.version = UserState::CURRENT_VERSION;
+    user_state.reward_debt = 0;
+    user_state.pending_farming_rewards = 0;
+    user_state.reserve = [0; 3];
 
     msg!("User initialized by admin {} for user: {} with referrer: {:?}", 
          ctx.accounts.admin.key(),
          ctx.accounts.user.key(), 
          referrer);
+    
+    emit!(UserInitializedEvent {
+        user: ctx.accounts.user.key(),
+        referrer,
+        initialized_by: ctx.accounts.admin.key(),
+        timestamp: user_state.last_harvest_time,
+    });
+    
     Ok(())
 }
\ No newline at end of file
@@ -23,6 +23,8 @@ pub mod constants;      // ゲーム定数
 pub mod validation;     // バリデーション機能
 pub mod economics;      // 経済計算
 pub mod error_handling; // エラーハンドリング
+pub mod randomness;     // シードパック乱数（ソース選択・コミット・リビール）
+pub mod events;         // インデクサー向けAnchorイベント
 
 // テストモジュール（開発時のみ）
 #[cfg(test)]
@@ -32,6 +34,7 @@ pub mod tests;
 
 // 他のモジュールから必要な要素をインポート
 use instructions::*;  // すべての命令コンテキストをインポート
+use state::{ConfigChange, ProposalAccount}; // 命令引数として使用する型
 
 // #[program]属性は、このモジュールがSolanaプログラムのエントリーポイントであることを示す
 // Anchorがこの属性を見つけると、各関数を呼び出し可能な命令として処理する
@@ -68,14 +71,14 @@ pub mod farm_game {
     /// # 機能
     /// - PDAによるミント権限管理（セキュリティ向上）
     /// - SPL Token 2022のTransfer Fee Extension（2%手数料）
-    /// - Metaplexメタデータ作成（トークン情報表示用）
+    /// - Token 2022メタデータ拡張（MetadataPointer + TokenMetadata、名称・シンボルは定数から）
     /// - 6桁精度での発行設定
     /// - 自動手数料回収（treasury宛）
     /// 
     /// # Transfer Fee Extension
     /// - 手数料: 2.00% (200 basis points)
     /// - 最大手数料: 1000 WEED
-    /// - 手数料回収権限: treasury
+    /// - 手数料回収権限: mint_authority PDA（`withdraw_withheld_to_treasury`経由でtreasuryへ）
     /// - 設定変更権限: mint_authority PDA
     /// 
     /// # Security
@@ -85,73 +88,226 @@ pub mod farm_game {
     pub fn create_reward_mint(ctx: Context<CreateRewardMint>) -> Result<()> {
         instructions::admin::create_reward_mint(ctx)
     }
+    
+    /// WEEDトークンメタデータの更新（管理者のみ）
+    /// `field`が"uri"ならURI、それ以外は追加フィールドとして設定
+    /// 名称・シンボルは定数で固定のため変更不可
+    pub fn update_token_metadata(ctx: Context<UpdateTokenMetadata>, field: String, value: String) -> Result<()> {
+        instructions::admin::update_token_metadata(ctx, field, value)
+    }
 
     /// Initialize global statistics
     pub fn initialize_global_stats(ctx: Context<InitializeGlobalStats>) -> Result<()> {
         instructions::admin::initialize_global_stats(ctx)
     }
 
-    /// Initialize fee pool
+    /// Create the emission schedule from the current halving config (admin only)
+    /// Required before planting, removing, buying farm space or claiming
+    pub fn initialize_emission_schedule(ctx: Context<InitializeEmissionSchedule>) -> Result<()> {
+        instructions::emission::initialize_emission_schedule(ctx)
+    }
+
+    /// Initialize fee pool (admin only)
+    /// The fee pool PDA also serves as the vault holding collected VRF fees
     pub fn initialize_fee_pool(ctx: Context<InitializeFeePool>, treasury_address: Pubkey) -> Result<()> {
         instructions::admin::initialize_fee_pool(ctx, treasury_address)
     }
     
+    /// Withdraw all accumulated fees to `FeePool.treasury_address` (admin only)
+    /// 
+    /// # Security
+    /// - Admin signature required
+    /// - Treasury account must match the fee pool's treasury
+    /// - The fee pool keeps its rent-exempt reserve
+    pub fn withdraw_fees(ctx: Context<WithdrawFees>) -> Result<()> {
+        instructions::admin::withdraw_fees(ctx)
+    }
+    
+    /// Harvest withheld WEED transfer fees from holder token accounts into the mint
+    /// Anyone can crank this; source token accounts are passed as remaining accounts
+    pub fn harvest_withheld_fees<'info>(ctx: Context<'_, '_, 'info, 'info, HarvestWithheldFees<'info>>) -> Result<()> {
+        instructions::transfer_fee::harvest_withheld_fees(ctx)
+    }
+    
+    /// Withdraw withheld WEED transfer fees from the mint to the treasury (admin only)
+    /// 
+    /// # Security
+    /// - Admin signature required
+    /// - Destination must be a WEED token account owned by `FeePool.treasury_address`
+    /// - Signed by the mint authority PDA (withdraw-withheld authority)
+    pub fn withdraw_withheld_to_treasury(ctx: Context<WithdrawWithheldToTreasury>) -> Result<()> {
+        instructions::transfer_fee::withdraw_withheld_to_treasury(ctx)
+    }
+    
+    /// Withdraw withheld WEED transfer fees to the fee pool vault (permissionless)
+    /// Only after the supply is exhausted; the fees are shared pro rata through the reward accumulator
+    pub fn withdraw_withheld_to_fee_pool(ctx: Context<WithdrawWithheldToFeePool>) -> Result<()> {
+        instructions::transfer_fee::withdraw_withheld_to_fee_pool(ctx)
+    }
+    
+    /// Change the WEED transfer fee rate and cap (admin only)
+    /// 
+    /// # Parameters
+    /// - `basis_points`: New fee in basis points (0-1000, i.e. up to 10%)
+    /// - `maximum_fee`: New per-transfer cap in WEED base units
+    /// 
+    /// Token 2022 applies the new fee two epochs later. It is stored as
+    /// `Config.pending_transfer_fee_basis_points` with `pending_transfer_fee_epoch`;
+    /// use `Config::transfer_fee_basis_points_at` for the fee charged in a given epoch
+    /// 
+    /// # Security
+    /// - Admin signature required
+    pub fn update_transfer_fee(ctx: Context<UpdateTransferFee>, basis_points: u16, maximum_fee: u64) -> Result<()> {
+        instructions::transfer_fee::update_transfer_fee(ctx, basis_points, maximum_fee)
+    }
+    
     /// Update system configuration (admin only)
-    /// Allows admin to modify various system parameters including operator address
+    /// Allows admin to modify non-economic system parameters
+    /// Operator changes go through `propose_operator` / `accept_operator`;
+    /// base rate and halving interval go through the config change timelock
     pub fn update_config(
         ctx: Context<UpdateConfig>,
-        new_operator: Option<Pubkey>,
-        new_base_rate: Option<u64>,
-        new_halving_interval: Option<i64>,
         new_treasury: Option<Pubkey>,
         new_max_invite_limit: Option<u8>,
     ) -> Result<()> {
-        instructions::admin::update_config(ctx, new_operator, new_base_rate, new_halving_interval, new_treasury, new_max_invite_limit)
+        instructions::admin::update_config(ctx, new_treasury, new_max_invite_limit)
     }
-    
-    /// Update seed pack cost (admin only)
-    /// Allows admin to dynamically adjust the WEED price for seed packs
-    /// 
+
+    /// Propose a new admin (admin only)
+    /// Step 1 of the two-step handover; the current admin keeps control until accepted
+    ///
     /// # Parameters
-    /// * `new_seed_pack_cost` - New cost in WEED tokens (with 6 decimals)
-    /// 
-    /// # Example
-    /// - 300 WEED = 300_000_000 (with 6 decimals)
-    /// - 500 WEED = 500_000_000 (with 6 decimals)
-    /// 
+    /// * `new_admin` - Proposed admin address
+    /// * `expires_in` - Optional validity window in seconds (None = no expiry)
+    ///
     /// # Security
     /// - Admin signature required
-    /// - Prevents zero cost (must be > 0)
-    /// - Prevents excessively high costs (max 10,000 WEED)
-    pub fn update_seed_pack_cost(
-        ctx: Context<UpdateSeedPackCost>,
-        new_seed_pack_cost: u64,
-    ) -> Result<()> {
-        instructions::admin::update_seed_pack_cost(ctx, new_seed_pack_cost)
+    /// - Rejects the zero address and the current admin
+    pub fn propose_admin(ctx: Context<ProposeAuthority>, new_admin: Pubkey, expires_in: Option<i64>) -> Result<()> {
+        instructions::admin::propose_admin(ctx, new_admin, expires_in)
     }
-    
-    /// Update farm space cost (admin only)
-    /// Allows admin to dynamically adjust the SOL price for farm space purchases
-    /// 
+
+    /// Accept a pending admin proposal
+    /// Step 2 of the two-step handover
+    ///
+    /// # Security
+    /// - Pending admin signature required
+    /// - Fails once the proposal has expired
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        instructions::admin::accept_admin(ctx)
+    }
+
+    /// Propose a new operator (admin only)
+    /// Step 1 of the two-step operator handover
+    ///
+    /// # Parameters
+    /// * `new_operator` - Proposed operator address
+    /// * `expires_in` - Optional validity window in seconds (None = no expiry)
+    pub fn propose_operator(ctx: Context<ProposeAuthority>, new_operator: Pubkey, expires_in: Option<i64>) -> Result<()> {
+        instructions::admin::propose_operator(ctx, new_operator, expires_in)
+    }
+
+    /// Accept a pending operator proposal
+    /// Step 2 of the two-step operator handover
+    ///
+    /// # Security
+    /// - Pending operator signature required
+    /// - Fails once the proposal has expired
+    pub fn accept_operator(ctx: Context<AcceptOperator>) -> Result<()> {
+        instructions::admin::accept_operator(ctx)
+    }
+
+    /// Initialize the role registry (admin only)
+    /// Enables delegating pricing, seed curation, pausing and invite issuance
+    pub fn initialize_role_registry(ctx: Context<InitializeRoleRegistry>) -> Result<()> {
+        instructions::admin::initialize_role_registry(ctx)
+    }
+
+    /// Grant roles to a member (admin only)
+    ///
+    /// # Parameters
+    /// * `member` - Address receiving the roles
+    /// * `roles` - Combination of `ROLE_PRICING_MANAGER`, `ROLE_SEED_CURATOR`, `ROLE_PAUSER`, `ROLE_INVITE_ISSUER`
+    ///
+    /// # Security
+    /// - Admin signature required
+    /// - Registry holds at most 16 members
+    pub fn grant_role(ctx: Context<UpdateRole>, member: Pubkey, roles: u8) -> Result<()> {
+        instructions::admin::grant_role(ctx, member, roles)
+    }
+
+    /// Revoke roles from a member (admin only)
+    /// The member is removed once no roles remain
+    pub fn revoke_role(ctx: Context<UpdateRole>, member: Pubkey, roles: u8) -> Result<()> {
+        instructions::admin::revoke_role(ctx, member, roles)
+    }
+
+    /// Pause all user-facing instructions (admin or pauser)
+    /// Emergency stop for production incidents
+    ///
+    /// # Parameters
+    /// * `reason` - Operator-defined reason code recorded in config
+    ///
+    /// # Security
+    /// - Admin or pauser signature required
+    /// - Fails if the system is already paused
+    pub fn pause_system(ctx: Context<PauseSystem>, reason: u8) -> Result<()> {
+        instructions::admin::pause_system(ctx, reason)
+    }
+
+    /// Resume all user-facing instructions (admin or pauser)
+    ///
+    /// # Security
+    /// - Admin or pauser signature required
+    /// - Fails if the system is not paused
+    pub fn unpause_system(ctx: Context<UnpauseSystem>) -> Result<()> {
+        instructions::admin::unpause_system(ctx)
+    }
+
+    /// Pause or resume individual subsystems (admin or pauser)
+    /// Lets ops isolate an incident without freezing the whole game
+    ///
     /// # Parameters
-    /// * `new_farm_space_cost_sol` - New cost in lamports (SOL)
+    /// * `flags` - Combination of `PAUSE_PURCHASE`, `PAUSE_OPEN`, `PAUSE_PLANT`, `PAUSE_CLAIM`, `PAUSE_INVITE`
+    /// * `paused` - true to set the bits, false to clear them
+    ///
+    /// # Security
+    /// - Admin or pauser signature required
+    /// - Rejects empty or unknown flag bits
+    pub fn set_subsystem_pause(ctx: Context<SetSubsystemPause>, flags: u8, paused: bool) -> Result<()> {
+        instructions::admin::set_subsystem_pause(ctx, flags, paused)
+    }
+
+    // ===== TIMELOCKED CONFIG CHANGES =====
+
+    /// Queue an economic config change
+    /// Seed pack cost, farm space cost, base rate, halving interval, probability table
+    /// and the timelock delay itself can only be changed through this queue
     /// 
-    /// # Example
-    /// - 0.5 SOL = 500_000_000 lamports
-    /// - 1.0 SOL = 1_000_000_000 lamports
+    /// # Parameters
+    /// * `change` - The `ConfigChange` to apply once the delay has elapsed
     /// 
     /// # Security
-    /// - Admin signature required
-    /// - Prevents zero cost (must be > 0)
-    /// - Prevents excessively high costs (max 10 SOL)
-    pub fn update_farm_space_cost(
-        ctx: Context<UpdateFarmSpaceCost>,
-        new_farm_space_cost_sol: u64,
-    ) -> Result<()> {
-        instructions::admin::update_farm_space_cost(ctx, new_farm_space_cost_sol)
+    /// - Pricing changes: admin or pricing manager
+    /// - Probability table: admin or seed curator
+    /// - Base rate, halving interval and delay: admin only
+    /// - Change is validated at queue time and again at execution
+    pub fn queue_config_change(ctx: Context<QueueConfigChange>, change: ConfigChange) -> Result<()> {
+        instructions::timelock::queue_config_change(ctx, change)
     }
-    
-    /// Reveal a new seed type (admin only)
+
+    /// Execute a queued config change after its timelock (same authority as queuing)
+    /// Pass the probability table account for probability table changes
+    pub fn execute_config_change(ctx: Context<ExecuteConfigChange>) -> Result<()> {
+        instructions::timelock::execute_config_change(ctx)
+    }
+
+    /// Cancel a queued config change (same authority as queuing)
+    pub fn cancel_config_change(ctx: Context<CancelConfigChange>) -> Result<()> {
+        instructions::timelock::cancel_config_change(ctx)
+    }
+
+    /// Reveal a new seed type (admin or seed curator)
     /// Makes a previously hidden seed type visible to users with its values
     /// 
     /// # Parameters
@@ -165,7 +321,7 @@ pub mod farm_game {
     /// - probability_percentage: 1.5
     /// 
     /// # Security
-    /// - Admin signature required
+    /// - Admin or seed curator signature required
     /// - Seed must not already be revealed
     /// - Values must be within reasonable ranges
     pub fn reveal_seed(
@@ -177,7 +333,7 @@ pub mod farm_game {
         instructions::admin::reveal_seed(ctx, seed_index, grow_power, probability_percentage)
     }
     
-    /// Update values for an already revealed seed type (admin only)
+    /// Update values for an already revealed seed type (admin or seed curator)
     /// Allows changing grow power and probability for existing revealed seeds
     /// 
     /// # Parameters
@@ -191,7 +347,7 @@ pub mod farm_game {
     /// - probability_percentage: 30.0 (same as before)
     /// 
     /// # Security
-    /// - Admin signature required
+    /// - Admin or seed curator signature required
     /// - Seed must already be revealed
     /// - Values must be within reasonable ranges
     pub fn update_seed_values(
@@ -221,55 +377,107 @@ pub mod farm_game {
         instructions::admin::initialize_probability_table(ctx)
     }
     
-    /// Update probability table with new settings (admin only)
-    /// Allows runtime modification of seed probabilities without code deployment
+    // ===== MULTISIG =====
+
+    /// Create the built-in multisig (admin only, one-time)
+    /// Hand `Config.admin` to the `multisig_signer` PDA afterwards to require M-of-N approval
     /// 
     /// # Parameters
-    /// * `version` - Version number for tracking changes
-    /// * `seed_count` - Number of seed types (1-9)
-    /// * `grow_powers` - Grow power values for each seed type
-    /// * `probability_thresholds` - Cumulative probability thresholds (must end at 10000)
-    /// * `probability_percentages` - Human-readable percentage values
-    /// * `expected_value` - Calculated expected grow power per pack
-    /// * `name` - Table name/description (max 32 chars)
-    /// 
-    /// # Examples
-    /// ## Table 1 (6 seeds):
-    /// - grow_powers: [100, 180, 420, 720, 1000, 5000]
-    /// - thresholds: [4300, 6800, 8200, 9100, 9700, 10000]
-    /// - percentages: [43.0, 25.0, 14.0, 9.0, 6.0, 3.0]
-    /// 
-    /// ## Table 2 (9 seeds):
-    /// - grow_powers: [100, 180, 420, 720, 1000, 5000, 15000, 30000, 60000]
-    /// - thresholds: [4222, 6666, 7999, 8832, 9388, 9721, 9854, 9943, 10000]
-    /// - percentages: [42.23, 24.44, 13.33, 8.33, 5.56, 3.33, 1.33, 0.89, 0.56]
+    /// * `signers` - Member addresses (1-10, unique)
+    /// * `threshold` - Approvals required to execute a proposal
+    pub fn create_multisig(ctx: Context<CreateMultisig>, signers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        instructions::multisig::create_multisig(ctx, signers, threshold)
+    }
+
+    /// Create a proposal wrapping an instruction for this program (members only)
     /// 
-    /// # Security
-    /// - Admin signature required
-    /// - Validates probability distribution sums to 100%
-    /// - Ensures thresholds are in ascending order
-    pub fn update_probability_table(
-        ctx: Context<UpdateProbabilityTable>,
-        version: u32,
-        seed_count: u8,
-        grow_powers: Vec<u64>,
-        probability_thresholds: Vec<u16>,
-        probability_percentages: Vec<f32>,
-        expected_value: u64,
-        name: String,
+    /// # Parameters
+    /// * `instruction_accounts` - Account metas of the wrapped instruction
+    /// * `instruction_data` - Serialized instruction data (discriminator + args)
+    pub fn create_proposal(
+        ctx: Context<CreateProposal>,
+        instruction_accounts: Vec<ProposalAccount>,
+        instruction_data: Vec<u8>,
     ) -> Result<()> {
-        instructions::admin::update_probability_table(
-            ctx,
-            version,
-            seed_count,
-            grow_powers,
-            probability_thresholds,
-            probability_percentages,
-            expected_value,
-            name,
-        )
+        instructions::multisig::create_proposal(ctx, instruction_accounts, instruction_data)
+    }
+
+    /// Approve a proposal (members only)
+    pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+        instructions::multisig::approve_proposal(ctx)
+    }
+
+    /// Execute a proposal once the threshold is met (members only)
+    /// Pass the wrapped instruction's accounts as remaining accounts
+    pub fn execute_proposal<'info>(ctx: Context<'_, '_, '_, 'info, ExecuteProposal<'info>>) -> Result<()> {
+        instructions::multisig::execute_proposal(ctx)
+    }
+
+    /// Replace multisig members and threshold
+    /// Only callable by the `multisig_signer` PDA, i.e. through an executed proposal
+    pub fn set_multisig_signers(ctx: Context<SetMultisigSigners>, signers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        instructions::multisig::set_multisig_signers(ctx, signers, threshold)
+    }
+
+    // ===== ACCOUNT MIGRATION =====
+
+    /// Migrate the global config to the current layout version
+    /// Reallocs the account in place and fills defaults for fields added since its version
+    /// 
+    /// # Security
+    /// - Admin signature required (admin pays any rent increase)
+    /// - Fails if the account is already current
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        instructions::migration::migrate_config(ctx)
+    }
+
+    /// Migrate a user state to the current layout version
+    /// Permissionless: only fills defaults, the payer covers any rent increase
+    /// Unclaimed rewards from before the accumulator are settled with the old formula
+    pub fn migrate_user_state(ctx: Context<MigrateUserState>) -> Result<()> {
+        instructions::migration::migrate_user_state(ctx)
+    }
+
+    /// Migrate a farm space to the current layout version
+    /// Permissionless: only fills defaults, the payer covers any rent increase
+    /// Unclaimed rewards from before the accumulator are settled with the old formula
+    pub fn migrate_farm_space(ctx: Context<MigrateFarmSpace>) -> Result<()> {
+        instructions::migration::migrate_farm_space(ctx)
     }
 
+    /// Migrate global statistics to the current layout version (admin only)
+    pub fn migrate_global_stats(ctx: Context<MigrateGlobalStats>) -> Result<()> {
+        instructions::migration::migrate_global_stats(ctx)
+    }
+
+    /// Migrate the fee pool to the current layout version (admin only)
+    pub fn migrate_fee_pool(ctx: Context<MigrateFeePool>) -> Result<()> {
+        instructions::migration::migrate_fee_pool(ctx)
+    }
+
+    /// Migrate a seed pack to the current layout version
+    /// Permissionless: unopened legacy packs commit to a future slot, the payer covers any rent increase
+    pub fn migrate_seed_pack(ctx: Context<MigrateSeedPack>, pack_id: u64) -> Result<()> {
+        instructions::migration::migrate_seed_pack(ctx, pack_id)
+    }
+
+    // ===== RANDOMNESS BEACON =====
+    
+    /// Create the admin randomness beacon (admin only)
+    /// Only needed when `Config.randomness_source` is `AdminBeacon` (test clusters)
+    pub fn initialize_randomness_beacon(ctx: Context<InitializeRandomnessBeacon>) -> Result<()> {
+        instructions::beacon::initialize_randomness_beacon(ctx)
+    }
+    
+    /// Publish the next randomness beacon round (admin only)
+    /// Packs bought since the previous round open with this value
+    ///
+    /// # Security
+    /// - The admin controls outcomes; never select this source on mainnet
+    pub fn publish_randomness_beacon(ctx: Context<PublishRandomnessBeacon>, value: [u8; 32]) -> Result<()> {
+        instructions::beacon::publish_randomness_beacon(ctx, value)
+    }
+    
     // ===== USER MANAGEMENT INSTRUCTIONS =====
 
     /// ユーザーアカウントの初期化
@@ -323,15 +531,8 @@ pub mod farm_game {
     
     // ===== REFERRAL REWARD ACCUMULATION SYSTEM =====
     
-    /// 紹介報酬蓄積（内部処理用）
-    /// 他のユーザーが報酬請求時に自動実行される
-    pub fn accumulate_referral_reward(
-        ctx: Context<AccumulateReferralReward>,
-        reward_amount: u64,
-        referral_level: u8,
-    ) -> Result<()> {
-        instructions::referral::accumulate_referral_reward(ctx, reward_amount, referral_level)
-    }
+    // Note: Referral rewards accrue only inside claim_reward_with_referral_rewards,
+    // from the farming reward actually paid along the recorded referral chain
     
     /// 未請求紹介報酬確認（読み取り専用）
     /// UI表示用、請求前の金額確認
@@ -339,6 +540,12 @@ pub mod farm_game {
         instructions::referral::view_pending_referral_rewards(ctx)
     }
     
+    /// 報酬請求シミュレーション（読み取り専用）
+    /// `simulateTransaction`で農場報酬・紹介報酬の内訳を返す
+    pub fn simulate_claim(ctx: Context<SimulateClaim>) -> Result<ClaimSimulation> {
+        instructions::referral::simulate_claim(ctx)
+    }
+    
     /// 統合報酬請求（メイン関数）
     /// 農場報酬と紹介報酬をすべて一度に請求する
     /// 
@@ -346,7 +553,7 @@ pub mod farm_game {
     /// 1. 半減期チェック・適用
     /// 2. 農場報酬計算（比例配分）
     /// 3. 蓄積された紹介報酬請求
-    /// 4. 新規紹介報酬分配（L1: 10%, L2: 5%）
+    /// 4. 新規紹介報酬分配（L1: 10%, L2: 5%、記録された紹介チェーンと照合）
     /// 5. すべてのトークンを一括ミント・配布
     /// 
     /// # 統合処理のメリット
@@ -416,8 +623,9 @@ pub mod farm_game {
     ///   * 総計: ~2,077,400 lamports
     /// 
     /// # VRF処理フロー
-    /// 1. **購入時（コミット）**: VRF要求 + 手数料支払い
-    /// 2. **開封時（リビール）**: VRF結果取得 + 種生成
+    /// 1. **購入時（コミット）**: `Config.randomness_source`のソースへコミット + 将来スロットへのコミット
+    ///    （VRF手数料はオラクルソースのみ）
+    /// 2. **開封時（リビール）**: ソースの値 + SlotHashesからコミット先スロットのハッシュ取得 + 種生成
     /// 3. **透明性**: すべてオンチェーンで検証可能
     /// 
     /// # 確率テーブル（VRF保証済み）
@@ -446,10 +654,27 @@ pub mod farm_game {
     }
 
     /// Open seed pack to reveal seeds
+    /// Randomness comes from the pack's recorded source mixed with the hash of the slot
+    /// committed at purchase (SlotHashes sysvar)
+    /// 
+    /// # Security
+    /// - Rejected before the commit slot has passed or the source has revealed
+    /// - Rejected once the slot hash has left SlotHashes (~512 slots), so packs cannot be re-rolled
+    /// - Legacy packs must go through `migrate_seed_pack` first
     pub fn open_seed_pack(ctx: Context<OpenSeedPack>, quantity: u8) -> Result<()> {
         instructions::seeds::open_seed_pack(ctx, quantity)
     }
 
+    /// Re-commit an unopened seed pack whose slot hash has expired
+    /// Moves the commit to a new future slot so the owner can still open the pack
+    /// 
+    /// # Security
+    /// - Admin signature required, so owners cannot reroll by waiting for expiry
+    /// - Fails while the committed slot's hash is still in SlotHashes
+    pub fn recommit_seed_pack(ctx: Context<RecommitSeedPack>) -> Result<()> {
+        instructions::seeds::recommit_seed_pack(ctx)
+    }
+
     /// Plant seed in farm space
     pub fn plant_seed(ctx: Context<PlantSeed>, seed_id: u64) -> Result<()> {
         instructions::seeds::plant_seed(ctx, seed_id)
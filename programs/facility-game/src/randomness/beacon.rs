@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use crate::error::GameError;
+use crate::state::{RandomnessBeacon, RandomnessSource};
+
+// ===== ADMIN RANDOMNESS BEACON =====
+// For test clusters without an oracle: the admin publishes numbered rounds to
+// the ["randomness_beacon"] PDA. A pack commits to the round after the latest
+// one at purchase and opens only while exactly that round is current, so the
+// owner cannot wait for a later, better round. A pack whose round was replaced
+// before it was opened is moved to the next round with `recommit_seed_pack`.
+// The admin still chooses every value, so the source can only be selected in
+// `localnet` builds.
+
+/// Round a pack purchased now commits to (the next one the admin publishes)
+pub fn next_round(beacon: &RandomnessBeacon) -> Result<u64> {
+    beacon.round
+        .checked_add(1)
+        .ok_or(GameError::CalculationOverflow.into())
+}
+
+/// Return the beacon value for a pack committed to `round`
+/// Only the committed round's own value is accepted
+pub fn validate_beacon_reveal(beacon: &RandomnessBeacon, round: u64) -> Result<[u8; 32]> {
+    require!(beacon.round >= round, GameError::RandomnessNotReady);
+    require!(beacon.round == round, GameError::BeaconRoundMissed);
+    Ok(beacon.value)
+}
+
+/// Validate a pack committed to `round` can no longer be opened (a later round is current)
+pub fn validate_beacon_round_missed(beacon: &RandomnessBeacon, round: u64) -> Result<()> {
+    require!(beacon.round > round, GameError::CommitNotExpired);
+    Ok(())
+}
+
+/// Validate a randomness source can be selected in this build
+/// The admin beacon lets the admin pick pack outcomes, so only `localnet` builds accept it
+pub fn validate_source_enabled(source: RandomnessSource) -> Result<()> {
+    require!(
+        source != RandomnessSource::AdminBeacon || cfg!(feature = "localnet"),
+        GameError::AdminBeaconDisabled
+    );
+    Ok(())
+}
+
+/// Validate a value before publishing it as the next round
+pub fn validate_beacon_value(value: &[u8; 32]) -> Result<()> {
+    require!(*value != [0u8; 32], GameError::InvalidBeaconValue);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn beacon(round: u64) -> RandomnessBeacon {
+        RandomnessBeacon {
+            round,
+            value: [round as u8; 32],
+            published_at: 0,
+            reserve: [0; 16],
+        }
+    }
+    
+    #[test]
+    fn test_beacon_rounds() {
+        assert_eq!(next_round(&beacon(0)).unwrap(), 1);
+        assert_eq!(next_round(&beacon(41)).unwrap(), 42);
+        assert!(next_round(&beacon(u64::MAX)).is_err());
+        
+        // Committed round not published yet
+        assert!(validate_beacon_reveal(&beacon(4), 5).is_err());
+        assert_eq!(validate_beacon_reveal(&beacon(5), 5).unwrap(), [5; 32]);
+        // Later rounds cannot be used to reroll the pack
+        assert!(validate_beacon_reveal(&beacon(7), 5).is_err());
+        
+        // A replaced round can be recommitted, the current one cannot
+        assert!(validate_beacon_round_missed(&beacon(5), 5).is_err());
+        assert!(validate_beacon_round_missed(&beacon(6), 5).is_ok());
+        
+        assert!(validate_beacon_value(&[1; 32]).is_ok());
+        assert!(validate_beacon_value(&[0; 32]).is_err());
+    }
+    
+    #[test]
+    fn test_beacon_source_gating() {
+        assert!(validate_source_enabled(RandomnessSource::SlotHash).is_ok());
+        assert!(validate_source_enabled(RandomnessSource::Oracle).is_ok());
+        assert_eq!(validate_source_enabled(RandomnessSource::AdminBeacon).is_ok(), cfg!(feature = "localnet"));
+    }
+}
@@ -0,0 +1,269 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use crate::error::GameError;
+use crate::state::{RandomnessBeacon, RandomnessSource, SeedPack};
+
+pub mod slot_hashes;    // SlotHashes commit-reveal (every pack)
+pub mod switchboard;    // Switchboard On-Demand randomness accounts
+pub mod beacon;         // Admin randomness beacon (test clusters)
+
+pub use slot_hashes::{calculate_commit_slot, find_slot_hash, validate_commit_expired};
+
+// ===== RANDOMNESS ROUTING =====
+// All seed pack randomness goes through `commit` (purchase) and `reveal` (open).
+// Every pack commits to a future SlotHashes entry; `Config.randomness_source`
+// selects the external value mixed in on top of it:
+// - SlotHash: none
+// - Oracle: Switchboard On-Demand reveal for the commit accepted at purchase
+// - AdminBeacon: admin-published beacon round (`localnet` builds only)
+// The source is recorded in the pack, so changing `Config.randomness_source`
+// never affects packs that were already bought.
+
+/// Accounts a randomness source may read (each source only uses its own)
+#[derive(Clone, Copy, Default)]
+pub struct SourceAccounts<'a, 'info> {
+    /// Oracle randomness account (owner checked by the account context)
+    pub oracle: Option<&'a AccountInfo<'info>>,
+    /// Admin randomness beacon
+    pub beacon: Option<&'a RandomnessBeacon>,
+}
+
+/// What a seed pack commits to at purchase
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Commitment {
+    /// Source selected at purchase
+    pub source: RandomnessSource,
+    /// Slot whose hash seeds the pack
+    pub commit_slot: u64,
+    /// Source-specific commitment: oracle seed slot or beacon round (0 for SlotHash)
+    pub source_commit: u64,
+    /// Oracle randomness account bound to the pack
+    pub vrf_account: Option<Pubkey>,
+}
+
+/// Commit a newly purchased pack to the selected source
+pub fn commit(source: RandomnessSource, accounts: &SourceAccounts, current_slot: u64) -> Result<Commitment> {
+    let commit_slot = calculate_commit_slot(current_slot)?;
+    
+    let (source_commit, vrf_account) = match source {
+        RandomnessSource::SlotHash => (0, None),
+        RandomnessSource::Oracle => {
+            let oracle = accounts.oracle.ok_or(GameError::VrfAccountRequired)?;
+            let randomness = switchboard::RandomnessAccount::parse(&oracle.try_borrow_data()?)?;
+            switchboard::validate_randomness_commit(&randomness, current_slot)?;
+            (randomness.seed_slot, Some(oracle.key()))
+        }
+        RandomnessSource::AdminBeacon => {
+            let beacon = accounts.beacon.ok_or(GameError::RandomnessBeaconRequired)?;
+            (beacon::next_round(beacon)?, None)
+        }
+    };
+    
+    Ok(Commitment {
+        source,
+        commit_slot,
+        source_commit,
+        vrf_account,
+    })
+}
+
+/// Reveal a pack's random value from its recorded source and the SlotHashes sysvar
+pub fn reveal(pack: &SeedPack, accounts: &SourceAccounts, slot_hashes_data: &[u8]) -> Result<u64> {
+    let external = match pack.randomness_source {
+        RandomnessSource::SlotHash => None,
+        RandomnessSource::Oracle => {
+            let oracle = accounts.oracle.ok_or(GameError::VrfAccountRequired)?;
+            require!(pack.vrf_account == Some(oracle.key()), GameError::InvalidVrfAccount);
+            let randomness = switchboard::RandomnessAccount::parse(&oracle.try_borrow_data()?)?;
+            Some(switchboard::validate_randomness_reveal(&randomness, pack.source_commit)?)
+        }
+        RandomnessSource::AdminBeacon => {
+            let beacon = accounts.beacon.ok_or(GameError::RandomnessBeaconRequired)?;
+            Some(beacon::validate_beacon_reveal(beacon, pack.source_commit)?)
+        }
+    };
+    
+    let slot_hash = find_slot_hash(slot_hashes_data, pack.commit_slot)?;
+    let sources: Vec<&[u8; 32]> = external.iter().chain([&slot_hash]).collect();
+    
+    Ok(derive_pack_random(
+        &sources,
+        pack.user_entropy_seed.unwrap_or(0),
+        &pack.owner,
+        pack.pack_id,
+    ))
+}
+
+/// Combine revealed randomness sources with the buyer's entropy into the pack's random value
+/// Sources are the external value (if any) and the committed slot hash, hashed in order;
+/// owner and pack ID keep packs committed to the same sources independent
+pub fn derive_pack_random(
+    sources: &[&[u8; 32]],
+    user_entropy_seed: u64,
+    owner: &Pubkey,
+    pack_id: u64,
+) -> u64 {
+    let entropy_bytes = user_entropy_seed.to_le_bytes();
+    let pack_id_bytes = pack_id.to_le_bytes();
+    let mut inputs: Vec<&[u8]> = sources.iter().map(|source| source.as_slice()).collect();
+    inputs.extend_from_slice(&[&entropy_bytes, owner.as_ref(), &pack_id_bytes]);
+    let digest = hashv(&inputs);
+    let mut value_bytes = [0u8; 8];
+    value_bytes.copy_from_slice(&digest.to_bytes()[..8]);
+    
+    // Ensure non-zero result
+    match u64::from_le_bytes(value_bytes) {
+        0 => 1,
+        value => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::AccountSerialize;
+    use switchboard_stub::RandomnessAccountData;
+    
+    /// Raw SlotHashes data with a single entry
+    fn slot_hashes(slot: u64, fill: u8) -> Vec<u8> {
+        let mut data = 1u64.to_le_bytes().to_vec();
+        data.extend_from_slice(&slot.to_le_bytes());
+        data.extend_from_slice(&[fill; 32]);
+        data
+    }
+    
+    /// Randomness account data as written by the local stand-in program
+    fn oracle_data(seed_slot: u64, reveal_slot: u64, value: [u8; 32]) -> Vec<u8> {
+        let account = RandomnessAccountData {
+            authority: Pubkey::new_unique(),
+            queue: Pubkey::new_unique(),
+            seed_slothash: [0; 32],
+            seed_slot,
+            oracle: Pubkey::new_unique(),
+            reveal_slot,
+            value,
+            ebuf: [0; 224],
+        };
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        data
+    }
+    
+    fn beacon(round: u64, fill: u8) -> RandomnessBeacon {
+        RandomnessBeacon {
+            round,
+            value: [fill; 32],
+            published_at: 0,
+            reserve: [0; 16],
+        }
+    }
+    
+    fn pack(commitment: &Commitment) -> SeedPack {
+        SeedPack {
+            pack_id: 1,
+            owner: Pubkey::new_unique(),
+            quantity: 1,
+            is_opened: false,
+            table_version: 1,
+            vrf_request: None,
+            random_seed: None,
+            purchased_at: 0,
+            opened_at: None,
+            cost_paid: 0,
+            vrf_fee_paid: 0,
+            vrf_sequence: None,
+            user_entropy_seed: Some(42),
+            final_random_value: None,
+            vrf_account: commitment.vrf_account,
+            commit_slot: commitment.commit_slot,
+            version: SeedPack::CURRENT_VERSION,
+            source_commit: commitment.source_commit,
+            randomness_source: commitment.source,
+            reserve: [0; 6],
+        }
+    }
+    
+    #[test]
+    fn test_slot_hash_source() {
+        let commitment = commit(RandomnessSource::SlotHash, &SourceAccounts::default(), 100).unwrap();
+        assert_eq!(commitment.source_commit, 0);
+        assert_eq!(commitment.vrf_account, None);
+        
+        let pack = pack(&commitment);
+        let value = reveal(&pack, &SourceAccounts::default(), &slot_hashes(commitment.commit_slot, 7)).unwrap();
+        assert_eq!(value, derive_pack_random(&[&[7; 32]], 42, &pack.owner, 1));
+        
+        // Commit slot not in SlotHashes yet
+        assert!(reveal(&pack, &SourceAccounts::default(), &slot_hashes(commitment.commit_slot - 1, 7)).is_err());
+    }
+    
+    #[test]
+    fn test_oracle_source() {
+        let key = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = oracle_data(99, 0, [0; 32]);
+        let info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+        let accounts = SourceAccounts { oracle: Some(&info), beacon: None };
+        
+        // Oracle account is mandatory for the oracle source
+        assert!(commit(RandomnessSource::Oracle, &SourceAccounts::default(), 100).is_err());
+        
+        let commitment = commit(RandomnessSource::Oracle, &accounts, 100).unwrap();
+        assert_eq!(commitment.source_commit, 99);
+        assert_eq!(commitment.vrf_account, Some(key));
+        
+        // Not revealed yet
+        let pack = pack(&commitment);
+        let hashes = slot_hashes(commitment.commit_slot, 7);
+        assert!(reveal(&pack, &accounts, &hashes).is_err());
+        
+        // Revealed: oracle value and slot hash are both mixed in
+        info.try_borrow_mut_data().unwrap().copy_from_slice(&oracle_data(99, 101, [9; 32]));
+        let value = reveal(&pack, &accounts, &hashes).unwrap();
+        assert_eq!(value, derive_pack_random(&[&[9; 32], &[7; 32]], 42, &pack.owner, 1));
+        
+        // A different account than the one recorded at purchase
+        let mut other = pack;
+        other.vrf_account = Some(Pubkey::new_unique());
+        assert!(reveal(&other, &accounts, &hashes).is_err());
+    }
+    
+    #[test]
+    fn test_admin_beacon_source() {
+        assert!(commit(RandomnessSource::AdminBeacon, &SourceAccounts::default(), 100).is_err());
+        
+        let published = beacon(4, 0);
+        let accounts = SourceAccounts { oracle: None, beacon: Some(&published) };
+        let commitment = commit(RandomnessSource::AdminBeacon, &accounts, 100).unwrap();
+        assert_eq!(commitment.source_commit, 5);
+        
+        // Committed round not published yet
+        let pack = pack(&commitment);
+        let hashes = slot_hashes(commitment.commit_slot, 7);
+        assert!(reveal(&pack, &accounts, &hashes).is_err());
+        
+        let next = beacon(5, 3);
+        let value = reveal(&pack, &SourceAccounts { oracle: None, beacon: Some(&next) }, &hashes).unwrap();
+        assert_eq!(value, derive_pack_random(&[&[3; 32], &[7; 32]], 42, &pack.owner, 1));
+    }
+    
+    #[test]
+    fn test_pack_random() {
+        let owner = Pubkey::new_unique();
+        let oracle = [9u8; 32];
+        let hash = [7u8; 32];
+        let value = derive_pack_random(&[&oracle, &hash], 42, &owner, 0);
+        
+        // Deterministic for the same inputs
+        assert_eq!(value, derive_pack_random(&[&oracle, &hash], 42, &owner, 0));
+        // Every input changes the result
+        assert_ne!(value, derive_pack_random(&[&[8u8; 32], &hash], 42, &owner, 0));
+        assert_ne!(value, derive_pack_random(&[&oracle, &[8u8; 32]], 42, &owner, 0));
+        assert_ne!(value, derive_pack_random(&[&oracle, &hash], 43, &owner, 0));
+        assert_ne!(value, derive_pack_random(&[&oracle, &hash], 42, &Pubkey::new_unique(), 0));
+        assert_ne!(value, derive_pack_random(&[&oracle, &hash], 42, &owner, 1));
+        // Both sources contribute
+        assert_ne!(value, derive_pack_random(&[&hash], 42, &owner, 0));
+    }
+}
@@ -0,0 +1,143 @@
+use anchor_lang::prelude::*;
+use crate::constants::{SEED_PACK_COMMIT_DELAY_SLOTS, SLOT_HASHES_MAX_ENTRIES};
+use crate::error::GameError;
+
+// ===== SLOTHASHES COMMIT-REVEAL =====
+// purchase_seed_pack commits to a future slot; open_seed_pack reveals with the
+// hash of the first block at or after that slot, read from the SlotHashes sysvar.
+// The hash is unknown to everyone (including the buyer) at purchase time, and
+// packs cannot be re-rolled by waiting: once the hash leaves SlotHashes the
+// owner can no longer open the pack. Only the admin can re-commit an expired
+// pack to a new future slot (recommit_seed_pack), so a buyer who saw an
+// unfavourable hash cannot reroll on their own.
+// Residual trust: the leader of the committed slot can withhold its block,
+// which moves the reveal to the next produced block but cannot pick a hash.
+
+/// Size of one SlotHashes entry: slot (u64) + hash (32 bytes)
+const SLOT_HASH_ENTRY_LEN: usize = 8 + 32;
+
+/// Calculate the slot a newly purchased pack commits to
+pub fn calculate_commit_slot(current_slot: u64) -> Result<u64> {
+    current_slot
+        .checked_add(SEED_PACK_COMMIT_DELAY_SLOTS)
+        .ok_or(GameError::CalculationOverflow.into())
+}
+
+/// Read one (slot, hash) entry from raw SlotHashes data
+fn slot_hash_entry(data: &[u8], index: usize) -> (u64, [u8; 32]) {
+    let start = 8 + index * SLOT_HASH_ENTRY_LEN;
+    let mut slot = [0u8; 8];
+    slot.copy_from_slice(&data[start..start + 8]);
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&data[start + 8..start + SLOT_HASH_ENTRY_LEN]);
+    (u64::from_le_bytes(slot), hash)
+}
+
+/// Find the hash of the first block at or after `commit_slot`
+/// `data` is the raw SlotHashes sysvar: a u64 entry count followed by
+/// (slot, hash) entries ordered newest first
+pub fn find_slot_hash(data: &[u8], commit_slot: u64) -> Result<[u8; 32]> {
+    require!(data.len() >= 8, GameError::InvalidSlotHashes);
+    let mut count_bytes = [0u8; 8];
+    count_bytes.copy_from_slice(&data[..8]);
+    let count = u64::from_le_bytes(count_bytes) as usize;
+    require!(
+        count <= SLOT_HASHES_MAX_ENTRIES && data.len() >= 8 + count * SLOT_HASH_ENTRY_LEN,
+        GameError::InvalidSlotHashes
+    );
+    require!(count > 0, GameError::RandomnessNotReady);
+    
+    // The committed slot must be finalized into SlotHashes before revealing
+    let (newest_slot, _) = slot_hash_entry(data, 0);
+    require!(newest_slot >= commit_slot, GameError::RandomnessNotReady);
+    
+    // Once the sysvar is full, entries older than the oldest retained slot are gone
+    let (oldest_slot, _) = slot_hash_entry(data, count - 1);
+    require!(
+        oldest_slot <= commit_slot || count < SLOT_HASHES_MAX_ENTRIES,
+        GameError::SlotHashExpired
+    );
+    
+    // Binary search for the oldest entry whose slot is still >= commit_slot
+    let (mut low, mut high) = (0usize, count);
+    while low < high {
+        let mid = (low + high) / 2;
+        if slot_hash_entry(data, mid).0 >= commit_slot {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    
+    Ok(slot_hash_entry(data, low - 1).1)
+}
+
+/// Check that `commit_slot` has passed and its hash has left SlotHashes for good
+pub fn validate_commit_expired(data: &[u8], commit_slot: u64) -> Result<()> {
+    match find_slot_hash(data, commit_slot) {
+        Ok(_) => err!(GameError::CommitNotExpired),
+        Err(e) if e == GameError::SlotHashExpired.into() => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    /// Build raw SlotHashes data from (slot, hash byte) pairs, newest first
+    fn build_slot_hashes(entries: &[(u64, u8)]) -> Vec<u8> {
+        let mut data = (entries.len() as u64).to_le_bytes().to_vec();
+        for (slot, fill) in entries {
+            data.extend_from_slice(&slot.to_le_bytes());
+            data.extend_from_slice(&[*fill; 32]);
+        }
+        data
+    }
+    
+    #[test]
+    fn test_commit_slot() {
+        assert_eq!(calculate_commit_slot(100).unwrap(), 100 + SEED_PACK_COMMIT_DELAY_SLOTS);
+        assert!(calculate_commit_slot(u64::MAX).is_err());
+    }
+    
+    #[test]
+    fn test_find_slot_hash() {
+        // Slot 103 was skipped by its leader
+        let data = build_slot_hashes(&[(105, 5), (104, 4), (102, 2), (101, 1)]);
+        
+        assert_eq!(find_slot_hash(&data, 102).unwrap(), [2; 32]);
+        assert_eq!(find_slot_hash(&data, 105).unwrap(), [5; 32]);
+        // A skipped commit slot reveals with the next produced block
+        assert_eq!(find_slot_hash(&data, 103).unwrap(), [4; 32]);
+        // Partially filled sysvar still holds the full history
+        assert_eq!(find_slot_hash(&data, 50).unwrap(), [1; 32]);
+        
+        // Commit slot not reached yet
+        assert!(find_slot_hash(&data, 106).is_err());
+        assert!(find_slot_hash(&build_slot_hashes(&[]), 1).is_err());
+        
+        // Malformed data
+        assert!(find_slot_hash(&[0u8; 4], 1).is_err());
+        let mut truncated = data.clone();
+        truncated.truncate(data.len() - 1);
+        assert!(find_slot_hash(&truncated, 102).is_err());
+    }
+    
+    #[test]
+    fn test_find_slot_hash_expired() {
+        let entries: Vec<(u64, u8)> = (0..SLOT_HASHES_MAX_ENTRIES as u64)
+            .map(|i| (2000 - i, (i % 256) as u8))
+            .collect();
+        let data = build_slot_hashes(&entries);
+        let oldest_slot = 2000 - (SLOT_HASHES_MAX_ENTRIES as u64 - 1);
+        
+        assert!(find_slot_hash(&data, oldest_slot).is_ok());
+        assert!(find_slot_hash(&data, oldest_slot - 1).is_err());
+        
+        // Only an expired commit may be re-committed
+        assert!(validate_commit_expired(&data, oldest_slot - 1).is_ok());
+        assert!(validate_commit_expired(&data, oldest_slot).is_err());
+        assert!(validate_commit_expired(&data, 2001).is_err());
+    }
+}
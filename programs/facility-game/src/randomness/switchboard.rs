@@ -0,0 +1,174 @@
+use anchor_lang::prelude::*;
+use crate::constants::ORACLE_COMMIT_MAX_AGE_SLOTS;
+use crate::error::GameError;
+
+// ===== SWITCHBOARD ON-DEMAND RANDOMNESS =====
+// Manual parsing of Switchboard On-Demand `RandomnessAccountData` (avoiding
+// SDK dependency conflicts with Anchor 0.31.1 + SPL Token 2022 v6.0.0).
+// Flow: the client commits a randomness account in the slot before
+// purchase_seed_pack, which records its seed slot; the oracle reveals after
+// purchase, and open_seed_pack only accepts the value revealed for that commit.
+// Owner checks against `Config.oracle_program` happen in the account contexts.
+
+/// Anchor discriminator of `RandomnessAccountData` (sha256("account:RandomnessAccountData")[..8])
+pub const RANDOMNESS_ACCOUNT_DISCRIMINATOR: [u8; 8] = [10, 66, 229, 135, 220, 239, 217, 114];
+
+/// Parsed Switchboard On-Demand randomness account
+/// Field order and sizes match the on-chain layout (trailing padding is ignored)
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct RandomnessAccount {
+    /// Authority allowed to commit this account
+    pub authority: Pubkey,
+    /// Oracle queue serving this account
+    pub queue: Pubkey,
+    /// Slot hash the commit was seeded with
+    pub seed_slothash: [u8; 32],
+    /// Slot of the latest commit
+    pub seed_slot: u64,
+    /// Oracle assigned to reveal
+    pub oracle: Pubkey,
+    /// Slot the value was revealed in (0 until revealed)
+    pub reveal_slot: u64,
+    /// Revealed randomness
+    pub value: [u8; 32],
+}
+
+impl RandomnessAccount {
+    /// Minimum account size: discriminator + parsed fields
+    pub const MIN_LEN: usize = 8 + // discriminator
+        32 + // authority
+        32 + // queue
+        32 + // seed_slothash
+        8 + // seed_slot
+        32 + // oracle
+        8 + // reveal_slot
+        32; // value
+    
+    /// Parse raw account data, checking discriminator and size
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        require!(data.len() >= Self::MIN_LEN, GameError::InvalidVrfAccount);
+        require!(data[..8] == RANDOMNESS_ACCOUNT_DISCRIMINATOR, GameError::InvalidVrfAccount);
+        
+        let mut offset = 8;
+        let mut take = |len: usize| {
+            let bytes = &data[offset..offset + len];
+            offset += len;
+            bytes
+        };
+        let pubkey = |bytes: &[u8]| Pubkey::new_from_array(bytes.try_into().unwrap());
+        let array = |bytes: &[u8]| -> [u8; 32] { bytes.try_into().unwrap() };
+        let slot = |bytes: &[u8]| u64::from_le_bytes(bytes.try_into().unwrap());
+        
+        Ok(Self {
+            authority: pubkey(take(32)),
+            queue: pubkey(take(32)),
+            seed_slothash: array(take(32)),
+            seed_slot: slot(take(8)),
+            oracle: pubkey(take(32)),
+            reveal_slot: slot(take(8)),
+            value: array(take(32)),
+        })
+    }
+    
+    /// Whether the oracle has revealed a value for the current commit
+    pub fn is_revealed(&self) -> bool {
+        self.reveal_slot != 0 && self.reveal_slot >= self.seed_slot
+    }
+}
+
+/// Validate a commit accepted at purchase: recent and not yet revealed
+/// An unrevealed commit means nobody (including the buyer) knows the value yet
+pub fn validate_randomness_commit(randomness: &RandomnessAccount, current_slot: u64) -> Result<()> {
+    require!(
+        randomness.seed_slot < current_slot
+            && current_slot - randomness.seed_slot <= ORACLE_COMMIT_MAX_AGE_SLOTS,
+        GameError::StaleOracleCommit
+    );
+    require!(!randomness.is_revealed(), GameError::StaleOracleCommit);
+    Ok(())
+}
+
+/// Validate a reveal at open and return the randomness value
+/// The account must still hold the commit recorded at purchase, so it cannot be re-rolled
+pub fn validate_randomness_reveal(randomness: &RandomnessAccount, expected_seed_slot: u64) -> Result<[u8; 32]> {
+    require!(randomness.seed_slot == expected_seed_slot, GameError::OracleSeedSlotMismatch);
+    require!(randomness.is_revealed(), GameError::VrfResultNotAvailable);
+    Ok(randomness.value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::{AccountSerialize, Discriminator};
+    use switchboard_stub::RandomnessAccountData;
+    
+    /// Serialize an account exactly as the local stand-in program writes it
+    fn stub_account(seed_slot: u64, reveal_slot: u64, value: [u8; 32]) -> (RandomnessAccountData, Vec<u8>) {
+        let account = RandomnessAccountData {
+            authority: Pubkey::new_unique(),
+            queue: Pubkey::new_unique(),
+            seed_slothash: [3; 32],
+            seed_slot,
+            oracle: Pubkey::new_unique(),
+            reveal_slot,
+            value,
+            ebuf: [0; 224],
+        };
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        (account, data)
+    }
+    
+    #[test]
+    fn test_parse_stub_layout() {
+        assert_eq!(RandomnessAccountData::DISCRIMINATOR, RANDOMNESS_ACCOUNT_DISCRIMINATOR);
+        
+        let (account, data) = stub_account(100, 102, [9; 32]);
+        let parsed = RandomnessAccount::parse(&data).unwrap();
+        assert_eq!(parsed.authority, account.authority);
+        assert_eq!(parsed.queue, account.queue);
+        assert_eq!(parsed.seed_slothash, account.seed_slothash);
+        assert_eq!(parsed.seed_slot, 100);
+        assert_eq!(parsed.oracle, account.oracle);
+        assert_eq!(parsed.reveal_slot, 102);
+        assert_eq!(parsed.value, [9; 32]);
+        
+        // Wrong discriminator or truncated data
+        let mut wrong = data.clone();
+        wrong[0] ^= 1;
+        assert!(RandomnessAccount::parse(&wrong).is_err());
+        assert!(RandomnessAccount::parse(&data[..RandomnessAccount::MIN_LEN - 1]).is_err());
+    }
+    
+    #[test]
+    fn test_randomness_commit_validation() {
+        let (_, data) = stub_account(100, 0, [0; 32]);
+        let committed = RandomnessAccount::parse(&data).unwrap();
+        
+        assert!(validate_randomness_commit(&committed, 101).is_ok());
+        assert!(validate_randomness_commit(&committed, 100 + ORACLE_COMMIT_MAX_AGE_SLOTS).is_ok());
+        // Same slot, too old
+        assert!(validate_randomness_commit(&committed, 100).is_err());
+        assert!(validate_randomness_commit(&committed, 101 + ORACLE_COMMIT_MAX_AGE_SLOTS).is_err());
+        
+        // Already revealed commits could be read before buying
+        let (_, data) = stub_account(100, 101, [9; 32]);
+        let revealed = RandomnessAccount::parse(&data).unwrap();
+        assert!(validate_randomness_commit(&revealed, 102).is_err());
+    }
+    
+    #[test]
+    fn test_randomness_reveal_validation() {
+        let (_, data) = stub_account(100, 103, [9; 32]);
+        let revealed = RandomnessAccount::parse(&data).unwrap();
+        assert_eq!(validate_randomness_reveal(&revealed, 100).unwrap(), [9; 32]);
+        
+        // Re-committed after purchase
+        assert!(validate_randomness_reveal(&revealed, 99).is_err());
+        
+        // Oracle result missing is a hard failure
+        let (_, data) = stub_account(100, 0, [0; 32]);
+        let pending = RandomnessAccount::parse(&data).unwrap();
+        assert!(validate_randomness_reveal(&pending, 100).is_err());
+    }
+}
@@ -1,7 +1,16 @@
 use anchor_lang::prelude::*;
 
-// VRF専用にするため、RandomnessMethodは削除
-// 全てSwitchboard VRFで統一
+/// Source of the external randomness mixed into seed packs (see randomness/mod.rs)
+/// Every pack also commits to a SlotHashes entry regardless of the source
+#[derive(Clone, Copy, PartialEq, Eq, AnchorSerialize, AnchorDeserialize, Debug)]
+pub enum RandomnessSource {
+    /// SlotHashes commit-reveal only (no oracle or trusted party)
+    SlotHash,
+    /// Switchboard On-Demand randomness account owned by `Config.oracle_program`
+    Oracle,
+    /// Admin-published randomness beacon (`localnet` builds only; the admin controls outcomes)
+    AdminBeacon,
+}
 
 /// Global system configuration
 /// Stores all game parameters and admin settings
@@ -27,7 +36,8 @@ pub struct Config {
     pub farm_space_cost_sol: u64,
     /// Maximum allowed invites per user (default: 5)
     pub max_invite_limit: u8,
-    /// Trading fee as percentage (default: 2%)
+    /// Trading fee as percentage (default: 2%), `transfer_fee_basis_points` rounded down
+    /// Like `transfer_fee_basis_points`, it lags a pending fee update (see `transfer_fee_basis_points_at`)
     pub trading_fee_percentage: u8,
     /// Protocol address that doesn't receive referral rewards
     pub protocol_referral_address: Pubkey,
@@ -35,8 +45,40 @@ pub struct Config {
     pub total_supply_minted: u64,
     /// Operator address with unlimited invite privileges
     pub operator: Pubkey,
-    /// Reserved bytes for future upgrades (reduced from 2 to accommodate total_supply_minted)
-    pub reserve: [u8; 2],
+    /// Account layout version (0 = legacy layout, see `migrate_config`)
+    pub version: u8,
+    /// Global emergency pause flag (blocks all user-facing instructions)
+    pub is_paused: bool,
+    /// Operator-defined reason code for the current pause (0 when not paused)
+    pub pause_reason: u8,
+    /// Timestamp when the system was paused (0 when not paused)
+    pub paused_at: i64,
+    /// Per-subsystem pause bits (see `constants::PAUSE_*`)
+    pub paused_subsystems: u8,
+    /// Proposed admin awaiting acceptance (two-step handover)
+    pub pending_admin: Option<Pubkey>,
+    /// Expiry for the pending admin proposal (0 = no expiry)
+    pub pending_admin_expires_at: i64,
+    /// Proposed operator awaiting acceptance (two-step handover)
+    pub pending_operator: Option<Pubkey>,
+    /// Expiry for the pending operator proposal (0 = no expiry)
+    pub pending_operator_expires_at: i64,
+    /// Delay in seconds before a queued economic config change can execute
+    pub config_change_delay: i64,
+    /// Counter for unique pending config change IDs
+    pub config_change_counter: u64,
+    /// Program that owns accepted oracle randomness accounts (Switchboard On-Demand by default)
+    pub oracle_program: Pubkey,
+    /// Randomness source used for newly purchased seed packs
+    pub randomness_source: RandomnessSource,
+    /// WEED transfer fee in basis points charged before `pending_transfer_fee_epoch` (default: 200)
+    pub transfer_fee_basis_points: u16,
+    /// Fee set by the latest `update_transfer_fee`, in basis points
+    pub pending_transfer_fee_basis_points: u16,
+    /// Epoch from which the mint charges `pending_transfer_fee_basis_points`
+    pub pending_transfer_fee_epoch: u64,
+    /// Reserved bytes for future upgrades
+    pub reserve: [u8; 1],
 }
 
 impl Config {
@@ -56,7 +98,23 @@ impl Config {
         32 + // protocol_referral_address
         8 + // total_supply_minted
         32 + // operator
-        2; // reserve
+        1 + // version
+        1 + // is_paused
+        1 + // pause_reason
+        8 + // paused_at
+        1 + // paused_subsystems
+        (1 + 32) + // pending_admin (Option<Pubkey>)
+        8 + // pending_admin_expires_at
+        (1 + 32) + // pending_operator (Option<Pubkey>)
+        8 + // pending_operator_expires_at
+        8 + // config_change_delay
+        8 + // config_change_counter
+        32 + // oracle_program
+        1 + // randomness_source (enum as u8)
+        2 + // transfer_fee_basis_points
+        2 + // pending_transfer_fee_basis_points
+        8 + // pending_transfer_fee_epoch
+        1; // reserve
 
     /// Default base rate for reward calculations
     pub const DEFAULT_BASE_RATE: u64 = crate::constants::DEFAULT_BASE_RATE;
@@ -66,6 +124,43 @@ impl Config {
     
     /// Default farm space cost in lamports (0.5 SOL)
     pub const DEFAULT_FARM_SPACE_COST: u64 = crate::constants::FARM_SPACE_COST_SOL;
+
+    /// Current account layout version
+    /// v1: pause flags, authority handover, timelock fields
+    /// v2: oracle_program
+    /// v3: randomness_source
+    /// v4: transfer_fee_basis_points
+    /// v5: pending transfer fee and the epoch it takes effect
+    pub const CURRENT_VERSION: u8 = 5;
+
+    /// Check whether any of the given subsystem pause bits are set
+    pub fn is_subsystem_paused(&self, flags: u8) -> bool {
+        self.paused_subsystems & flags != 0
+    }
+
+    /// WEED transfer fee in basis points that the mint charges during `epoch`
+    pub fn transfer_fee_basis_points_at(&self, epoch: u64) -> u16 {
+        if epoch >= self.pending_transfer_fee_epoch {
+            self.pending_transfer_fee_basis_points
+        } else {
+            self.transfer_fee_basis_points
+        }
+    }
+
+    /// Record a fee set on the mint during `current_epoch`
+    /// Mirrors Token 2022's older/newer fee pair: a pending fee already in effect becomes
+    /// the current fee, and the new one takes effect `TRANSFER_FEE_EPOCH_DELAY` epochs later
+    pub fn schedule_transfer_fee(&mut self, basis_points: u16, current_epoch: u64) -> Result<()> {
+        use crate::constants::{PERCENTAGE_TO_BASIS_POINTS, TRANSFER_FEE_EPOCH_DELAY};
+        
+        self.transfer_fee_basis_points = self.transfer_fee_basis_points_at(current_epoch);
+        self.trading_fee_percentage = (self.transfer_fee_basis_points / PERCENTAGE_TO_BASIS_POINTS) as u8;
+        self.pending_transfer_fee_basis_points = basis_points;
+        self.pending_transfer_fee_epoch = current_epoch
+            .checked_add(TRANSFER_FEE_EPOCH_DELAY)
+            .ok_or(crate::error::GameError::CalculationOverflow)?;
+        Ok(())
+    }
 }
 
 /// Individual user account state
@@ -86,8 +181,14 @@ pub struct UserState {
     pub pending_referral_rewards: u64,
     /// Total number of seed packs purchased by this user (for farm auto-upgrade)
     pub total_packs_purchased: u32,
-    /// Reserved bytes for future features (reduced from 32 to 28 to accommodate total_packs_purchased)
-    pub reserve: [u8; 28],
+    /// Account layout version (0 = legacy layout, see `migrate_user_state`)
+    pub version: u8,
+    /// total_grow_power × acc_reward_per_grow_power at the last checkpoint or claim (scaled)
+    pub reward_debt: u128,
+    /// Farming rewards settled on grow power changes, paid out by the next claim
+    pub pending_farming_rewards: u64,
+    /// Reserved bytes for future features (reduced from 27 to 3 for reward_debt, pending_farming_rewards)
+    pub reserve: [u8; 3],
 }
 
 /// Farm space account for seed cultivation
@@ -104,8 +205,10 @@ pub struct FarmSpace {
     pub seed_count: u8,
     /// Combined grow power of all planted seeds
     pub total_grow_power: u64,
-    /// Reserved bytes for future expansion
-    pub reserve: [u8; 32],
+    /// Account layout version (0 = legacy layout, see `migrate_farm_space`)
+    pub version: u8,
+    /// Reserved bytes for future expansion (reduced from 32 to 31 to accommodate version)
+    pub reserve: [u8; 31],
 }
 
 /// Seed types with dynamic grow power and probabilities
@@ -376,34 +479,6 @@ impl ProbabilityTable {
     }
 }
 
-impl Config {
-    pub const LEN: usize = 8 + // discriminator
-        8 + // base_rate
-        8 + // halving_interval
-        8 + // next_halving_time
-        32 + // admin
-        32 + // treasury
-        8 + // seed_pack_cost
-        8 + // seed_counter
-        8 + // seed_pack_counter
-        8 + // farm_space_cost_sol
-        1 + // max_invite_limit
-        1 + // trading_fee_percentage
-        32 + // protocol_referral_address
-        8 + // total_supply_minted
-        32 + // operator
-        2; // reserve (kept for backward compatibility)
-        
-    /// Default farm space cost (0.5 SOL in lamports)
-    pub const DEFAULT_FARM_SPACE_COST: u64 = 500_000_000; // 0.5 SOL
-    
-    /// Default base rate (100 WEED per second)
-    pub const DEFAULT_BASE_RATE: u64 = 100;
-    
-    /// Default halving interval (200 seconds)
-    pub const DEFAULT_HALVING_INTERVAL: i64 = 200;
-}
-
 /// User's seed inventory management
 /// Tracks all seeds owned by a user with type-based limits (16 seed types)
 #[account]
@@ -429,7 +504,11 @@ impl FarmSpace {
         1 + // capacity
         1 + // seed_count
         8 + // total_grow_power
-        32; // reserve
+        1 + // version
+        31; // reserve
+
+    /// Current account layout version
+    pub const CURRENT_VERSION: u8 = 1;
         
     /// Get capacity for a given level
     pub fn get_capacity_for_level(level: u8) -> u8 {
@@ -445,7 +524,7 @@ impl SeedStorage {
     pub const LEN: usize = 8 + // discriminator
         32 + // owner
         4 + (8 * 2_000) + // seed_ids (Vec<u64> with max 2,000 seeds)
-        4 + (1 * 2_000) + // seed_types (Vec<SeedType> with max 2,000 entries)
+        4 + 2_000 + // seed_types (Vec<SeedType> with max 2,000 entries)
         4 + // total_seeds (u32 for 2,000+ seeds)
         (2 * 16) + // seed_type_counts (16 x u16)
         8; // reserve
@@ -685,14 +764,34 @@ pub struct GlobalStats {
     pub total_grow_power: u64,
     /// Total number of farm spaces created
     pub total_farm_spaces: u64,
-    /// Total WEED tokens minted
+    /// Circulating WEED supply (minted minus burned)
     pub total_supply: u64,
-    /// Current effective rewards per second (after halving)
+    /// Emission rate of the active schedule segment, refreshed on every accumulator update
     pub current_rewards_per_second: u64,
     /// Last time statistics were updated
     pub last_update_time: i64,
-    /// Reserved for future expansion
-    pub reserve: [u8; 32],
+    /// Account layout version (0 = legacy layout, see `migrate_global_stats`)
+    pub version: u8,
+    /// Farming reward per unit of grow power since launch (scaled by ACC_REWARD_PRECISION)
+    pub acc_reward_per_grow_power: u128,
+    /// Time the accumulator was last advanced (0 = not started yet)
+    pub last_reward_time: i64,
+    /// Set once emission reaches TOTAL_WEED_SUPPLY (or it is all minted); emission then stops and
+    /// fees moved into the fee pool vault are shared through the accumulator instead
+    pub supply_exhausted: bool,
+    /// Emission credited through the accumulator; capped at TOTAL_WEED_SUPPLY so every
+    /// accrued reward can still be minted
+    pub total_emission_accrued: u64,
+    /// Total WEED burned by seed pack purchases
+    pub total_burned: u64,
+    /// Total seed packs sold
+    pub total_packs_sold: u64,
+    /// Total seeds generated (pack openings and starter seeds)
+    pub total_seeds_generated: u64,
+    /// Total SOL collected in lamports (farm space purchases and VRF fees)
+    pub total_sol_collected: u64,
+    /// Reserved for future expansion (reduced from 31 to 6 for the reward accumulator, supply_exhausted)
+    pub reserve: [u8; 6],
 }
 
 impl GlobalStats {
@@ -702,23 +801,41 @@ impl GlobalStats {
         8 + // total_supply
         8 + // current_rewards_per_second
         8 + // last_update_time
-        32; // reserve
+        1 + // version
+        16 + // acc_reward_per_grow_power
+        8 + // last_reward_time
+        1 + // supply_exhausted
+        8 + // total_emission_accrued
+        8 + // total_burned
+        8 + // total_packs_sold
+        8 + // total_seeds_generated
+        8 + // total_sol_collected
+        6; // reserve
         
     /// Initial total supply (placeholder value)
     pub const INITIAL_TOTAL_SUPPLY: u64 = 0;
+
+    /// Current account layout version
+    pub const CURRENT_VERSION: u8 = 5;
 }
 
 /// Fee pool for collecting and managing trading fees
 #[account]
 pub struct FeePool {
-    /// Accumulated fees in lamports
+    /// SOL fees (seed pack VRF fees) held by the pool, in lamports
+    /// WEED transfer fees are counted separately in `accumulated_token_fees`
     pub accumulated_fees: u64,
     /// Treasury address for fee withdrawal
     pub treasury_address: Pubkey,
     /// Last time fees were collected
     pub last_collection_time: i64,
-    /// Reserved for future expansion
-    pub reserve: [u8; 48],
+    /// Account layout version (0 = legacy layout, see `migrate_fee_pool`)
+    pub version: u8,
+    /// Total WEED transfer fees withdrawn from the mint to the treasury or fee pool vault,
+    /// in WEED base units (never mixed into the lamport `accumulated_fees`)
+    pub accumulated_token_fees: u64,
+    /// Reserved for future expansion (reduced from 47 to 39 for accumulated_token_fees)
+    pub reserve: [u8; 39],
 }
 
 impl FeePool {
@@ -726,7 +843,12 @@ impl FeePool {
         8 + // accumulated_fees
         32 + // treasury_address
         8 + // last_collection_time
-        48; // reserve
+        1 + // version
+        8 + // accumulated_token_fees
+        39; // reserve
+
+    /// Current account layout version
+    pub const CURRENT_VERSION: u8 = 2;
 }
 
 /// Individual seed account for planted seeds
@@ -873,8 +995,16 @@ pub struct SeedPack {
     pub final_random_value: Option<u64>,
     /// VRF account used for randomness
     pub vrf_account: Option<Pubkey>,
-    /// Reserved for future expansion
-    pub reserve: [u8; 8],
+    /// Slot whose hash seeds this pack (committed at purchase, revealed at open)
+    pub commit_slot: u64,
+    /// Account layout version (0 = legacy layout, see `migrate_seed_pack`)
+    pub version: u8,
+    /// Source-specific commitment: oracle seed slot or beacon round (0 for SlotHash)
+    pub source_commit: u64,
+    /// Randomness source that produced this pack (fixed at purchase)
+    pub randomness_source: RandomnessSource,
+    /// Reserved for future expansion (reduced from 8 to 6 to accommodate version and randomness_source)
+    pub reserve: [u8; 6],
 }
 
 impl SeedPack {
@@ -894,7 +1024,15 @@ impl SeedPack {
         (1 + 8) + // user_entropy_seed (Option<u64>)
         (1 + 8) + // final_random_value (Option<u64>)
         (1 + 32) + // vrf_account (Option<Pubkey>)
-        8; // reserve
+        8 + // commit_slot
+        1 + // version
+        8 + // source_commit
+        1 + // randomness_source (enum as u8)
+        6; // reserve
+
+    /// Current account layout version
+    /// v1: SlotHashes commit slot, per-pack randomness source and its commitment
+    pub const CURRENT_VERSION: u8 = 1;
 }
 
 impl UserState {
@@ -906,7 +1044,13 @@ impl UserState {
         (1 + 32) + // referrer (Option<Pubkey>)
         8 + // pending_referral_rewards
         4 + // total_packs_purchased
-        28; // reserve
+        1 + // version
+        16 + // reward_debt
+        8 + // pending_farming_rewards
+        3; // reserve
+
+    /// Current account layout version
+    pub const CURRENT_VERSION: u8 = 2;
 }
 
 impl SeedType {
@@ -1030,3 +1174,315 @@ impl SeedType {
         }
     }
 }
+
+/// Economic parameter change that must pass through the timelock
+/// Stored inline in a fixed-size account, so the large variant is not boxed
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, PartialEq, AnchorSerialize, AnchorDeserialize, Debug)]
+pub enum ConfigChange {
+    /// New seed pack cost in WEED base units
+    SeedPackCost { new_cost: u64 },
+    /// New farm space cost in lamports
+    FarmSpaceCost { new_cost: u64 },
+    /// New base reward rate
+    BaseRate { new_base_rate: u64 },
+    /// New halving interval in seconds
+    HalvingInterval { new_halving_interval: i64 },
+    /// Full probability table replacement
+    ProbabilityTable {
+        version: u32,
+        seed_count: u8,
+        grow_powers: [u64; 16],
+        probability_thresholds: [u16; 16],
+        probability_percentages: [f32; 16],
+        expected_value: u64,
+        name: [u8; 32],
+    },
+    /// New timelock delay (the delay change itself is timelocked)
+    TimelockDelay { new_delay: i64 },
+    /// New owner program for oracle randomness accounts
+    OracleProgram { program_id: Pubkey },
+    /// New randomness source for seed packs purchased afterwards
+    RandomnessSource { source: RandomnessSource },
+    /// Full emission schedule replacement (first `segment_count` segments are used)
+    EmissionSchedule {
+        segment_count: u8,
+        segments: [EmissionSegment; crate::constants::MAX_EMISSION_SEGMENTS],
+    },
+}
+
+impl ConfigChange {
+    /// Serialized size of the largest variant (ProbabilityTable)
+    pub const MAX_SIZE: usize = 1 + // variant tag
+        4 + // version
+        1 + // seed_count
+        8 * 16 + // grow_powers
+        2 * 16 + // probability_thresholds
+        4 * 16 + // probability_percentages
+        8 + // expected_value
+        32; // name
+}
+
+/// Queued economic config change awaiting its timelock
+/// PDA: ["pending_config_change", change_id]
+#[account]
+pub struct PendingConfigChange {
+    /// Unique change ID (from Config.config_change_counter)
+    pub change_id: u64,
+    /// Authority who queued the change
+    pub proposer: Pubkey,
+    /// The change to apply
+    pub change: ConfigChange,
+    /// When the change was queued
+    pub queued_at: i64,
+    /// Earliest time the change can be executed
+    pub executable_at: i64,
+    /// Reserved for future expansion
+    pub reserve: [u8; 16],
+}
+
+impl PendingConfigChange {
+    pub const LEN: usize = 8 + // discriminator
+        8 + // change_id
+        32 + // proposer
+        ConfigChange::MAX_SIZE + // change
+        8 + // queued_at
+        8 + // executable_at
+        16; // reserve
+}
+
+/// Single role registry entry
+#[derive(Clone, Copy, Default, PartialEq, AnchorSerialize, AnchorDeserialize, Debug)]
+pub struct RoleMember {
+    /// Member address
+    pub authority: Pubkey,
+    /// Role bitmask (see `constants::ROLE_*`)
+    pub roles: u8,
+}
+
+/// Delegated roles beyond admin/operator
+/// PDA: ["role_registry"]
+#[account]
+pub struct RoleRegistry {
+    /// Members and their role bits (first `member_count` entries are valid)
+    pub members: [RoleMember; crate::constants::MAX_ROLE_MEMBERS],
+    /// Number of active members
+    pub member_count: u8,
+    /// Reserved for future expansion
+    pub reserve: [u8; 32],
+}
+
+impl RoleRegistry {
+    pub const LEN: usize = 8 + // discriminator
+        (32 + 1) * crate::constants::MAX_ROLE_MEMBERS + // members
+        1 + // member_count
+        32; // reserve
+
+    /// Role bits held by an address (0 if not a member)
+    pub fn roles_of(&self, authority: &Pubkey) -> u8 {
+        self.members[..self.member_count as usize]
+            .iter()
+            .find(|m| m.authority == *authority)
+            .map(|m| m.roles)
+            .unwrap_or(0)
+    }
+
+    /// Check whether an address holds any of the given role bits
+    pub fn has_role(&self, authority: &Pubkey, role: u8) -> bool {
+        self.roles_of(authority) & role != 0
+    }
+
+    /// Add role bits to a member, inserting it if needed
+    /// Returns the member's resulting roles, or None if the registry is full
+    pub fn grant(&mut self, authority: Pubkey, roles: u8) -> Option<u8> {
+        let count = self.member_count as usize;
+        if let Some(member) = self.members[..count].iter_mut().find(|m| m.authority == authority) {
+            member.roles |= roles;
+            return Some(member.roles);
+        }
+        if count >= self.members.len() {
+            return None;
+        }
+        self.members[count] = RoleMember { authority, roles };
+        self.member_count += 1;
+        Some(roles)
+    }
+
+    /// Remove role bits from a member, dropping it once no roles remain
+    /// Returns the member's resulting roles, or None if not a member
+    pub fn revoke(&mut self, authority: &Pubkey, roles: u8) -> Option<u8> {
+        let count = self.member_count as usize;
+        let index = self.members[..count].iter().position(|m| m.authority == *authority)?;
+        self.members[index].roles &= !roles;
+        let remaining = self.members[index].roles;
+        if remaining == 0 {
+            // Swap-remove to keep active members contiguous
+            self.members[index] = self.members[count - 1];
+            self.members[count - 1] = RoleMember::default();
+            self.member_count -= 1;
+        }
+        Some(remaining)
+    }
+}
+
+/// Built-in M-of-N multisig for admin actions
+/// PDA: ["multisig"]; its companion ["multisig_signer"] PDA is the key handed `Config.admin`
+#[account]
+pub struct Multisig {
+    /// Member addresses (first `signer_count` entries are valid)
+    pub signers: [Pubkey; crate::constants::MAX_MULTISIG_SIGNERS],
+    /// Number of active members
+    pub signer_count: u8,
+    /// Approvals required to execute a proposal
+    pub threshold: u8,
+    /// Counter for unique proposal IDs
+    pub proposal_counter: u64,
+    /// Incremented on membership changes; invalidates open proposals
+    pub owner_set_seqno: u32,
+    /// Reserved for future expansion
+    pub reserve: [u8; 32],
+}
+
+impl Multisig {
+    pub const LEN: usize = 8 + // discriminator
+        32 * crate::constants::MAX_MULTISIG_SIGNERS + // signers
+        1 + // signer_count
+        1 + // threshold
+        8 + // proposal_counter
+        4 + // owner_set_seqno
+        32; // reserve
+
+    /// Index of a member in the signer list
+    pub fn signer_index(&self, key: &Pubkey) -> Option<usize> {
+        self.signers[..self.signer_count as usize]
+            .iter()
+            .position(|s| s == key)
+    }
+}
+
+/// Account reference stored in a proposal
+#[derive(Clone, Copy, PartialEq, AnchorSerialize, AnchorDeserialize, Debug)]
+pub struct ProposalAccount {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+impl ProposalAccount {
+    pub const LEN: usize = 32 + 1 + 1;
+}
+
+/// Pending multisig proposal holding a serialized admin instruction for this program
+/// PDA: ["proposal", proposal_id]
+#[account]
+pub struct Proposal {
+    /// Unique proposal ID (from Multisig.proposal_counter)
+    pub proposal_id: u64,
+    /// Member who created the proposal
+    pub proposer: Pubkey,
+    /// Accounts for the wrapped instruction
+    pub accounts: Vec<ProposalAccount>,
+    /// Serialized instruction data (discriminator + args)
+    pub data: Vec<u8>,
+    /// Approval bitmask indexed by Multisig.signers position
+    pub approvals: u16,
+    /// Multisig membership sequence number at creation time
+    pub owner_set_seqno: u32,
+    /// Whether the proposal has been executed
+    pub executed: bool,
+    /// Creation timestamp
+    pub created_at: i64,
+    /// Execution timestamp (0 until executed)
+    pub executed_at: i64,
+    /// Reserved for future expansion
+    pub reserve: [u8; 16],
+}
+
+impl Proposal {
+    /// Account size for a proposal with the given instruction shape
+    pub fn space(account_count: usize, data_len: usize) -> usize {
+        8 + // discriminator
+        8 + // proposal_id
+        32 + // proposer
+        4 + ProposalAccount::LEN * account_count + // accounts
+        4 + data_len + // data
+        2 + // approvals
+        4 + // owner_set_seqno
+        1 + // executed
+        8 + // created_at
+        8 + // executed_at
+        16 // reserve
+    }
+
+    /// Number of approvals recorded
+    pub fn approval_count(&self) -> u32 {
+        self.approvals.count_ones()
+    }
+}
+
+/// Admin-published randomness for test clusters (`RandomnessSource::AdminBeacon`)
+/// PDA: ["randomness_beacon"]
+#[account]
+pub struct RandomnessBeacon {
+    /// Latest published round (0 = nothing published yet)
+    pub round: u64,
+    /// Value published for `round`
+    pub value: [u8; 32],
+    /// When `round` was published
+    pub published_at: i64,
+    /// Reserved for future expansion
+    pub reserve: [u8; 16],
+}
+
+impl RandomnessBeacon {
+    pub const LEN: usize = 8 + // discriminator
+        8 + // round
+        32 + // value
+        8 + // published_at
+        16; // reserve
+}
+
+/// Emission rate from `start_time` until the next segment starts
+#[derive(Clone, Copy, Default, PartialEq, AnchorSerialize, AnchorDeserialize, Debug)]
+pub struct EmissionSegment {
+    /// Unix timestamp the segment starts at
+    pub start_time: i64,
+    /// Emission per second while the segment is active
+    pub rate: u64,
+}
+
+/// Piecewise farming emission schedule (replaces the fixed halving)
+/// PDA: ["emission_schedule"]
+#[account]
+pub struct EmissionSchedule {
+    /// Segments in ascending `start_time` order (first `segment_count` entries are valid)
+    /// Nothing is emitted before the first segment; the last one runs indefinitely
+    pub segments: [EmissionSegment; crate::constants::MAX_EMISSION_SEGMENTS],
+    /// Number of active segments
+    pub segment_count: u8,
+    /// Last time the segments were replaced
+    pub updated_at: i64,
+    /// Reserved for future expansion
+    pub reserve: [u8; 32],
+}
+
+impl EmissionSchedule {
+    pub const LEN: usize = 8 + // discriminator
+        (8 + 8) * crate::constants::MAX_EMISSION_SEGMENTS + // segments
+        1 + // segment_count
+        8 + // updated_at
+        32; // reserve
+    
+    /// Active segments
+    pub fn active_segments(&self) -> &[EmissionSegment] {
+        &self.segments[..self.segment_count as usize]
+    }
+    
+    /// Replace all segments (the caller validates them)
+    pub fn set_segments(&mut self, segments: &[EmissionSegment], updated_at: i64) {
+        self.segments = [EmissionSegment::default(); crate::constants::MAX_EMISSION_SEGMENTS];
+        self.segments[..segments.len()].copy_from_slice(segments);
+        self.segment_count = segments.len() as u8;
+        self.updated_at = updated_at;
+    }
+}
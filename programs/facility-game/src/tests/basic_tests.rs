@@ -1,6 +1,6 @@
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 mod basic_tests {
-    use anchor_lang::prelude::*;
     use crate::constants::*;
     use crate::error::GameError;
     use crate::state::*;
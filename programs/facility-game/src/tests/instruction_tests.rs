@@ -1,8 +1,8 @@
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 mod instruction_tests {
     use anchor_lang::prelude::*;
     use crate::state::*;
-    use crate::error::GameError;
     use crate::constants::*;
 
     // Mock data for testing
@@ -22,7 +22,23 @@ mod instruction_tests {
             protocol_referral_address: Pubkey::new_unique(),
             total_supply_minted: 0,
             operator: Pubkey::new_unique(),
-            reserve: [0; 2],
+            version: 1,
+            is_paused: false,
+            pause_reason: 0,
+            paused_at: 0,
+            paused_subsystems: 0,
+            pending_admin: None,
+            pending_admin_expires_at: 0,
+            pending_operator: None,
+            pending_operator_expires_at: 0,
+            config_change_delay: 86400,
+            config_change_counter: 0,
+            oracle_program: Pubkey::default(),
+            randomness_source: RandomnessSource::Oracle,
+            transfer_fee_basis_points: 500,
+            pending_transfer_fee_basis_points: 500,
+            pending_transfer_fee_epoch: 0,
+            reserve: [0; 1],
         }
     }
 
@@ -35,23 +51,52 @@ mod instruction_tests {
             referrer: None,
             pending_referral_rewards: 0,
             total_packs_purchased: 0,
-            reserve: [0; 28],
+            version: 2,
+            reward_debt: 0,
+            pending_farming_rewards: 0,
+            reserve: [0; 3],
         }
     }
     
+    #[allow(dead_code)]
     fn create_mock_user_state_with_packs(owner: Pubkey, packs: u32) -> UserState {
         UserState {
-            owner,
-            total_grow_power: 0,
-            last_harvest_time: 0,
-            has_farm_space: false,
-            referrer: None,
-            pending_referral_rewards: 0,
             total_packs_purchased: packs,
-            reserve: [0; 28],
+            ..create_mock_user_state(owner)
         }
     }
 
+    fn create_mock_global_stats(total_grow_power: u64, last_reward_time: i64) -> GlobalStats {
+        GlobalStats {
+            total_grow_power,
+            total_farm_spaces: 0,
+            total_supply: 0,
+            current_rewards_per_second: 0,
+            last_update_time: 0,
+            version: GlobalStats::CURRENT_VERSION,
+            acc_reward_per_grow_power: 0,
+            last_reward_time,
+            supply_exhausted: false,
+            total_emission_accrued: 0,
+            total_burned: 0,
+            total_packs_sold: 0,
+            total_seeds_generated: 0,
+            total_sol_collected: 0,
+            reserve: [0; 6],
+        }
+    }
+
+    fn create_mock_emission_schedule(rate: u64) -> EmissionSchedule {
+        let mut schedule = EmissionSchedule {
+            segments: [EmissionSegment::default(); MAX_EMISSION_SEGMENTS],
+            segment_count: 0,
+            updated_at: 0,
+            reserve: [0; 32],
+        };
+        schedule.set_segments(&[EmissionSegment { start_time: 0, rate }], 0);
+        schedule
+    }
+
     fn create_mock_farm_space(owner: Pubkey) -> FarmSpace {
         FarmSpace {
             owner,
@@ -59,7 +104,8 @@ mod instruction_tests {
             capacity: 4,
             seed_count: 0,
             total_grow_power: 0,
-            reserve: [0; 32],
+            version: 1,
+            reserve: [0; 31],
         }
     }
 
@@ -141,7 +187,11 @@ mod instruction_tests {
             user_entropy_seed: Some(12345),
             final_random_value: Some(0),
             vrf_account: Some(vrf_account),
-            reserve: [0; 8],
+            commit_slot: 0,
+            version: 1,
+            source_commit: 0,
+            randomness_source: RandomnessSource::Oracle,
+            reserve: [0; 6],
         };
         
         assert_eq!(seed_pack.owner, owner);
@@ -284,4 +334,295 @@ mod instruction_tests {
         assert_eq!(farm_space.level, 3); // Still level 3
         assert_eq!(farm_space.capacity, 10); // Still capacity 10
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_legacy_rewards_capped_at_remaining_supply() {
+        use crate::utils::{settle_legacy_user_rewards, update_reward_accumulator};
+        
+        // 10 WEED/s; the user held all 1000 grow power since t=0
+        let config = Config {
+            base_rate: 10,
+            next_halving_time: 1_000_000,
+            halving_interval: 1_000_000,
+            ..create_mock_config()
+        };
+        let schedule = create_mock_emission_schedule(10);
+        let mut global_stats = create_mock_global_stats(1000, 500);
+        let legacy_user = UserState {
+            total_grow_power: 1000,
+            has_farm_space: true,
+            version: 1,
+            ..create_mock_user_state(Pubkey::new_unique())
+        };
+        update_reward_accumulator(&mut global_stats, &schedule, 1000).unwrap();
+        
+        // Far from the cap: the whole legacy period is settled and counted as emitted
+        let mut stats = global_stats.clone();
+        let mut user_state = legacy_user.clone();
+        settle_legacy_user_rewards(&mut user_state, &mut stats, &config, 1000).unwrap();
+        assert_eq!(user_state.pending_farming_rewards, 10_000);
+        assert_eq!(user_state.reward_debt, 1000 * global_stats.acc_reward_per_grow_power);
+        assert_eq!(stats.total_emission_accrued, global_stats.total_emission_accrued + 10_000);
+        assert!(!stats.supply_exhausted);
+        
+        // 4000 left under the cap: the settlement stops there and emission ends
+        let mut stats = GlobalStats {
+            total_emission_accrued: TOTAL_WEED_SUPPLY - 4000,
+            ..global_stats.clone()
+        };
+        let mut user_state = legacy_user.clone();
+        settle_legacy_user_rewards(&mut user_state, &mut stats, &config, 1000).unwrap();
+        assert_eq!(user_state.pending_farming_rewards, 4000);
+        assert_eq!(stats.total_emission_accrued, TOTAL_WEED_SUPPLY);
+        assert!(stats.supply_exhausted);
+        
+        // Already at the cap: nothing more is owed
+        let mut user_state = legacy_user;
+        settle_legacy_user_rewards(&mut user_state, &mut stats, &config, 1000).unwrap();
+        assert_eq!(user_state.pending_farming_rewards, 0);
+        assert_eq!(stats.total_emission_accrued, TOTAL_WEED_SUPPLY);
+    }
+    
+    #[test]
+    fn test_grow_power_change_settles_checkpoint() {
+        use crate::utils::{apply_user_grow_power_change, settle_pending_farming_rewards, update_reward_accumulator};
+        
+        // 10 WEED/s; A plants 100 and B plants 300 at t=1000
+        let schedule = create_mock_emission_schedule(10);
+        let mut global_stats = create_mock_global_stats(0, 1000);
+        let mut user_a = create_mock_user_state(Pubkey::new_unique());
+        let mut user_b = create_mock_user_state(Pubkey::new_unique());
+        apply_user_grow_power_change(&mut user_a, &mut global_stats, &schedule, 100, 1000).unwrap();
+        apply_user_grow_power_change(&mut user_b, &mut global_stats, &schedule, 300, 1000).unwrap();
+        
+        // t=1100: A plants 100 more; the 1000 emitted so far is settled at A's old share (1/4)
+        apply_user_grow_power_change(&mut user_a, &mut global_stats, &schedule, 100, 1100).unwrap();
+        assert_eq!(user_a.pending_farming_rewards, 250);
+        assert_eq!(user_a.total_grow_power, 200);
+        
+        // t=1200: B removes everything and keeps 3/4 of the first 1000 plus 3/5 of the next 1000
+        apply_user_grow_power_change(&mut user_b, &mut global_stats, &schedule, -300, 1200).unwrap();
+        assert_eq!(user_b.pending_farming_rewards, 750 + 600);
+        assert_eq!(global_stats.total_grow_power, 200);
+        
+        // t=1300: B earns nothing more; A gets 2/5 of 1000 and then all of the last 1000
+        update_reward_accumulator(&mut global_stats, &schedule, 1300).unwrap();
+        assert_eq!(settle_pending_farming_rewards(&mut user_b, &global_stats).unwrap(), 0);
+        settle_pending_farming_rewards(&mut user_a, &global_stats).unwrap();
+        assert_eq!(user_a.pending_farming_rewards, 250 + 400 + 1000);
+        
+        // Everything emitted from t=1000 to t=1300 is accounted for
+        assert_eq!(user_a.pending_farming_rewards + user_b.pending_farming_rewards, 3000);
+    }
+    
+    #[test]
+    fn test_grow_power_change_requires_migration() {
+        use crate::economics::calculate_rewards_across_halving;
+        use crate::utils::{apply_user_grow_power_change, settle_legacy_user_rewards, update_reward_accumulator};
+        use crate::validation::user_validation::validate_user_state_migrated;
+        
+        let config = Config {
+            base_rate: 10,
+            next_halving_time: 1_000_000,
+            halving_interval: 1_000_000,
+            ..create_mock_config()
+        };
+        // Global stats were migrated at t=500; other legacy users hold 900 grow power
+        let schedule = create_mock_emission_schedule(10);
+        let mut global_stats = create_mock_global_stats(1000, 500);
+        let mut user_state = UserState {
+            total_grow_power: 100,
+            has_farm_space: true,
+            version: 1,
+            ..create_mock_user_state(Pubkey::new_unique())
+        };
+        
+        // Planting, removing and claiming are refused until the user state is migrated
+        assert!(validate_user_state_migrated(&user_state).is_err());
+        
+        // Migrating at t=1000 settles the legacy period at the grow power held during it (10%)
+        update_reward_accumulator(&mut global_stats, &schedule, 1000).unwrap();
+        settle_legacy_user_rewards(&mut user_state, &mut global_stats, &config, 1000).unwrap();
+        user_state.version = UserState::CURRENT_VERSION;
+        assert_eq!(user_state.pending_farming_rewards, 1000);
+        
+        // Planting 1000 first would have paid the whole legacy period at 1100/1900
+        let inflated = calculate_rewards_across_halving(1100, 1900, 10, 0, 1000, 1_000_000, 1_000_000).unwrap();
+        assert!(inflated > 5 * user_state.pending_farming_rewards);
+        
+        // Planted after the migration, the new grow power only earns from t=1000
+        assert!(validate_user_state_migrated(&user_state).is_ok());
+        apply_user_grow_power_change(&mut user_state, &mut global_stats, &schedule, 1000, 1000).unwrap();
+        assert_eq!(user_state.pending_farming_rewards, 1000);
+        
+        // t=2000: 10000 emitted over 2000 grow power, 1100 of it the user's
+        apply_user_grow_power_change(&mut user_state, &mut global_stats, &schedule, 0, 2000).unwrap();
+        assert_eq!(user_state.pending_farming_rewards, 1000 + 5500);
+    }
+    
+    #[test]
+    fn test_global_stats_track_supply_and_activity() {
+        use crate::utils::{
+            update_global_stats_on_farm_creation, update_global_stats_on_mint, update_global_stats_on_pack_open,
+            update_global_stats_on_pack_purchase, update_reward_accumulator,
+        };
+        
+        // Rate halves from 10 to 5 per second at t=100
+        let mut schedule = create_mock_emission_schedule(10);
+        schedule.set_segments(&[EmissionSegment { start_time: 0, rate: 10 }, EmissionSegment { start_time: 100, rate: 5 }], 0);
+        let mut global_stats = create_mock_global_stats(0, 1);
+        
+        // Farm purchase: starter seed, grow power and the SOL price
+        update_global_stats_on_farm_creation(&mut global_stats, 100, 500_000_000, 10).unwrap();
+        assert_eq!(global_stats.total_farm_spaces, 1);
+        assert_eq!(global_stats.total_grow_power, 100);
+        assert_eq!(global_stats.total_seeds_generated, 1);
+        assert_eq!(global_stats.total_sol_collected, 500_000_000);
+        
+        // Claims mint, pack purchases burn and pay the VRF fee
+        update_global_stats_on_mint(&mut global_stats, 1000, 20).unwrap();
+        update_global_stats_on_pack_purchase(&mut global_stats, 3, 300, 2_000_000, 30).unwrap();
+        assert_eq!(global_stats.total_supply, 700);
+        assert_eq!(global_stats.total_burned, 300);
+        assert_eq!(global_stats.total_packs_sold, 3);
+        assert_eq!(global_stats.total_sol_collected, 502_000_000);
+        
+        update_global_stats_on_pack_open(&mut global_stats, 3, 40).unwrap();
+        assert_eq!(global_stats.total_seeds_generated, 4);
+        assert_eq!(global_stats.last_update_time, 40);
+        
+        // The emission rate follows the halving step
+        update_reward_accumulator(&mut global_stats, &schedule, 50).unwrap();
+        assert_eq!(global_stats.current_rewards_per_second, 10);
+        update_reward_accumulator(&mut global_stats, &schedule, 150).unwrap();
+        assert_eq!(global_stats.current_rewards_per_second, 5);
+    }
+    
+    #[test]
+    fn test_simulate_claim_matches_accumulator_across_halving() {
+        use crate::economics::{count_rate_changes, halving_emission_segments};
+        use crate::instructions::referral::simulate_farming_reward;
+        use crate::utils::{apply_user_grow_power_change, settle_pending_farming_rewards, update_reward_accumulator};
+        
+        // 100 WEED/s halving to 50 at t=1000
+        let mut schedule = create_mock_emission_schedule(0);
+        schedule.set_segments(&halving_emission_segments(100, 1000, 1000, 0).unwrap(), 0);
+        let mut global_stats = create_mock_global_stats(0, 500);
+        let mut user_a = create_mock_user_state(Pubkey::new_unique());
+        let mut user_b = create_mock_user_state(Pubkey::new_unique());
+        apply_user_grow_power_change(&mut user_a, &mut global_stats, &schedule, 100, 500).unwrap();
+        apply_user_grow_power_change(&mut user_b, &mut global_stats, &schedule, 300, 800).unwrap();
+        
+        // A: 30000 alone, then a quarter of 20000 before and 20000 after the halving
+        let mut simulated_stats = global_stats.clone();
+        let simulated = simulate_farming_reward(&user_a, &mut simulated_stats, &schedule, 1400).unwrap();
+        assert_eq!(simulated, 30000 + 5000 + 5000);
+        assert_eq!(count_rate_changes(schedule.active_segments(), 500, 1400), 1);
+        
+        // The simulation leaves the accounts untouched and matches what the claim settles
+        assert_eq!(user_a.pending_farming_rewards, 0);
+        update_reward_accumulator(&mut global_stats, &schedule, 1400).unwrap();
+        settle_pending_farming_rewards(&mut user_a, &global_stats).unwrap();
+        assert_eq!(user_a.pending_farming_rewards, simulated);
+        assert_eq!(global_stats.acc_reward_per_grow_power, simulated_stats.acc_reward_per_grow_power);
+    }
+    
+    #[test]
+    fn test_simulate_claim_split_matches_claim_for_self_referrer() {
+        use crate::instructions::referral::simulate_referral_split;
+        
+        let user = Pubkey::new_unique();
+        let referrer = Pubkey::new_unique();
+        let protocol = create_mock_config().protocol_referral_address;
+        
+        // A self-referrer earns nothing, so the claimant keeps the whole reward as the claim pays it
+        assert_eq!(simulate_referral_split(1000, Some(user), Some(referrer), user, protocol).unwrap(), (1000, 0, 0));
+        assert_eq!(simulate_referral_split(1000, Some(protocol), None, user, protocol).unwrap(), (1000, 0, 0));
+        
+        // Regular chain and a cycle back to the claimant
+        assert_eq!(simulate_referral_split(1000, Some(referrer), Some(protocol), user, protocol).unwrap(), (900, 100, 0));
+        assert_eq!(simulate_referral_split(1000, Some(referrer), Some(user), user, protocol).unwrap(), (900, 100, 0));
+        assert_eq!(simulate_referral_split(1000, Some(referrer), Some(Pubkey::new_unique()), user, protocol).unwrap(), (850, 100, 50));
+    }
+    
+    #[test]
+    fn test_fee_funded_claims_are_shared_pro_rata() {
+        use crate::utils::{apply_user_grow_power_change, distribute_fee_rewards, settle_pending_farming_rewards, update_reward_accumulator};
+        
+        // 1000 WEED of emission left before the cap; A holds 100 and B 300 grow power
+        let schedule = create_mock_emission_schedule(10);
+        let mut global_stats = GlobalStats {
+            total_emission_accrued: TOTAL_WEED_SUPPLY - 1000,
+            ..create_mock_global_stats(0, 1000)
+        };
+        let mut user_a = create_mock_user_state(Pubkey::new_unique());
+        let mut user_b = create_mock_user_state(Pubkey::new_unique());
+        apply_user_grow_power_change(&mut user_a, &mut global_stats, &schedule, 100, 1000).unwrap();
+        apply_user_grow_power_change(&mut user_b, &mut global_stats, &schedule, 300, 1000).unwrap();
+        
+        // 2000 would be emitted by t=1200, but only the last 1000 before the cap accrues
+        update_reward_accumulator(&mut global_stats, &schedule, 1200).unwrap();
+        assert!(global_stats.supply_exhausted);
+        assert_eq!(global_stats.total_emission_accrued, TOTAL_WEED_SUPPLY);
+        
+        // Emission stops once the supply is exhausted
+        let acc_at_cap = global_stats.acc_reward_per_grow_power;
+        update_reward_accumulator(&mut global_stats, &schedule, 5000).unwrap();
+        assert_eq!(global_stats.acc_reward_per_grow_power, acc_at_cap);
+        assert_eq!(global_stats.current_rewards_per_second, 0);
+        
+        // 4000 WEED of fees reach the vault and are shared by grow power (1:3)
+        distribute_fee_rewards(&mut global_stats, 4000).unwrap();
+        settle_pending_farming_rewards(&mut user_a, &global_stats).unwrap();
+        settle_pending_farming_rewards(&mut user_b, &global_stats).unwrap();
+        assert_eq!(user_a.pending_farming_rewards, 250 + 1000);
+        assert_eq!(user_b.pending_farming_rewards, 750 + 3000);
+        
+        // Claims mint the remaining supply first and then draw on the vault;
+        // either claimer can go first and both are paid in full
+        let owed = [user_a.pending_farming_rewards, user_b.pending_farming_rewards];
+        for order in [[0, 1], [1, 0]] {
+            let (mut supply_headroom, mut vault) = (1000u64, 4000u64);
+            for claimer in order {
+                let minted = owed[claimer].min(supply_headroom);
+                supply_headroom -= minted;
+                let from_vault = (owed[claimer] - minted).min(vault);
+                vault -= from_vault;
+                assert_eq!(minted + from_vault, owed[claimer]);
+            }
+            assert_eq!((supply_headroom, vault), (0, 0));
+        }
+        
+        // Fees cannot be shared while nobody holds grow power
+        let mut empty_stats = create_mock_global_stats(0, 1000);
+        assert!(distribute_fee_rewards(&mut empty_stats, 4000).is_err());
+    }
+    
+    #[test]
+    fn test_claim_split_ignores_self_and_protocol_referrers() {
+        use crate::utils::{creditable_referrer, validate_referral_scenario};
+        
+        let user = Pubkey::new_unique();
+        let referrer = Pubkey::new_unique();
+        let config = create_mock_config();
+        let protocol = config.protocol_referral_address;
+        let split = |level1: Option<Pubkey>, level2: Option<Pubkey>| {
+            let level1 = creditable_referrer(level1, user, protocol);
+            let level2 = creditable_referrer(level2, user, protocol);
+            validate_referral_scenario(1000, level1.is_some(), level2.is_some(), false, false, false).unwrap()
+        };
+        
+        // A self-referrer (invitee == inviter) earns nothing, so the claimant keeps 100%
+        assert_eq!(split(Some(user), Some(user)), (1000, 0, 0));
+        
+        // Protocol referrers hand their shares back to the claimant
+        assert_eq!(split(Some(protocol), None), (1000, 0, 0));
+        assert_eq!(split(Some(referrer), Some(protocol)), (900, 100, 0));
+        
+        // Regular chain: 85% / 10% / 5%
+        assert_eq!(split(Some(referrer), Some(Pubkey::new_unique())), (850, 100, 50));
+        
+        // A referral cycle back to the claimant stops at level 1
+        assert_eq!(split(Some(referrer), Some(user)), (900, 100, 0));
+    }
+}
@@ -37,6 +37,29 @@ pub fn transfer_tokens_with_cpi<'info>(
     token_2022::transfer_checked(cpi_ctx, amount, 6) // 6 decimals for WEED
 }
 
+/// Transfer WEED out of the fee pool vault, signed by the fee pool PDA
+pub fn transfer_from_fee_pool_vault<'info>(
+    fee_pool_token_account: &UncheckedAccount<'info>,
+    to: &UncheckedAccount<'info>,
+    mint: &UncheckedAccount<'info>,
+    fee_pool: &Account<'info, FeePool>,
+    token_program: &Program<'info, Token2022>,
+    fee_pool_bump: u8,
+    amount: u64,
+) -> Result<()> {
+    let transfer_accounts = TransferChecked {
+        from: fee_pool_token_account.to_account_info(),
+        mint: mint.to_account_info(),
+        to: to.to_account_info(),
+        authority: fee_pool.to_account_info(),
+    };
+    
+    let seeds = &[b"fee_pool".as_ref(), &[fee_pool_bump]];
+    let signer = &[&seeds[..]];
+    let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), transfer_accounts, signer);
+    token_2022::transfer_checked(cpi_ctx, amount, 6) // 6 decimals for WEED
+}
+
 /// Transfer SOL using system program
 pub fn transfer_sol_payment<'info>(
     from: &Signer<'info>,
@@ -62,6 +85,117 @@ pub fn transfer_sol_payment<'info>(
     Ok(())
 }
 
+/// Grow a program-owned account to `new_len`, topping up rent-exemption from `payer`
+/// New bytes are zero-initialized, so appended fields start at their zero value
+pub fn realloc_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    new_len: usize,
+) -> Result<()> {
+    if account.data_len() >= new_len {
+        return Ok(());
+    }
+    
+    top_up_rent_exemption(account, payer, system_program, new_len)?;
+    account.resize(new_len)?;
+    
+    Ok(())
+}
+
+/// Transfer from `payer` whatever `account` lacks to be rent-exempt at `len` bytes
+pub fn top_up_rent_exemption<'info>(
+    account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    len: usize,
+) -> Result<()> {
+    let required_lamports = Rent::get()?.minimum_balance(len);
+    let shortfall = required_lamports.saturating_sub(account.lamports());
+    if shortfall > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    
+    Ok(())
+}
+
+// ===== TRANSFER FEE HELPERS =====
+
+/// Read the withheld transfer fees held by a Token 2022 mint
+pub fn mint_withheld_amount(mint: &AccountInfo) -> Result<u64> {
+    use anchor_spl::token_2022::spl_token_2022::extension::{BaseStateWithExtensions, StateWithExtensions};
+    use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::TransferFeeConfig;
+    use anchor_spl::token_2022::spl_token_2022::state::Mint;
+    
+    let data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<Mint>::unpack(&data)
+        .map_err(|_| GameError::InvalidRewardMint)?;
+    let transfer_fee_config = mint_state.get_extension::<TransferFeeConfig>()
+        .map_err(|_| GameError::InvalidRewardMint)?;
+    
+    Ok(u64::from(transfer_fee_config.withheld_amount))
+}
+
+/// Read the TokenMetadata stored in a Token 2022 mint
+pub fn mint_token_metadata(
+    mint: &AccountInfo,
+) -> Result<anchor_spl::token_2022_extensions::spl_token_metadata_interface::state::TokenMetadata> {
+    use anchor_spl::token_2022::spl_token_2022::extension::{BaseStateWithExtensions, StateWithExtensions};
+    use anchor_spl::token_2022::spl_token_2022::state::Mint;
+    
+    let data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<Mint>::unpack(&data)
+        .map_err(|_| GameError::InvalidRewardMint)?;
+    
+    mint_state.get_variable_len_extension()
+        .map_err(|_| GameError::InvalidRewardMint.into())
+}
+
+/// Read the (mint, owner) of a Token 2022 token account
+pub fn token_account_mint_and_owner(token_account: &AccountInfo) -> Result<(Pubkey, Pubkey)> {
+    use anchor_spl::token_2022::spl_token_2022::extension::StateWithExtensions;
+    use anchor_spl::token_2022::spl_token_2022::state::Account as TokenAccount;
+    
+    let data = token_account.try_borrow_data()?;
+    let account_state = StateWithExtensions::<TokenAccount>::unpack(&data)
+        .map_err(|_| GameError::InvalidTreasuryTokenAccount)?;
+    
+    Ok((account_state.base.mint, account_state.base.owner))
+}
+
+/// Read the balance of a Token 2022 token account
+pub fn token_account_amount(token_account: &AccountInfo) -> Result<u64> {
+    use anchor_spl::token_2022::spl_token_2022::extension::StateWithExtensions;
+    use anchor_spl::token_2022::spl_token_2022::state::Account as TokenAccount;
+    
+    let data = token_account.try_borrow_data()?;
+    let account_state = StateWithExtensions::<TokenAccount>::unpack(&data)
+        .map_err(|_| GameError::InvalidFeePoolVault)?;
+    
+    Ok(account_state.base.amount)
+}
+
+/// Read the current supply of a Token 2022 mint
+pub fn mint_supply(mint: &AccountInfo) -> Result<u64> {
+    use anchor_spl::token_2022::spl_token_2022::extension::StateWithExtensions;
+    use anchor_spl::token_2022::spl_token_2022::state::Mint;
+    
+    let data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<Mint>::unpack(&data)
+        .map_err(|_| GameError::InvalidRewardMint)?;
+    
+    Ok(mint_state.base.supply)
+}
+
 // ===== CALCULATION HELPERS =====
 
 // Delegate to economics module for calculation functions
@@ -73,7 +207,7 @@ pub fn calculate_referral_reward(base_reward: u64) -> Result<u64> {
 }
 
 // Delegate to economics module for advanced calculation functions
-pub use crate::economics::{calculate_user_share_reward as calculate_user_share_of_global_rewards, calculate_rewards_across_halving as calculate_user_rewards_across_halving};
+pub use crate::economics::calculate_user_share_reward as calculate_user_share_of_global_rewards;
 
 // Note: Manual upgrade functions removed - now using auto-upgrade system
 
@@ -83,6 +217,12 @@ pub use crate::economics::check_and_apply_halving;
 // Delegate to economics module for referral calculations
 pub use crate::economics::calculate_referral_rewards;
 
+/// A recorded referrer that earns a share of the claimant's farming reward
+/// Self-referrals and the protocol address are excluded; their shares stay with the claimant
+pub fn creditable_referrer(referrer: Option<Pubkey>, user: Pubkey, protocol: Pubkey) -> Option<Pubkey> {
+    referrer.filter(|key| *key != user && *key != protocol)
+}
+
 /// Calculate reward distribution percentages for all scenarios
 /// Returns (user_percentage, level1_percentage, level2_percentage) as basis points (1/100%)
 /// Total always equals 10000 (100%), user's share varies: 100%, 90%, or 85%
@@ -207,19 +347,76 @@ pub fn initialize_farm_space_level_1(farm_space: &mut FarmSpace, owner: Pubkey)
     farm_space.capacity = FarmSpace::get_capacity_for_level(1);
     farm_space.seed_count = 1; // Starting with 1 seed (Seed 1)
     farm_space.total_grow_power = SeedType::Seed1.get_grow_power(); // 100 Grow Power
-    farm_space.reserve = [0; 32];
+    farm_space.version = FarmSpace::CURRENT_VERSION;
+    farm_space.reserve = [0; 31];
     Ok(())
 }
 
-/// Update global stats when farm space is created
+/// Update global stats when farm space is created (with its starter seed)
 pub fn update_global_stats_on_farm_creation(
     global_stats: &mut GlobalStats,
     farm_grow_power: u64,
+    sol_paid: u64,
     current_time: i64,
-) {
+) -> Result<()> {
     global_stats.total_grow_power += farm_grow_power;
     global_stats.total_farm_spaces += 1;
+    global_stats.total_seeds_generated = global_stats.total_seeds_generated
+        .checked_add(1)
+        .ok_or(GameError::CalculationOverflow)?;
+    global_stats.total_sol_collected = global_stats.total_sol_collected
+        .checked_add(sol_paid)
+        .ok_or(GameError::CalculationOverflow)?;
+    global_stats.last_update_time = current_time;
+    Ok(())
+}
+
+/// Update global stats when WEED is minted
+pub fn update_global_stats_on_mint(
+    global_stats: &mut GlobalStats,
+    amount: u64,
+    current_time: i64,
+) -> Result<()> {
+    global_stats.total_supply = global_stats.total_supply
+        .checked_add(amount)
+        .ok_or(GameError::CalculationOverflow)?;
     global_stats.last_update_time = current_time;
+    Ok(())
+}
+
+/// Update global stats when seed packs are bought (WEED burned, VRF fee paid)
+pub fn update_global_stats_on_pack_purchase(
+    global_stats: &mut GlobalStats,
+    quantity: u8,
+    weed_burned: u64,
+    sol_paid: u64,
+    current_time: i64,
+) -> Result<()> {
+    global_stats.total_supply = global_stats.total_supply.saturating_sub(weed_burned);
+    global_stats.total_burned = global_stats.total_burned
+        .checked_add(weed_burned)
+        .ok_or(GameError::CalculationOverflow)?;
+    global_stats.total_packs_sold = global_stats.total_packs_sold
+        .checked_add(quantity as u64)
+        .ok_or(GameError::CalculationOverflow)?;
+    global_stats.total_sol_collected = global_stats.total_sol_collected
+        .checked_add(sol_paid)
+        .ok_or(GameError::CalculationOverflow)?;
+    global_stats.last_update_time = current_time;
+    Ok(())
+}
+
+/// Update global stats when a seed pack is opened
+pub fn update_global_stats_on_pack_open(
+    global_stats: &mut GlobalStats,
+    seeds_generated: u8,
+    current_time: i64,
+) -> Result<()> {
+    global_stats.total_seeds_generated = global_stats.total_seeds_generated
+        .checked_add(seeds_generated as u64)
+        .ok_or(GameError::CalculationOverflow)?;
+    global_stats.last_update_time = current_time;
+    Ok(())
 }
 
 /// Update global stats when grow power changes
@@ -238,6 +435,149 @@ pub fn update_global_grow_power(
     Ok(())
 }
 
+/// Advance the reward accumulator to `current_time` at the current total grow power
+/// Must run before anything changes `total_grow_power` or pays farming rewards
+/// Emission is capped so the total accrued never exceeds TOTAL_WEED_SUPPLY; reaching
+/// the cap marks the supply exhausted, and nothing more is emitted after that
+pub fn update_reward_accumulator(
+    global_stats: &mut GlobalStats,
+    emission_schedule: &EmissionSchedule,
+    current_time: i64,
+) -> Result<()> {
+    global_stats.current_rewards_per_second = if global_stats.supply_exhausted {
+        0
+    } else {
+        crate::economics::current_emission_rate(emission_schedule.active_segments(), current_time)
+    };
+    
+    // Not started yet (pre-accumulator stats account): begin accruing from now
+    if global_stats.last_reward_time == 0 {
+        global_stats.last_reward_time = current_time;
+        return Ok(());
+    }
+    if current_time <= global_stats.last_reward_time {
+        return Ok(());
+    }
+    
+    // Emission with no grow power to credit is not accrued
+    let emission = if global_stats.supply_exhausted || global_stats.total_grow_power == 0 {
+        0
+    } else {
+        crate::economics::calculate_emission(
+            emission_schedule.active_segments(),
+            global_stats.last_reward_time,
+            current_time,
+        )?
+        .min(crate::constants::TOTAL_WEED_SUPPLY.saturating_sub(global_stats.total_emission_accrued))
+    };
+    global_stats.acc_reward_per_grow_power = crate::economics::calculate_acc_reward_per_grow_power(
+        global_stats.acc_reward_per_grow_power,
+        emission,
+        global_stats.total_grow_power,
+    )?;
+    global_stats.total_emission_accrued = global_stats.total_emission_accrued
+        .checked_add(emission)
+        .ok_or(GameError::CalculationOverflow)?;
+    if global_stats.total_emission_accrued >= crate::constants::TOTAL_WEED_SUPPLY {
+        global_stats.supply_exhausted = true;
+    }
+    global_stats.last_reward_time = current_time;
+    Ok(())
+}
+
+/// Share WEED moved into the fee pool vault among the current grow power through the accumulator
+/// Fee-funded claims can then only draw their own share of the vault
+pub fn distribute_fee_rewards(global_stats: &mut GlobalStats, amount: u64) -> Result<()> {
+    require!(global_stats.total_grow_power > 0, GameError::NoGlobalGrowPower);
+    global_stats.acc_reward_per_grow_power = crate::economics::calculate_acc_reward_per_grow_power(
+        global_stats.acc_reward_per_grow_power,
+        amount,
+        global_stats.total_grow_power,
+    )?;
+    Ok(())
+}
+
+/// Checkpoint the reward accrued at the current grow power into `pending_farming_rewards`
+/// Call after `update_reward_accumulator` and before changing `total_grow_power`;
+/// the caller resets `reward_debt` for the new grow power
+pub fn settle_pending_farming_rewards(user_state: &mut UserState, global_stats: &GlobalStats) -> Result<u64> {
+    let accrued = crate::economics::calculate_pending_reward(
+        user_state.total_grow_power,
+        global_stats.acc_reward_per_grow_power,
+        user_state.reward_debt,
+    )?;
+    user_state.pending_farming_rewards = user_state.pending_farming_rewards
+        .checked_add(accrued)
+        .ok_or(GameError::CalculationOverflow)?;
+    Ok(accrued)
+}
+
+/// Change a user's grow power (and the global total), settling rewards earned at the old power first
+pub fn apply_user_grow_power_change(
+    user_state: &mut UserState,
+    global_stats: &mut GlobalStats,
+    emission_schedule: &EmissionSchedule,
+    power_change: i64,
+    current_time: i64,
+) -> Result<()> {
+    update_reward_accumulator(global_stats, emission_schedule, current_time)?;
+    settle_pending_farming_rewards(user_state, global_stats)?;
+    
+    user_state.total_grow_power = if power_change >= 0 {
+        user_state.total_grow_power.checked_add(power_change as u64)
+    } else {
+        user_state.total_grow_power.checked_sub(power_change.unsigned_abs())
+    }
+    .ok_or(GameError::CalculationOverflow)?;
+    user_state.reward_debt = crate::economics::calculate_reward_debt(
+        user_state.total_grow_power,
+        global_stats.acc_reward_per_grow_power,
+    )?;
+    
+    update_global_grow_power(global_stats, power_change, current_time)
+}
+
+/// Settle what the old time-based formula owes a v1 user state into `pending_farming_rewards`
+/// and restart its debt from the current accumulator (see `migrate_user_state`)
+/// The settled amount counts toward `total_emission_accrued` and is capped so that total
+/// stays within TOTAL_WEED_SUPPLY; call after `update_reward_accumulator`
+pub fn settle_legacy_user_rewards(
+    user_state: &mut UserState,
+    global_stats: &mut GlobalStats,
+    config: &Config,
+    current_time: i64,
+) -> Result<()> {
+    if user_state.version >= 2 {
+        return Ok(());
+    }
+    
+    let legacy_rewards = crate::economics::calculate_rewards_across_halving(
+        user_state.total_grow_power,
+        global_stats.total_grow_power,
+        config.base_rate,
+        user_state.last_harvest_time,
+        current_time,
+        config.next_halving_time,
+        config.halving_interval,
+    )?
+    .min(crate::constants::TOTAL_WEED_SUPPLY.saturating_sub(global_stats.total_emission_accrued));
+    
+    global_stats.total_emission_accrued = global_stats.total_emission_accrued
+        .checked_add(legacy_rewards)
+        .ok_or(GameError::CalculationOverflow)?;
+    if global_stats.total_emission_accrued >= crate::constants::TOTAL_WEED_SUPPLY {
+        global_stats.supply_exhausted = true;
+    }
+    
+    user_state.pending_farming_rewards = user_state.pending_farming_rewards.checked_add(legacy_rewards)
+        .ok_or(GameError::CalculationOverflow)?;
+    user_state.reward_debt = crate::economics::calculate_reward_debt(
+        user_state.total_grow_power,
+        global_stats.acc_reward_per_grow_power,
+    )?;
+    Ok(())
+}
+
 // ===== SEED MANAGEMENT HELPERS =====
 
 /// Initialize seed storage for a user
@@ -364,9 +704,7 @@ pub fn get_fixed_salt() -> [u8; 16] {
 pub fn validate_invite_code_format(invite_code: &[u8; 12]) -> Result<()> {
     for &byte in invite_code.iter() {
         require!(
-            (byte >= b'0' && byte <= b'9') || 
-            (byte >= b'A' && byte <= b'Z') || 
-            (byte >= b'a' && byte <= b'z'),
+            byte.is_ascii_alphanumeric(),
             GameError::InvalidInviteCode
         );
     }
@@ -1,5 +1,5 @@
-/// Admin-related validation functions
-/// Handles administrative privileges, configuration validation, and system constraints
+//! Admin-related validation functions
+//! Handles administrative privileges, configuration validation, and system constraints
 
 use anchor_lang::prelude::*;
 use crate::state::*;
@@ -20,21 +20,104 @@ pub fn validate_admin_authority(config: &Config, signer: Pubkey) -> Result<()> {
 // to avoid duplicate function conflicts
 
 /// Validate system is not paused for user actions
-/// Note: Current Config struct doesn't have is_paused field
-/// This is a placeholder for future implementation
-pub fn validate_system_not_paused(_config: &Config) -> Result<()> {
-    // For now, system is never paused since Config doesn't have is_paused field
-    // This can be implemented when the pause functionality is added
+pub fn validate_system_not_paused(config: &Config) -> Result<()> {
+    require!(
+        !config.is_paused,
+        GameError::SystemPaused
+    );
+    Ok(())
+}
+
+/// Validate a specific subsystem is available for user actions
+/// Fails if either the global pause or any of the given subsystem bits is set
+pub fn validate_subsystem_not_paused(config: &Config, flags: u8) -> Result<()> {
+    validate_system_not_paused(config)?;
+    require!(
+        !config.is_subsystem_paused(flags),
+        GameError::SubsystemPaused
+    );
+    Ok(())
+}
+
+/// Validate subsystem pause flags are non-empty and known
+pub fn validate_pause_flags(flags: u8) -> Result<()> {
+    require!(
+        flags != 0 && flags & !crate::constants::PAUSE_ALL_SUBSYSTEMS == 0,
+        GameError::InvalidPauseFlags
+    );
     Ok(())
 }
 
 /// Validate admin can pause/unpause system
-pub fn validate_admin_can_pause(config: &Config, admin: Pubkey) -> Result<()> {
-    validate_admin_authority(config, admin)?;
-    // Additional checks can be added here (e.g., emergency conditions)
+/// Admin or a registered pauser may pause and resume
+pub fn validate_admin_can_pause(
+    config: &Config,
+    registry: Option<&RoleRegistry>,
+    signer: Pubkey
+) -> Result<()> {
+    validate_role(config, registry, signer, crate::constants::ROLE_PAUSER)
+}
+
+// ===== ROLE VALIDATION =====
+
+/// Check whether a signer holds a role
+/// The admin implicitly holds every role, so admin-only flows keep working without a registry
+pub fn has_role(config: &Config, registry: Option<&RoleRegistry>, signer: Pubkey, role: u8) -> bool {
+    config.admin == signer || registry.is_some_and(|r| r.has_role(&signer, role))
+}
+
+/// Validate a signer holds a role
+pub fn validate_role(
+    config: &Config,
+    registry: Option<&RoleRegistry>,
+    signer: Pubkey,
+    role: u8
+) -> Result<()> {
+    require!(
+        has_role(config, registry, signer, role),
+        GameError::MissingRole
+    );
     Ok(())
 }
 
+/// Validate role flags are non-empty and known
+pub fn validate_role_flags(roles: u8) -> Result<()> {
+    require!(
+        roles != 0 && roles & !crate::constants::ALL_ROLES == 0,
+        GameError::InvalidRoleFlags
+    );
+    Ok(())
+}
+
+/// Role allowed to manage a config change (None = admin only)
+pub fn required_role_for_change(change: &ConfigChange) -> Option<u8> {
+    match change {
+        ConfigChange::SeedPackCost { .. } | ConfigChange::FarmSpaceCost { .. } => {
+            Some(crate::constants::ROLE_PRICING_MANAGER)
+        }
+        ConfigChange::ProbabilityTable { .. } => Some(crate::constants::ROLE_SEED_CURATOR),
+        ConfigChange::BaseRate { .. }
+        | ConfigChange::HalvingInterval { .. }
+        | ConfigChange::EmissionSchedule { .. }
+        | ConfigChange::TimelockDelay { .. }
+        | ConfigChange::OracleProgram { .. }
+        | ConfigChange::RandomnessSource { .. } => None,
+    }
+}
+
+/// Check whether a signer may queue, execute or cancel a config change
+pub fn can_manage_config_change(
+    config: &Config,
+    registry: Option<&RoleRegistry>,
+    signer: Pubkey,
+    change: &ConfigChange
+) -> bool {
+    match required_role_for_change(change) {
+        Some(role) => has_role(config, registry, signer, role),
+        None => config.admin == signer,
+    }
+}
+
 // ===== CONFIGURATION VALIDATION =====
 
 /// Validate complete system configuration
@@ -66,7 +149,7 @@ pub fn validate_reward_mint_config(
 ) -> Result<()> {
     // Validate decimals are reasonable (6-9 is typical for tokens)
     require!(
-        decimals >= 6 && decimals <= 9,
+        (6..=9).contains(&decimals),
         GameError::InvalidConfig
     );
     
@@ -104,12 +187,14 @@ pub fn validate_admin_update_request(
 }
 
 /// Validate emergency pause conditions
+/// Admin or a registered pauser may pause
 pub fn validate_emergency_pause_conditions(
     config: &Config,
-    admin: Pubkey,
+    registry: Option<&RoleRegistry>,
+    signer: Pubkey,
     total_grow_power: u64
 ) -> Result<()> {
-    validate_admin_authority(config, admin)?;
+    validate_admin_can_pause(config, registry, signer)?;
     
     // Only allow pause if system has grown beyond initial state
     // This prevents accidental pausing during initial setup
@@ -121,16 +206,254 @@ pub fn validate_emergency_pause_conditions(
     Ok(())
 }
 
+/// Validate operator update request
+pub fn validate_operator_update_request(
+    current_config: &Config,
+    current_admin: Pubkey,
+    new_operator: Pubkey
+) -> Result<()> {
+    validate_admin_authority(current_config, current_admin)?;
+    
+    require!(
+        new_operator != Pubkey::default(),
+        GameError::InvalidConfig
+    );
+    
+    require!(
+        new_operator != current_config.operator,
+        GameError::InvalidConfig
+    );
+    
+    Ok(())
+}
+
+/// Compute the expiry timestamp for a handover proposal
+/// `expires_in` of None means the proposal never expires (stored as 0)
+pub fn calculate_handover_expiry(current_time: i64, expires_in: Option<i64>) -> Result<i64> {
+    match expires_in {
+        None => Ok(0),
+        Some(duration) => {
+            require!(duration > 0, GameError::InvalidHandoverExpiry);
+            current_time
+                .checked_add(duration)
+                .ok_or(GameError::CalculationOverflow.into())
+        }
+    }
+}
+
+/// Validate a pending authority can accept the handover
+pub fn validate_handover_acceptance(
+    pending: Option<Pubkey>,
+    expires_at: i64,
+    signer: Pubkey,
+    current_time: i64
+) -> Result<()> {
+    let pending = pending.ok_or(GameError::NoPendingHandover)?;
+    
+    require!(
+        pending == signer,
+        GameError::NotPendingAuthority
+    );
+    
+    require!(
+        expires_at == 0 || current_time <= expires_at,
+        GameError::HandoverExpired
+    );
+    
+    Ok(())
+}
+
+// ===== ECONOMIC PARAMETER VALIDATION =====
+
+/// Validate seed pack cost (1 - 10,000 WEED)
+pub fn validate_seed_pack_cost(new_cost: u64) -> Result<()> {
+    require!(new_cost > 0, GameError::InvalidConfig);
+    require!(new_cost <= 10_000 * 1_000_000, GameError::InvalidConfig);
+    Ok(())
+}
+
+/// Validate farm space cost (max 10 SOL)
+pub fn validate_farm_space_cost(new_cost: u64) -> Result<()> {
+    require!(new_cost > 0, GameError::InvalidAmount);
+    require!(new_cost <= 10_000_000_000, GameError::InvalidAmount);
+    Ok(())
+}
+
+/// Validate a WEED transfer fee update (up to 10%, non-zero cap)
+pub fn validate_transfer_fee(basis_points: u16, maximum_fee: u64) -> Result<()> {
+    use crate::constants::MAX_TRANSFER_FEE_BASIS_POINTS;
+    
+    require!(basis_points <= MAX_TRANSFER_FEE_BASIS_POINTS, GameError::InvalidConfig);
+    require!(maximum_fee > 0, GameError::InvalidConfig);
+    Ok(())
+}
+
+/// Validate a WEED metadata update
+/// `field` is "uri" or an additional key; name and symbol come from constants and are not updatable
+pub fn validate_token_metadata_update(field: &str, value: &str) -> Result<()> {
+    use crate::constants::{MAX_METADATA_KEY_LENGTH, MAX_METADATA_URI_LENGTH, MAX_METADATA_VALUE_LENGTH};
+    
+    match field {
+        "uri" => require!(value.len() <= MAX_METADATA_URI_LENGTH, GameError::InvalidMetadataField),
+        "name" | "symbol" | "" => return err!(GameError::InvalidMetadataField),
+        _ => {
+            require!(field.len() <= MAX_METADATA_KEY_LENGTH, GameError::InvalidMetadataField);
+            require!(value.len() <= MAX_METADATA_VALUE_LENGTH, GameError::InvalidMetadataField);
+        }
+    }
+    Ok(())
+}
+
+/// Validate probability table contents
+/// Only the first `seed_count` entries of each slice are considered
+pub fn validate_probability_table_update(
+    seed_count: u8,
+    grow_powers: &[u64],
+    probability_thresholds: &[u16],
+    probability_percentages: &[f32],
+) -> Result<()> {
+    let count = seed_count as usize;
+    require!(count > 0 && count <= 16, GameError::InvalidQuantity);
+    require!(grow_powers.len() >= count, GameError::InvalidQuantity);
+    require!(probability_thresholds.len() >= count, GameError::InvalidQuantity);
+    require!(probability_percentages.len() >= count, GameError::InvalidQuantity);
+    
+    // Thresholds must be strictly ascending and end at 10000
+    for i in 1..count {
+        require!(
+            probability_thresholds[i] > probability_thresholds[i - 1],
+            GameError::InvalidConfig
+        );
+    }
+    require!(
+        probability_thresholds[count - 1] == 10000,
+        GameError::InvalidConfig
+    );
+    
+    Ok(())
+}
+
+/// Validate values for revealing or updating a single seed type
+/// Whether the seed is already revealed is checked against the table by the caller
+pub fn validate_seed_values(seed_index: u8, grow_power: u64, probability_percentage: f32) -> Result<()> {
+    require!(seed_index < 16, GameError::InvalidConfig);
+    require!((0.0..=100.0).contains(&probability_percentage), GameError::InvalidConfig);
+    require!(grow_power > 0, GameError::InvalidConfig);
+    Ok(())
+}
+
+/// Validate timelock delay is within bounds
+pub fn validate_timelock_delay(delay: i64) -> Result<()> {
+    require!(
+        (crate::constants::MIN_CONFIG_CHANGE_DELAY..=crate::constants::MAX_CONFIG_CHANGE_DELAY).contains(&delay),
+        GameError::InvalidTimelockDelay
+    );
+    Ok(())
+}
+
+/// Validate a queued economic config change against the current config
+pub fn validate_config_change(config: &Config, change: &ConfigChange) -> Result<()> {
+    match change {
+        ConfigChange::SeedPackCost { new_cost } => validate_seed_pack_cost(*new_cost),
+        ConfigChange::FarmSpaceCost { new_cost } => validate_farm_space_cost(*new_cost),
+        ConfigChange::BaseRate { new_base_rate } => {
+            crate::validation::economic_validation::validate_halving_config(*new_base_rate, config.halving_interval)
+        }
+        ConfigChange::HalvingInterval { new_halving_interval } => {
+            crate::validation::economic_validation::validate_halving_config(config.base_rate, *new_halving_interval)
+        }
+        ConfigChange::ProbabilityTable {
+            seed_count,
+            grow_powers,
+            probability_thresholds,
+            probability_percentages,
+            ..
+        } => validate_probability_table_update(
+            *seed_count,
+            grow_powers,
+            probability_thresholds,
+            probability_percentages,
+        ),
+        ConfigChange::TimelockDelay { new_delay } => validate_timelock_delay(*new_delay),
+        ConfigChange::OracleProgram { program_id } => {
+            require!(*program_id != Pubkey::default(), GameError::InvalidOracleProgram);
+            Ok(())
+        }
+        ConfigChange::RandomnessSource { source } => crate::randomness::beacon::validate_source_enabled(*source),
+        ConfigChange::EmissionSchedule { segment_count, segments } => {
+            require!(
+                (*segment_count as usize) <= segments.len(),
+                GameError::InvalidEmissionSchedule
+            );
+            crate::validation::economic_validation::validate_emission_schedule(
+                &segments[..*segment_count as usize]
+            )
+        }
+    }
+}
+
+/// Validate a queued change's timelock has elapsed
+pub fn validate_timelock_elapsed(executable_at: i64, current_time: i64) -> Result<()> {
+    require!(
+        current_time >= executable_at,
+        GameError::TimelockNotElapsed
+    );
+    Ok(())
+}
+
+// ===== MULTISIG VALIDATION =====
+
+/// Validate multisig membership and threshold
+/// Signers must be unique non-default keys and 1 <= threshold <= signer count
+pub fn validate_multisig_config(signers: &[Pubkey], threshold: u8) -> Result<()> {
+    require!(
+        !signers.is_empty() && signers.len() <= crate::constants::MAX_MULTISIG_SIGNERS,
+        GameError::InvalidMultisigConfig
+    );
+    require!(
+        threshold >= 1 && threshold as usize <= signers.len(),
+        GameError::InvalidMultisigConfig
+    );
+    for (i, signer) in signers.iter().enumerate() {
+        require!(*signer != Pubkey::default(), GameError::InvalidMultisigConfig);
+        require!(!signers[..i].contains(signer), GameError::InvalidMultisigConfig);
+    }
+    Ok(())
+}
+
+/// Validate the shape of a proposal's wrapped instruction
+pub fn validate_proposal_instruction(account_count: usize, data_len: usize) -> Result<()> {
+    require!(
+        account_count <= crate::constants::MAX_PROPOSAL_ACCOUNTS,
+        GameError::InvalidProposal
+    );
+    // Anchor instructions always start with an 8-byte discriminator
+    require!(
+        (8..=crate::constants::MAX_PROPOSAL_DATA_LEN).contains(&data_len),
+        GameError::InvalidProposal
+    );
+    Ok(())
+}
+
+// ===== MIGRATION VALIDATION =====
+
+/// Validate that an account needs migrating and that its version is known
+/// An account migrates when its version is behind or its data is shorter than the current layout
+pub fn validate_migration(from_version: u8, current_version: u8, old_len: usize, new_len: usize) -> Result<()> {
+    require!(from_version <= current_version, GameError::UnsupportedAccountVersion);
+    require!(
+        from_version < current_version || old_len < new_len,
+        GameError::AccountAlreadyMigrated
+    );
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    #[test]
-    fn test_admin_authority_validation() {
-        let admin = Pubkey::new_unique();
-        let other = Pubkey::new_unique();
-        
-        let config = Config {
+    
+    fn create_test_config(admin: Pubkey) -> Config {
+        Config {
             admin,
             treasury: Pubkey::new_unique(),
             base_rate: 100,
@@ -145,8 +468,32 @@ mod tests {
             protocol_referral_address: Pubkey::new_unique(),
             total_supply_minted: 0,
             operator: Pubkey::new_unique(),
-            reserve: [0; 2],
-        };
+            version: 1,
+            is_paused: false,
+            pause_reason: 0,
+            paused_at: 0,
+            paused_subsystems: 0,
+            pending_admin: None,
+            pending_admin_expires_at: 0,
+            pending_operator: None,
+            pending_operator_expires_at: 0,
+            config_change_delay: 86400,
+            config_change_counter: 0,
+            oracle_program: Pubkey::default(),
+            randomness_source: RandomnessSource::Oracle,
+            transfer_fee_basis_points: 200,
+            pending_transfer_fee_basis_points: 200,
+            pending_transfer_fee_epoch: 0,
+            reserve: [0; 1],
+        }
+    }
+
+    #[test]
+    fn test_admin_authority_validation() {
+        let admin = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        
+        let config = create_test_config(admin);
         
         // Valid admin authority
         assert!(validate_admin_authority(&config, admin).is_ok());
@@ -155,6 +502,291 @@ mod tests {
         assert!(validate_admin_authority(&config, other).is_err());
     }
 
+    #[test]
+    fn test_pause_validation() {
+        let admin = Pubkey::new_unique();
+        
+        let mut config = create_test_config(admin);
+        
+        // Running system accepts user actions
+        assert!(validate_system_not_paused(&config).is_ok());
+        
+        // Paused system rejects user actions
+        config.is_paused = true;
+        config.pause_reason = 1;
+        assert!(validate_system_not_paused(&config).is_err());
+        
+        // Only admin can pause
+        assert!(validate_admin_can_pause(&config, None, admin).is_ok());
+        assert!(validate_admin_can_pause(&config, None, Pubkey::new_unique()).is_err());
+        
+        // Emergency pause needs a running game and pause rights
+        assert!(validate_emergency_pause_conditions(&config, None, admin, 100).is_ok());
+        assert!(validate_emergency_pause_conditions(&config, None, admin, 0).is_err());
+        assert!(validate_emergency_pause_conditions(&config, None, Pubkey::new_unique(), 100).is_err());
+    }
+
+    #[test]
+    fn test_subsystem_pause_validation() {
+        use crate::constants::*;
+        
+        let mut config = create_test_config(Pubkey::new_unique());
+        
+        // Nothing paused
+        assert!(validate_subsystem_not_paused(&config, PAUSE_OPEN).is_ok());
+        
+        // Pausing pack opening leaves claims running
+        config.paused_subsystems = PAUSE_OPEN;
+        assert!(validate_subsystem_not_paused(&config, PAUSE_OPEN).is_err());
+        assert!(validate_subsystem_not_paused(&config, PAUSE_CLAIM).is_ok());
+        
+        // Global pause overrides subsystem bits
+        config.paused_subsystems = 0;
+        config.is_paused = true;
+        assert!(validate_subsystem_not_paused(&config, PAUSE_CLAIM).is_err());
+        
+        // Flag validation
+        assert!(validate_pause_flags(PAUSE_PURCHASE | PAUSE_INVITE).is_ok());
+        assert!(validate_pause_flags(PAUSE_ALL_SUBSYSTEMS).is_ok());
+        assert!(validate_pause_flags(0).is_err());
+        assert!(validate_pause_flags(1 << 7).is_err());
+    }
+
+    #[test]
+    fn test_handover_validation() {
+        let admin = Pubkey::new_unique();
+        let operator = Pubkey::new_unique();
+        let candidate = Pubkey::new_unique();
+        
+        let config = Config {
+            operator,
+            ..create_test_config(admin)
+        };
+        
+        // Proposals
+        assert!(validate_admin_update_request(&config, admin, candidate).is_ok());
+        assert!(validate_admin_update_request(&config, candidate, candidate).is_err());
+        assert!(validate_admin_update_request(&config, admin, admin).is_err());
+        assert!(validate_operator_update_request(&config, admin, candidate).is_ok());
+        assert!(validate_operator_update_request(&config, admin, operator).is_err());
+        assert!(validate_operator_update_request(&config, admin, Pubkey::default()).is_err());
+        
+        // Expiry calculation
+        assert_eq!(calculate_handover_expiry(1000, None).unwrap(), 0);
+        assert_eq!(calculate_handover_expiry(1000, Some(3600)).unwrap(), 4600);
+        assert!(calculate_handover_expiry(1000, Some(0)).is_err());
+        assert!(calculate_handover_expiry(1000, Some(-1)).is_err());
+        assert!(calculate_handover_expiry(i64::MAX, Some(1)).is_err());
+        
+        // Acceptance
+        assert!(validate_handover_acceptance(None, 0, candidate, 1000).is_err());
+        assert!(validate_handover_acceptance(Some(candidate), 0, candidate, 1000).is_ok());
+        assert!(validate_handover_acceptance(Some(candidate), 0, admin, 1000).is_err());
+        assert!(validate_handover_acceptance(Some(candidate), 2000, candidate, 2000).is_ok());
+        assert!(validate_handover_acceptance(Some(candidate), 2000, candidate, 2001).is_err());
+    }
+
+    #[test]
+    fn test_config_change_validation() {
+        let config = create_test_config(Pubkey::new_unique());
+        
+        // Price bounds
+        assert!(validate_config_change(&config, &ConfigChange::SeedPackCost { new_cost: 500_000_000 }).is_ok());
+        assert!(validate_config_change(&config, &ConfigChange::SeedPackCost { new_cost: 0 }).is_err());
+        assert!(validate_config_change(&config, &ConfigChange::SeedPackCost { new_cost: 10_001 * 1_000_000 }).is_err());
+        assert!(validate_config_change(&config, &ConfigChange::FarmSpaceCost { new_cost: 1_000_000_000 }).is_ok());
+        assert!(validate_config_change(&config, &ConfigChange::FarmSpaceCost { new_cost: 11_000_000_000 }).is_err());
+        
+        // Reward parameters
+        assert!(validate_config_change(&config, &ConfigChange::BaseRate { new_base_rate: 200 }).is_ok());
+        assert!(validate_config_change(&config, &ConfigChange::BaseRate { new_base_rate: 0 }).is_err());
+        assert!(validate_config_change(&config, &ConfigChange::HalvingInterval { new_halving_interval: 3600 }).is_ok());
+        assert!(validate_config_change(&config, &ConfigChange::HalvingInterval { new_halving_interval: 60 }).is_err());
+        
+        // Emission schedule: only the first `segment_count` segments are checked
+        let mut segments = [EmissionSegment::default(); crate::constants::MAX_EMISSION_SEGMENTS];
+        segments[0] = EmissionSegment { start_time: 0, rate: 500 };
+        segments[1] = EmissionSegment { start_time: 3600, rate: 100 };
+        assert!(validate_config_change(&config, &ConfigChange::EmissionSchedule { segment_count: 2, segments }).is_ok());
+        assert!(validate_config_change(&config, &ConfigChange::EmissionSchedule { segment_count: 3, segments }).is_err());
+        assert!(validate_config_change(&config, &ConfigChange::EmissionSchedule { segment_count: 0, segments }).is_err());
+        assert!(validate_config_change(&config, &ConfigChange::EmissionSchedule { segment_count: 17, segments }).is_err());
+        
+        // Timelock delay
+        assert!(validate_config_change(&config, &ConfigChange::TimelockDelay { new_delay: crate::constants::MIN_CONFIG_CHANGE_DELAY }).is_ok());
+        assert!(validate_config_change(&config, &ConfigChange::TimelockDelay { new_delay: 0 }).is_err());
+        assert!(validate_config_change(&config, &ConfigChange::TimelockDelay { new_delay: -1 }).is_err());
+        assert!(validate_config_change(&config, &ConfigChange::TimelockDelay { new_delay: 31 * 86400 }).is_err());
+        
+        // Randomness settings
+        assert!(validate_config_change(&config, &ConfigChange::OracleProgram { program_id: Pubkey::new_unique() }).is_ok());
+        assert!(validate_config_change(&config, &ConfigChange::OracleProgram { program_id: Pubkey::default() }).is_err());
+        assert!(validate_config_change(&config, &ConfigChange::RandomnessSource { source: RandomnessSource::SlotHash }).is_ok());
+        assert_eq!(
+            validate_config_change(&config, &ConfigChange::RandomnessSource { source: RandomnessSource::AdminBeacon }).is_ok(),
+            cfg!(feature = "localnet")
+        );
+        
+        // Probability table
+        let mut grow_powers = [0u64; 16];
+        let mut thresholds = [0u16; 16];
+        let percentages = [0.0f32; 16];
+        grow_powers[..3].copy_from_slice(&[100, 500, 1000]);
+        thresholds[..3].copy_from_slice(&[6000, 9000, 10000]);
+        let table = |seed_count: u8, probability_thresholds: [u16; 16]| ConfigChange::ProbabilityTable {
+            version: 2,
+            seed_count,
+            grow_powers,
+            probability_thresholds,
+            probability_percentages: percentages,
+            expected_value: 310,
+            name: [0; 32],
+        };
+        assert!(validate_config_change(&config, &table(3, thresholds)).is_ok());
+        assert!(validate_config_change(&config, &table(0, thresholds)).is_err());
+        assert!(validate_config_change(&config, &table(2, thresholds)).is_err());
+        assert!(validate_config_change(&config, &table(17, thresholds)).is_err());
+        let mut unordered = thresholds;
+        unordered[1] = 5000;
+        assert!(validate_config_change(&config, &table(3, unordered)).is_err());
+        
+        // Timelock elapsed
+        assert!(validate_timelock_elapsed(1000, 999).is_err());
+        assert!(validate_timelock_elapsed(1000, 1000).is_ok());
+    }
+
+    #[test]
+    fn test_role_validation() {
+        use crate::constants::*;
+        
+        let admin = Pubkey::new_unique();
+        let pricing = Pubkey::new_unique();
+        let curator = Pubkey::new_unique();
+        let outsider = Pubkey::new_unique();
+        
+        let config = create_test_config(admin);
+        
+        let mut registry = RoleRegistry {
+            members: [RoleMember::default(); MAX_ROLE_MEMBERS],
+            member_count: 0,
+            reserve: [0; 32],
+        };
+        assert_eq!(registry.grant(pricing, ROLE_PRICING_MANAGER), Some(ROLE_PRICING_MANAGER));
+        assert_eq!(registry.grant(curator, ROLE_SEED_CURATOR), Some(ROLE_SEED_CURATOR));
+        assert_eq!(registry.grant(curator, ROLE_PAUSER), Some(ROLE_SEED_CURATOR | ROLE_PAUSER));
+        assert_eq!(registry.member_count, 2);
+        
+        // Admin implicitly holds every role, even without a registry
+        assert!(has_role(&config, None, admin, ROLE_PAUSER));
+        assert!(!has_role(&config, None, pricing, ROLE_PRICING_MANAGER));
+        assert!(has_role(&config, Some(&registry), pricing, ROLE_PRICING_MANAGER));
+        assert!(!has_role(&config, Some(&registry), pricing, ROLE_PAUSER));
+        assert!(validate_admin_can_pause(&config, Some(&registry), curator).is_ok());
+        assert!(validate_admin_can_pause(&config, Some(&registry), outsider).is_err());
+        
+        // Config change authority follows the change kind
+        let price = ConfigChange::SeedPackCost { new_cost: 500_000_000 };
+        let rate = ConfigChange::BaseRate { new_base_rate: 200 };
+        assert!(can_manage_config_change(&config, Some(&registry), pricing, &price));
+        assert!(!can_manage_config_change(&config, Some(&registry), curator, &price));
+        assert!(!can_manage_config_change(&config, Some(&registry), pricing, &rate));
+        assert!(can_manage_config_change(&config, Some(&registry), admin, &rate));
+        let source = ConfigChange::RandomnessSource { source: RandomnessSource::AdminBeacon };
+        assert!(!can_manage_config_change(&config, Some(&registry), pricing, &source));
+        assert!(can_manage_config_change(&config, Some(&registry), admin, &source));
+        
+        // Revoking the last role removes the member
+        assert_eq!(registry.revoke(&curator, ROLE_PAUSER), Some(ROLE_SEED_CURATOR));
+        assert_eq!(registry.revoke(&pricing, ROLE_PRICING_MANAGER), Some(0));
+        assert_eq!(registry.member_count, 1);
+        assert_eq!(registry.roles_of(&pricing), 0);
+        assert_eq!(registry.roles_of(&curator), ROLE_SEED_CURATOR);
+        assert_eq!(registry.revoke(&outsider, ROLE_PAUSER), None);
+        
+        // Registry capacity
+        for _ in 1..MAX_ROLE_MEMBERS {
+            assert!(registry.grant(Pubkey::new_unique(), ROLE_PAUSER).is_some());
+        }
+        assert_eq!(registry.grant(Pubkey::new_unique(), ROLE_PAUSER), None);
+        
+        // Flag validation
+        assert!(validate_role_flags(ALL_ROLES).is_ok());
+        assert!(validate_role_flags(0).is_err());
+        assert!(validate_role_flags(1 << 6).is_err());
+    }
+
+    #[test]
+    fn test_multisig_validation() {
+        use crate::constants::*;
+        
+        let a = Pubkey::new_unique();
+        let b = Pubkey::new_unique();
+        let c = Pubkey::new_unique();
+        
+        // Membership and threshold
+        assert!(validate_multisig_config(&[a, b, c], 2).is_ok());
+        assert!(validate_multisig_config(&[a], 1).is_ok());
+        assert!(validate_multisig_config(&[], 0).is_err());
+        assert!(validate_multisig_config(&[a, b], 0).is_err());
+        assert!(validate_multisig_config(&[a, b], 3).is_err());
+        assert!(validate_multisig_config(&[a, a], 1).is_err());
+        assert!(validate_multisig_config(&[a, Pubkey::default()], 1).is_err());
+        let too_many: Vec<Pubkey> = (0..=MAX_MULTISIG_SIGNERS).map(|_| Pubkey::new_unique()).collect();
+        assert!(validate_multisig_config(&too_many, 1).is_err());
+        
+        // Wrapped instruction shape
+        assert!(validate_proposal_instruction(3, 8).is_ok());
+        assert!(validate_proposal_instruction(MAX_PROPOSAL_ACCOUNTS, MAX_PROPOSAL_DATA_LEN).is_ok());
+        assert!(validate_proposal_instruction(MAX_PROPOSAL_ACCOUNTS + 1, 8).is_err());
+        assert!(validate_proposal_instruction(3, 7).is_err());
+        assert!(validate_proposal_instruction(3, MAX_PROPOSAL_DATA_LEN + 1).is_err());
+        
+        // Signer lookup ignores unused slots
+        let mut signers = [Pubkey::default(); MAX_MULTISIG_SIGNERS];
+        signers[0] = a;
+        signers[1] = b;
+        let multisig = Multisig {
+            signers,
+            signer_count: 2,
+            threshold: 2,
+            proposal_counter: 0,
+            owner_set_seqno: 0,
+            reserve: [0; 32],
+        };
+        assert_eq!(multisig.signer_index(&b), Some(1));
+        assert_eq!(multisig.signer_index(&c), None);
+        assert_eq!(multisig.signer_index(&Pubkey::default()), None);
+        
+        // Approvals are a bitmap over signer slots
+        let proposal = Proposal {
+            proposal_id: 0,
+            proposer: a,
+            accounts: vec![],
+            data: vec![0; 8],
+            approvals: 0b101,
+            owner_set_seqno: 0,
+            executed: false,
+            created_at: 0,
+            executed_at: 0,
+            reserve: [0; 16],
+        };
+        assert_eq!(proposal.approval_count(), 2);
+        assert_eq!(Proposal::space(2, 16), Proposal::space(0, 0) + 2 * ProposalAccount::LEN + 16);
+    }
+
+    #[test]
+    fn test_migration_validation() {
+        // Legacy account behind on version
+        assert!(validate_migration(0, 1, 100, 100).is_ok());
+        assert!(validate_migration(0, 1, 80, 100).is_ok());
+        // Current version but shorter layout (fields appended without a bump)
+        assert!(validate_migration(1, 1, 80, 100).is_ok());
+        // Already current
+        assert!(validate_migration(1, 1, 100, 100).is_err());
+        // Written by a newer program
+        assert!(validate_migration(2, 1, 100, 100).is_err());
+    }
+
     // Note: Treasury validation tests are in economic_validation.rs
 
     #[test]
@@ -202,4 +834,81 @@ mod tests {
         assert!(validate_reward_mint_config(6, max_allowed_supply + 1).is_err());
         assert!(validate_reward_mint_config(6, u64::MAX).is_err());
     }
+
+    #[test]
+    fn test_transfer_fee_validation() {
+        // Up to 10%, in single basis points
+        assert!(validate_transfer_fee(200, 1_000_000_000).is_ok());
+        assert!(validate_transfer_fee(0, 1).is_ok());
+        assert!(validate_transfer_fee(1_000, 1).is_ok());
+        assert!(validate_transfer_fee(250, 1).is_ok());
+        
+        // Above the cap
+        assert!(validate_transfer_fee(1_100, 1).is_err());
+        assert!(validate_transfer_fee(1_001, 1).is_err());
+        
+        // Zero maximum fee
+        assert!(validate_transfer_fee(200, 0).is_err());
+    }
+
+    #[test]
+    fn test_transfer_fee_schedule() {
+        let mut config = create_test_config(Pubkey::new_unique());
+        
+        // Set in epoch 10: the mint keeps charging 2% until epoch 12
+        config.schedule_transfer_fee(250, 10).unwrap();
+        assert_eq!(config.pending_transfer_fee_epoch, 12);
+        assert_eq!(config.transfer_fee_basis_points_at(10), 200);
+        assert_eq!(config.transfer_fee_basis_points_at(11), 200);
+        assert_eq!(config.transfer_fee_basis_points_at(12), 250);
+        assert_eq!(config.trading_fee_percentage, 2);
+        
+        // A second update before epoch 12 replaces the pending fee; 2% still applies
+        config.schedule_transfer_fee(99, 11).unwrap();
+        assert_eq!(config.transfer_fee_basis_points, 200);
+        assert_eq!(config.pending_transfer_fee_epoch, 13);
+        assert_eq!(config.transfer_fee_basis_points_at(12), 200);
+        assert_eq!(config.transfer_fee_basis_points_at(13), 99);
+        
+        // Once the pending fee is in effect, the next update promotes it
+        config.schedule_transfer_fee(1_000, 20).unwrap();
+        assert_eq!(config.transfer_fee_basis_points, 99);
+        assert_eq!(config.trading_fee_percentage, 0); // rounds down
+        assert_eq!(config.transfer_fee_basis_points_at(21), 99);
+        assert_eq!(config.transfer_fee_basis_points_at(22), 1_000);
+        
+        assert!(config.schedule_transfer_fee(200, u64::MAX).is_err());
+    }
+
+    #[test]
+    fn test_token_metadata_update_validation() {
+        // URI and additional fields
+        assert!(validate_token_metadata_update("uri", "https://example.com/weed.json").is_ok());
+        assert!(validate_token_metadata_update("uri", "").is_ok());
+        assert!(validate_token_metadata_update("website", "https://example.com").is_ok());
+        
+        // Name and symbol are fixed; keys must be non-empty
+        assert!(validate_token_metadata_update("name", "Other").is_err());
+        assert!(validate_token_metadata_update("symbol", "OTH").is_err());
+        assert!(validate_token_metadata_update("", "value").is_err());
+        
+        // Length limits
+        assert!(validate_token_metadata_update("uri", &"a".repeat(201)).is_err());
+        assert!(validate_token_metadata_update(&"k".repeat(33), "value").is_err());
+        assert!(validate_token_metadata_update("website", &"a".repeat(201)).is_err());
+    }
+
+    #[test]
+    fn test_seed_values_validation() {
+        assert!(validate_seed_values(0, 100, 30.0).is_ok());
+        assert!(validate_seed_values(15, 1, 0.0).is_ok());
+        assert!(validate_seed_values(15, 1, 100.0).is_ok());
+        
+        // Index out of range, probability outside 0-100%, zero grow power
+        assert!(validate_seed_values(16, 100, 30.0).is_err());
+        assert!(validate_seed_values(0, 100, 100.5).is_err());
+        assert!(validate_seed_values(0, 100, -1.0).is_err());
+        assert!(validate_seed_values(0, 100, f32::NAN).is_err());
+        assert!(validate_seed_values(0, 0, 30.0).is_err());
+    }
 }
\ No newline at end of file
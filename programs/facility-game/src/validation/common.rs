@@ -1,5 +1,5 @@
-/// Common validation patterns used across the application
-/// Centralized location for reusable validation logic
+//! Common validation patterns used across the application
+//! Centralized location for reusable validation logic
 
 use anchor_lang::prelude::*;
 use anchor_spl::token::TokenAccount;
@@ -89,13 +89,13 @@ pub fn validate_seed_pack_quantity(quantity: u8) -> Result<()> {
 
 /// Validate farm level (currently level 5, future expansion to 10)
 pub fn validate_farm_level(level: u8) -> Result<()> {
-    require!(level >= 1 && level <= 5, GameError::InvalidConfig);
+    require!((1..=5).contains(&level), GameError::InvalidConfig);
     Ok(())
 }
 
 /// Validate referral level
 pub fn validate_referral_level(level: u8) -> Result<()> {
-    require!(level >= 1 && level <= MAX_REFERRAL_DEPTH, GameError::InvalidReferralLevel);
+    require!((1..=MAX_REFERRAL_DEPTH).contains(&level), GameError::InvalidReferralLevel);
     Ok(())
 }
 
@@ -380,7 +380,7 @@ mod tests {
 
     #[test]
     fn test_supply_cap_validation() {
-        let current_supply = 100_000_000 * 1_000_000; // 100M WEED
+        let current_supply = 230_000_000 * 1_000_000; // 230M WEED (10M below the 240M cap)
         let small_mint = 1_000_000 * 1_000_000; // 1M WEED
         let large_mint = 50_000_000 * 1_000_000; // 50M WEED (would exceed cap)
         
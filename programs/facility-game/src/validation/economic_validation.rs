@@ -1,9 +1,10 @@
-/// Economic validation functions
-/// Handles balance checks, reward limits, and economic constraints
+//! Economic validation functions
+//! Handles balance checks, reward limits, and economic constraints
 
 use anchor_lang::prelude::*;
 use crate::error::GameError;
 use crate::constants::*;
+use crate::state::EmissionSegment;
 
 // ===== BALANCE & PAYMENT VALIDATION =====
 
@@ -76,6 +77,11 @@ pub fn get_remaining_supply(current_minted: u64) -> u64 {
     TOTAL_WEED_SUPPLY.saturating_sub(current_minted)
 }
 
+/// Portion of `amount` that can still be minted (the final claim gets whatever is left)
+pub fn cap_to_remaining_supply(current_minted: u64, amount: u64) -> u64 {
+    amount.min(get_remaining_supply(current_minted))
+}
+
 /// Validate halving parameters
 pub fn validate_halving_config(base_rate: u64, halving_interval: i64) -> Result<()> {
     require!(
@@ -96,6 +102,19 @@ pub fn validate_halving_config(base_rate: u64, halving_interval: i64) -> Result<
     Ok(())
 }
 
+/// Validate emission schedule segments (1..=MAX_EMISSION_SEGMENTS, strictly ascending start times)
+pub fn validate_emission_schedule(segments: &[EmissionSegment]) -> Result<()> {
+    require!(
+        !segments.is_empty() && segments.len() <= MAX_EMISSION_SEGMENTS,
+        GameError::InvalidEmissionSchedule
+    );
+    require!(
+        segments.windows(2).all(|pair| pair[0].start_time < pair[1].start_time),
+        GameError::InvalidEmissionSchedule
+    );
+    Ok(())
+}
+
 /// Validate global grow power is positive
 pub fn validate_global_grow_power(total_grow_power: u64) -> Result<()> {
     require!(
@@ -114,6 +133,45 @@ pub fn validate_treasury_address(treasury: Pubkey) -> Result<()> {
     Ok(())
 }
 
+/// Validate a fee withdrawal leaves the fee vault rent-exempt
+/// Returns the amount to withdraw (all accumulated fees)
+pub fn validate_fee_withdrawal(accumulated_fees: u64, vault_lamports: u64, rent_minimum: u64) -> Result<u64> {
+    require!(accumulated_fees > 0, GameError::InsufficientFees);
+    let remaining = vault_lamports
+        .checked_sub(accumulated_fees)
+        .ok_or(GameError::InsufficientFees)?;
+    require!(remaining >= rent_minimum, GameError::InsufficientFees);
+    Ok(accumulated_fees)
+}
+
+/// Validate the destination of withheld transfer fees is the treasury's reward token account
+pub fn validate_treasury_token_account(
+    account_mint: Pubkey,
+    account_owner: Pubkey,
+    reward_mint: Pubkey,
+    treasury: Pubkey,
+) -> Result<()> {
+    require!(
+        account_mint == reward_mint && account_owner == treasury,
+        GameError::InvalidTreasuryTokenAccount
+    );
+    Ok(())
+}
+
+/// Validate the fee pool vault is a reward token account owned by the fee pool PDA
+pub fn validate_fee_pool_vault(
+    account_mint: Pubkey,
+    account_owner: Pubkey,
+    reward_mint: Pubkey,
+    fee_pool: Pubkey,
+) -> Result<()> {
+    require!(
+        account_mint == reward_mint && account_owner == fee_pool,
+        GameError::InvalidFeePoolVault
+    );
+    Ok(())
+}
+
 // ===== QUANTITY VALIDATION =====
 
 /// Validate purchase quantity
@@ -191,6 +249,25 @@ mod tests {
         assert!(validate_halving_config(100, 366 * SECONDS_PER_DAY).is_err());
     }
 
+    #[test]
+    fn test_emission_schedule_validation() {
+        let segment = |start_time, rate| EmissionSegment { start_time, rate };
+        
+        // Boost, decay steps, tail
+        assert!(validate_emission_schedule(&[segment(0, 500), segment(100, 200), segment(200, 50)]).is_ok());
+        assert!(validate_emission_schedule(&[segment(0, 0)]).is_ok());
+        
+        // Empty or too many segments
+        assert!(validate_emission_schedule(&[]).is_err());
+        let too_many: Vec<_> = (0..=MAX_EMISSION_SEGMENTS as i64).map(|i| segment(i, 1)).collect();
+        assert!(validate_emission_schedule(&too_many).is_err());
+        assert!(validate_emission_schedule(&too_many[..MAX_EMISSION_SEGMENTS]).is_ok());
+        
+        // Start times must strictly ascend
+        assert!(validate_emission_schedule(&[segment(100, 1), segment(100, 2)]).is_err());
+        assert!(validate_emission_schedule(&[segment(100, 1), segment(50, 2)]).is_err());
+    }
+
     #[test]
     fn test_quantity_validation() {
         // Valid quantities
@@ -225,7 +302,7 @@ mod tests {
 
     #[test]
     fn test_supply_cap_validation() {
-        let current_minted = 100_000_000 * 1_000_000u64; // 100M tokens minted
+        let current_minted = 230_000_000 * 1_000_000u64; // 230M tokens minted (10M below cap)
         let small_amount = 1_000_000u64; // 1 token
         let large_amount = 30_000_000 * 1_000_000u64; // 30M tokens
         
@@ -259,6 +336,47 @@ mod tests {
         // Over cap (shouldn't happen but test anyway)
         assert!(is_supply_exhausted(at_cap + 1));
         assert_eq!(get_remaining_supply(at_cap + 1), 0);
+        
+        // The claim that crosses the cap receives exactly the remainder
+        let remaining = get_remaining_supply(current_minted);
+        assert_eq!(cap_to_remaining_supply(current_minted, remaining - 1), remaining - 1);
+        assert_eq!(cap_to_remaining_supply(current_minted, remaining * 2), remaining);
+        assert!(is_supply_exhausted(current_minted + cap_to_remaining_supply(current_minted, remaining * 2)));
+        assert_eq!(cap_to_remaining_supply(at_cap, 1_000_000), 0);
+    }
+
+    #[test]
+    fn test_fee_withdrawal_validation() {
+        let rent_minimum = 1_000_000;
+        
+        // Vault holds rent plus accumulated fees
+        assert_eq!(validate_fee_withdrawal(2_077_400, rent_minimum + 2_077_400, rent_minimum).unwrap(), 2_077_400);
+        
+        // Nothing to withdraw
+        assert!(validate_fee_withdrawal(0, rent_minimum, rent_minimum).is_err());
+        
+        // Withdrawal would dip into rent or exceed the balance
+        assert!(validate_fee_withdrawal(2_077_400, rent_minimum + 2_077_399, rent_minimum).is_err());
+        assert!(validate_fee_withdrawal(2_077_400, 1_000, rent_minimum).is_err());
+    }
+
+    #[test]
+    fn test_treasury_token_account_validation() {
+        let reward_mint = Pubkey::new_unique();
+        let treasury = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        
+        assert!(validate_treasury_token_account(reward_mint, treasury, reward_mint, treasury).is_ok());
+        
+        // Wrong mint or owner
+        assert!(validate_treasury_token_account(other, treasury, reward_mint, treasury).is_err());
+        assert!(validate_treasury_token_account(reward_mint, other, reward_mint, treasury).is_err());
+        
+        // Fee pool vault: same shape, owned by the fee pool PDA instead
+        let fee_pool = Pubkey::new_unique();
+        assert!(validate_fee_pool_vault(reward_mint, fee_pool, reward_mint, fee_pool).is_ok());
+        assert!(validate_fee_pool_vault(reward_mint, treasury, reward_mint, fee_pool).is_err());
+        assert!(validate_fee_pool_vault(other, fee_pool, reward_mint, fee_pool).is_err());
     }
 
     #[test]
@@ -1,5 +1,5 @@
-/// Game-specific validation functions  
-/// Handles farm spaces, seeds, storage, and invite system validation
+//! Game-specific validation functions  
+//! Handles farm spaces, seeds, storage, and invite system validation
 
 use anchor_lang::prelude::*;
 use crate::state::*;
@@ -48,6 +48,28 @@ pub fn validate_farm_space_level_with_config(level: u8, config: &FarmLevelConfig
     Ok(())
 }
 
+/// Validate a farm level configuration update
+/// One capacity and threshold per level, both strictly ascending; names are optional (max 32 bytes)
+pub fn validate_farm_level_config_update(
+    max_level: u8,
+    capacities: &[u8],
+    upgrade_thresholds: &[u32],
+    level_names: Option<&[String]>,
+) -> Result<()> {
+    require!((1..=20).contains(&max_level), GameError::InvalidConfig);
+    require!(capacities.len() == max_level as usize, GameError::InvalidConfig);
+    require!(upgrade_thresholds.len() == max_level as usize, GameError::InvalidConfig);
+    require!(capacities.windows(2).all(|pair| pair[1] > pair[0]), GameError::InvalidConfig);
+    require!(upgrade_thresholds.windows(2).all(|pair| pair[1] > pair[0]), GameError::InvalidConfig);
+    
+    if let Some(names) = level_names {
+        require!(names.len() == max_level as usize, GameError::InvalidConfig);
+        require!(names.iter().all(|name| name.len() <= 32), GameError::InvalidConfig);
+    }
+    
+    Ok(())
+}
+
 // ===== SEED VALIDATION =====
 
 /// Validate seed ownership (moved from common.rs)
@@ -139,7 +161,7 @@ pub fn validate_seed_storage_ownership(storage: &SeedStorage, expected_owner: Pu
 // ===== INVITE SYSTEM VALIDATION =====
 
 /// Validate invite code format
-pub fn validate_invite_code_format(code: &[u8; 8]) -> Result<()> {
+pub fn validate_invite_code_format(code: &[u8; 12]) -> Result<()> {
     require!(
         validate_invite_code(code),
         GameError::InvalidInviteCode
@@ -209,7 +231,8 @@ mod tests {
             capacity: 8,
             seed_count: 4,
             total_grow_power: 800,
-            reserve: [0; 32],
+            version: 1,
+            reserve: [0; 31],
         };
         
         let full_farm_space = FarmSpace {
@@ -217,7 +240,7 @@ mod tests {
             ..farm_space
         };
         
-        let max_level_farm = FarmSpace {
+        let _max_level_farm = FarmSpace {
             level: 5, // Max level
             ..farm_space
         };
@@ -239,6 +262,28 @@ mod tests {
         
     }
 
+    #[test]
+    fn test_farm_level_config_update_validation() {
+        let names: Vec<String> = ["Sprout", "Garden", "Field"].iter().map(|name| name.to_string()).collect();
+        assert!(validate_farm_level_config_update(3, &[4, 6, 10], &[0, 30, 100], None).is_ok());
+        assert!(validate_farm_level_config_update(3, &[4, 6, 10], &[0, 30, 100], Some(&names)).is_ok());
+        
+        // Level count out of range or not matching the arrays
+        assert!(validate_farm_level_config_update(0, &[], &[], None).is_err());
+        assert!(validate_farm_level_config_update(21, &[1; 21], &[0; 21], None).is_err());
+        assert!(validate_farm_level_config_update(3, &[4, 6], &[0, 30, 100], None).is_err());
+        assert!(validate_farm_level_config_update(2, &[4, 6], &[0, 30, 100], None).is_err());
+        
+        // Not strictly ascending
+        assert!(validate_farm_level_config_update(3, &[4, 4, 10], &[0, 30, 100], None).is_err());
+        assert!(validate_farm_level_config_update(3, &[4, 6, 10], &[0, 100, 30], None).is_err());
+        
+        // Names must match the level count and fit 32 bytes
+        assert!(validate_farm_level_config_update(3, &[4, 6, 10], &[0, 30, 100], Some(&names[..2])).is_err());
+        let long_names = vec!["a".to_string(), "b".to_string(), "c".repeat(33)];
+        assert!(validate_farm_level_config_update(3, &[4, 6, 10], &[0, 30, 100], Some(&long_names)).is_err());
+    }
+
     #[test]
     fn test_seed_validation() {
         let owner = Pubkey::new_unique();
@@ -303,7 +348,11 @@ mod tests {
             user_entropy_seed: Some(12345),
             final_random_value: Some(0),
             vrf_account: Some(Pubkey::new_unique()),
-            reserve: [0; 8],
+            commit_slot: 0,
+            version: 1,
+            source_commit: 0,
+            randomness_source: RandomnessSource::Oracle,
+            reserve: [0; 6],
         };
         
         let opened_pack = SeedPack {
@@ -342,8 +391,8 @@ mod tests {
     #[test]
     fn test_composite_validation() {
         let owner = Pubkey::new_unique();
-        let sufficient_balance = 100_000_000u64;
-        let insufficient_balance = 1000u64;
+        let _sufficient_balance = 100_000_000u64;
+        let _insufficient_balance = 1000u64;
         
         let farm_space = FarmSpace {
             owner,
@@ -351,7 +400,8 @@ mod tests {
             capacity: 4,
             seed_count: 2, // Has capacity
             total_grow_power: 200,
-            reserve: [0; 32],
+            version: 1,
+            reserve: [0; 31],
         };
         
         let seed = Seed {
@@ -1,5 +1,5 @@
-/// Validation module for game logic
-/// Refactored for better organization and performance
+//! Validation module for game logic
+//! Refactored for better organization and performance
 
 // Core validation modules organized by domain
 pub mod common;
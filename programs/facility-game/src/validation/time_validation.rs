@@ -1,5 +1,5 @@
-/// Time-related validation functions
-/// Handles timing constraints, intervals, and timestamp validation
+//! Time-related validation functions
+//! Handles timing constraints, intervals, and timestamp validation
 
 use anchor_lang::prelude::*;
 use crate::error::GameError;
@@ -1,5 +1,5 @@
-/// User-related validation functions
-/// Handles user ownership, permissions, and state validation
+//! User-related validation functions
+//! Handles user ownership, permissions, and state validation
 
 use anchor_lang::prelude::*;
 use crate::state::*;
@@ -34,6 +34,16 @@ pub fn validate_no_existing_farm_space(user_state: &UserState) -> Result<()> {
     Ok(())
 }
 
+/// Validate the user state is on the current layout
+/// Legacy rewards are settled by `migrate_user_state`, so grow power must not change before it runs
+pub fn validate_user_state_migrated(user_state: &UserState) -> Result<()> {
+    require!(
+        user_state.version == UserState::CURRENT_VERSION,
+        GameError::UserStateNotMigrated
+    );
+    Ok(())
+}
+
 /// Validate user has grow power
 pub fn validate_has_grow_power(user_state: &UserState) -> Result<()> {
     require!(
@@ -101,7 +111,10 @@ mod tests {
             referrer: None,
             pending_referral_rewards: 0,
             total_packs_purchased: 0,
-            reserve: [0; 28],
+            version: 2,
+            reward_debt: 0,
+            pending_farming_rewards: 0,
+            reserve: [0; 3],
         };
 
         // Valid ownership
@@ -121,7 +134,10 @@ mod tests {
             referrer: None,
             pending_referral_rewards: 0,
             total_packs_purchased: 0,
-            reserve: [0; 28],
+            version: 2,
+            reward_debt: 0,
+            pending_farming_rewards: 0,
+            reserve: [0; 3],
         };
 
         let user_state_without_farm = UserState {
@@ -148,7 +164,10 @@ mod tests {
             referrer: None,
             pending_referral_rewards: 0,
             total_packs_purchased: 0,
-            reserve: [0; 28],
+            version: 2,
+            reward_debt: 0,
+            pending_farming_rewards: 0,
+            reserve: [0; 3],
         };
 
         let user_without_power = UserState {
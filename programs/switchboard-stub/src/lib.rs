@@ -0,0 +1,105 @@
+// Switchboard On-Demandのランダムネスアカウントを再現するローカル用スタンドイン
+// farm_gameのオラクル検証（所有者・ディスクリミネーター・シードスロット）を
+// ローカルバリデーター上でテストするためだけに使用する。本番環境にはデプロイしないこと。
+//
+// 使い方:
+// 1. Config.oracle_program をこのプログラムIDに変更（ConfigChange::OracleProgram）
+// 2. initialize_randomness でアカウント作成
+// 3. purchase_seed_pack と同じトランザクションで commit_randomness
+// 4. reveal_randomness で値を公開してから open_seed_pack
+#![allow(deprecated)] // Suppress Anchor framework's internal deprecation warnings
+use anchor_lang::prelude::*;
+
+declare_id!("7FXvFWezGUCEA2FpMEJ25NbE2hGej5bSBuutBz35qRZd");
+
+#[program]
+pub mod switchboard_stub {
+    use super::*;
+    
+    /// Create a randomness account owned by this program
+    pub fn initialize_randomness(ctx: Context<InitializeRandomness>) -> Result<()> {
+        let randomness = &mut ctx.accounts.randomness;
+        randomness.authority = ctx.accounts.authority.key();
+        randomness.queue = Pubkey::default();
+        randomness.seed_slothash = [0; 32];
+        randomness.seed_slot = 0;
+        randomness.oracle = ctx.accounts.authority.key();
+        randomness.reveal_slot = 0;
+        randomness.value = [0; 32];
+        randomness.ebuf = [0; 224];
+        Ok(())
+    }
+    
+    /// Commit to the previous slot and clear any earlier reveal
+    /// Mirrors On-Demand, which seeds a commit with the latest slot hash
+    pub fn commit_randomness(ctx: Context<UpdateRandomness>) -> Result<()> {
+        let randomness = &mut ctx.accounts.randomness;
+        randomness.seed_slot = Clock::get()?.slot.saturating_sub(1);
+        randomness.reveal_slot = 0;
+        randomness.value = [0; 32];
+        msg!("Randomness committed at seed slot {}", randomness.seed_slot);
+        Ok(())
+    }
+    
+    /// Reveal a value for the current commit (the authority acts as the oracle)
+    pub fn reveal_randomness(ctx: Context<UpdateRandomness>, value: [u8; 32]) -> Result<()> {
+        let randomness = &mut ctx.accounts.randomness;
+        randomness.reveal_slot = Clock::get()?.slot;
+        randomness.value = value;
+        msg!("Randomness revealed at slot {}", randomness.reveal_slot);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializeRandomness<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = RandomnessAccountData::LEN
+    )]
+    pub randomness: Account<'info, RandomnessAccountData>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateRandomness<'info> {
+    #[account(
+        mut,
+        has_one = authority
+    )]
+    pub randomness: Account<'info, RandomnessAccountData>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Same name, field order and size as Switchboard On-Demand's account,
+/// so the Anchor discriminator and byte layout match
+#[account]
+pub struct RandomnessAccountData {
+    pub authority: Pubkey,
+    pub queue: Pubkey,
+    pub seed_slothash: [u8; 32],
+    pub seed_slot: u64,
+    pub oracle: Pubkey,
+    pub reveal_slot: u64,
+    pub value: [u8; 32],
+    /// Trailing padding of the real account
+    pub ebuf: [u8; 224],
+}
+
+impl RandomnessAccountData {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // authority
+        32 + // queue
+        32 + // seed_slothash
+        8 + // seed_slot
+        32 + // oracle
+        8 + // reveal_slot
+        32 + // value
+        224; // ebuf
+}
@@ -0,0 +1,140 @@
+//! Player strategies and per-agent state
+//! An agent mirrors the parts of UserState, FarmSpace and SeedStorage that affect rewards
+
+use anchor_lang::Result;
+use farm_game::economics::{calculate_pending_reward, calculate_reward_debt};
+
+pub const SECONDS_PER_DAY: i64 = 86_400;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Strategy {
+    /// Claims weekly and keeps the WEED; plays on the starter seed only
+    Hodler,
+    /// Claims daily and sells everything on the market
+    Seller,
+    /// Claims daily and spends everything on seed packs
+    Compounder,
+    /// Compounder that also buys WEED from the market with a SOL budget
+    Whale,
+    /// Compounder that invites the agents who join through referrals
+    Referrer,
+}
+
+impl Strategy {
+    pub const ALL: [Strategy; 5] = [
+        Strategy::Hodler,
+        Strategy::Seller,
+        Strategy::Compounder,
+        Strategy::Whale,
+        Strategy::Referrer,
+    ];
+    
+    pub fn name(self) -> &'static str {
+        match self {
+            Strategy::Hodler => "hodler",
+            Strategy::Seller => "seller",
+            Strategy::Compounder => "compounder",
+            Strategy::Whale => "whale",
+            Strategy::Referrer => "referrer",
+        }
+    }
+    
+    /// Seconds between claims
+    pub fn claim_interval(self) -> i64 {
+        match self {
+            Strategy::Hodler => 7 * SECONDS_PER_DAY,
+            _ => SECONDS_PER_DAY,
+        }
+    }
+    
+    /// Whether claimed WEED goes back into seed packs
+    pub fn reinvests(self) -> bool {
+        matches!(self, Strategy::Compounder | Strategy::Whale | Strategy::Referrer)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Agent {
+    pub strategy: Strategy,
+    pub joined_at: i64,
+    /// Next claim (and spend) time
+    pub next_action: i64,
+    /// Index of the level 1 referrer
+    pub referrer: Option<usize>,
+    /// Farm level, 1-based
+    pub level: u8,
+    pub capacity: u8,
+    pub packs_purchased: u32,
+    /// Grow power of each planted seed, strongest first, at most `capacity`
+    pub planted: Vec<u64>,
+    /// Unplanted seeds worth keeping for a later capacity upgrade, strongest first
+    pub stored: Vec<u64>,
+    pub grow_power: u64,
+    pub reward_debt: u128,
+    pub pending_farming_rewards: u64,
+    pub pending_referral_rewards: u64,
+    /// Wallet WEED balance
+    pub weed: u64,
+    pub weed_claimed: u64,
+    pub weed_burned: u64,
+    /// Lamports paid for the farm space and market WEED
+    pub sol_spent: u64,
+    /// Lamports received from market sales
+    pub sol_received: u64,
+    /// Lamports the agent may still spend buying WEED (whales only)
+    pub market_budget: u64,
+}
+
+impl Agent {
+    /// Agent that just bought a farm space with the starter seed planted
+    pub fn new(strategy: Strategy, joined_at: i64, capacity: u8) -> Self {
+        Self {
+            strategy,
+            joined_at,
+            next_action: joined_at,
+            referrer: None,
+            level: 1,
+            capacity,
+            packs_purchased: 0,
+            planted: Vec::new(),
+            stored: Vec::new(),
+            grow_power: 0,
+            reward_debt: 0,
+            pending_farming_rewards: 0,
+            pending_referral_rewards: 0,
+            weed: 0,
+            weed_claimed: 0,
+            weed_burned: 0,
+            sol_spent: 0,
+            sol_received: 0,
+            market_budget: 0,
+        }
+    }
+    
+    /// Farming reward accrued so far, including rewards checkpointed by grow power changes
+    pub fn farming_reward(&self, acc_reward_per_grow_power: u128) -> Result<u64> {
+        let accrued = calculate_pending_reward(self.grow_power, acc_reward_per_grow_power, self.reward_debt)?;
+        Ok(self.pending_farming_rewards.saturating_add(accrued))
+    }
+    
+    /// Checkpoint the accrued reward, then switch to `grow_power` (as `settle_pending_farming_rewards`)
+    /// Returns the previous grow power
+    pub fn set_grow_power(&mut self, grow_power: u64, acc_reward_per_grow_power: u128) -> Result<u64> {
+        self.pending_farming_rewards = self.farming_reward(acc_reward_per_grow_power)?;
+        let previous = self.grow_power;
+        self.grow_power = grow_power;
+        self.reward_debt = calculate_reward_debt(grow_power, acc_reward_per_grow_power)?;
+        Ok(previous)
+    }
+    
+    /// Plant the strongest seeds the farm can hold; planted and stored seeds are swapped freely
+    /// `keep` bounds storage to the seeds that could ever be planted
+    pub fn replant(&mut self, keep: usize) {
+        let mut seeds = std::mem::take(&mut self.planted);
+        seeds.append(&mut self.stored);
+        seeds.sort_unstable_by(|a, b| b.cmp(a));
+        seeds.truncate(keep.max(self.capacity as usize));
+        self.stored = seeds.split_off((self.capacity as usize).min(seeds.len()));
+        self.planted = seeds;
+    }
+}
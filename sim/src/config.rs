@@ -0,0 +1,234 @@
+//! Simulation parameters
+//! Defaults are the program's constants; the values we tune by spreadsheet can all be overridden
+
+use crate::agent::{Strategy, SECONDS_PER_DAY};
+use anyhow::{anyhow, bail, Context, Result};
+use farm_game::constants::{
+    DEFAULT_BASE_RATE, DEFAULT_HALVING_INTERVAL, FARM_CAPACITIES, FARM_SPACE_COST_SOL, FARM_UPGRADE_THRESHOLDS,
+    SEED_PACK_COST,
+};
+use farm_game::state::ProbabilityTable;
+use farm_game::validation::{admin_validation, economic_validation, game_validation};
+use serde::Deserialize;
+use std::path::Path;
+
+/// Levels a FarmSpace can reach on-chain (`FarmSpace::auto_upgrade` stops at 5)
+pub const DEFAULT_FARM_LEVELS: usize = 5;
+
+#[derive(Clone)]
+pub struct SimConfig {
+    /// PRNG seed; the same config and seed always produce the same output
+    pub seed: u64,
+    pub agents: u32,
+    /// Agents join evenly over this many days from launch
+    pub join_days: u32,
+    /// Simulated game time in days
+    pub days: u32,
+    /// Seconds per step; must divide a day
+    pub step_seconds: i64,
+    /// Emission per second in WEED base units
+    pub base_rate: u64,
+    pub halving_interval: i64,
+    pub seed_pack_cost: u64,
+    /// Farm space price in lamports
+    pub farm_space_cost: u64,
+    pub table: ProbabilityTable,
+    /// Capacity of each farm level
+    pub farm_capacities: Vec<u8>,
+    /// Cumulative pack purchases needed for each farm level
+    pub upgrade_thresholds: Vec<u32>,
+    /// Relative share of each strategy (in `Strategy::ALL` order) among joining agents
+    pub strategy_weights: [u32; 5],
+    /// Chance, in basis points, that a joining agent was invited by a referrer agent
+    pub referral_rate_bps: u16,
+    /// Market price in lamports per whole WEED
+    pub weed_price: u64,
+    /// Lamports each whale spends buying WEED on the market
+    pub whale_budget: u64,
+}
+
+impl Default for SimConfig {
+    fn default() -> Self {
+        Self {
+            seed: 1,
+            agents: 2_000,
+            join_days: 30,
+            days: 180,
+            step_seconds: 3_600,
+            base_rate: DEFAULT_BASE_RATE,
+            halving_interval: DEFAULT_HALVING_INTERVAL,
+            seed_pack_cost: SEED_PACK_COST,
+            farm_space_cost: FARM_SPACE_COST_SOL,
+            table: ProbabilityTable::init_standard_table(),
+            farm_capacities: FARM_CAPACITIES[..DEFAULT_FARM_LEVELS].to_vec(),
+            upgrade_thresholds: FARM_UPGRADE_THRESHOLDS[..DEFAULT_FARM_LEVELS].to_vec(),
+            strategy_weights: [20, 20, 40, 5, 15],
+            referral_rate_bps: 5_000,
+            weed_price: 1_000_000,
+            whale_budget: 50_000_000_000,
+        }
+    }
+}
+
+/// Convert a program validation error into an error naming the rejected parameter
+fn check<T>(result: anchor_lang::Result<T>, input: &str) -> Result<T> {
+    result.map_err(|error| anyhow!("invalid {}: {}", input, error))
+}
+
+impl SimConfig {
+    /// Reject parameters the program itself would refuse, plus a few the simulation needs
+    pub fn validate(&self) -> Result<()> {
+        check(
+            economic_validation::validate_halving_config(self.base_rate, self.halving_interval),
+            "base rate or halving interval",
+        )?;
+        let table = &self.table;
+        check(
+            admin_validation::validate_probability_table_update(
+                table.seed_count,
+                &table.grow_powers,
+                &table.probability_thresholds,
+                &table.probability_percentages,
+            ),
+            "probability table",
+        )?;
+        check(
+            game_validation::validate_farm_level_config_update(
+                u8::try_from(self.farm_capacities.len()).unwrap_or(u8::MAX),
+                &self.farm_capacities,
+                &self.upgrade_thresholds,
+                None,
+            ),
+            "farm levels",
+        )?;
+        
+        if self.agents == 0 || self.days == 0 {
+            bail!("agents and days must be positive");
+        }
+        if self.step_seconds <= 0 || SECONDS_PER_DAY % self.step_seconds != 0 {
+            bail!("step seconds must divide a day, got {}", self.step_seconds);
+        }
+        if self.seed_pack_cost == 0 || self.weed_price == 0 {
+            bail!("seed pack cost and WEED price must be positive");
+        }
+        if self.strategy_weights.iter().all(|&weight| weight == 0) {
+            bail!("at least one strategy needs a positive weight");
+        }
+        if self.referral_rate_bps > 10000 {
+            bail!("referral rate is more than 10000 basis points");
+        }
+        Ok(())
+    }
+    
+    pub fn strategy_weight(&self, strategy: Strategy) -> u32 {
+        let index = Strategy::ALL.iter().position(|&s| s == strategy).unwrap_or_default();
+        self.strategy_weights[index]
+    }
+}
+
+/// Probability table in the operator CLI's file format, so a candidate table can be
+/// simulated first and then queued unchanged with `update-probability-table`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ProbabilityTableFile {
+    version: u32,
+    name: String,
+    grow_powers: Vec<u64>,
+    probability_thresholds: Vec<u16>,
+    probability_percentages: Vec<f32>,
+    expected_value: u64,
+}
+
+/// `standard`, `enhanced`, or the path of a .toml / .json table file
+pub fn load_table(spec: &str) -> Result<ProbabilityTable> {
+    match spec {
+        "standard" => return Ok(ProbabilityTable::init_standard_table()),
+        "enhanced" => return Ok(ProbabilityTable::init_enhanced_table()),
+        _ => {}
+    }
+    
+    let path = Path::new(spec);
+    let contents = std::fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let file: ProbabilityTableFile = match path.extension().and_then(|extension| extension.to_str()) {
+        Some("toml") => toml::from_str(&contents).context("invalid TOML probability table")?,
+        Some("json") => serde_json::from_str(&contents).context("invalid JSON probability table")?,
+        _ => bail!("probability table must be standard, enhanced or a .toml/.json file: {}", spec),
+    };
+    table_from_file(file)
+}
+
+fn table_from_file(file: ProbabilityTableFile) -> Result<ProbabilityTable> {
+    let count = file.grow_powers.len();
+    if count == 0 || count > 16 {
+        bail!("probability table must have 1-16 seeds, got {}", count);
+    }
+    if file.probability_thresholds.len() != count || file.probability_percentages.len() != count {
+        bail!("grow_powers, probability_thresholds and probability_percentages must have the same length");
+    }
+    
+    let mut table = ProbabilityTable::init_standard_table();
+    table.version = file.version;
+    table.seed_count = count as u8;
+    table.revealed_seed_count = count as u8;
+    table.grow_powers = [0; 16];
+    table.probability_thresholds = [0; 16];
+    table.probability_percentages = [0.0; 16];
+    table.grow_powers[..count].copy_from_slice(&file.grow_powers);
+    table.probability_thresholds[..count].copy_from_slice(&file.probability_thresholds);
+    table.probability_percentages[..count].copy_from_slice(&file.probability_percentages);
+    table.expected_value = file.expected_value;
+    table.name = [0; 32];
+    let name = file.name.as_bytes();
+    table.name[..name.len().min(32)].copy_from_slice(&name[..name.len().min(32)]);
+    Ok(table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_config_validation() {
+        assert!(SimConfig::default().validate().is_ok());
+        
+        let mut config = SimConfig::default();
+        config.table.probability_thresholds[7] = 9999;
+        assert!(config.validate().is_err());
+        
+        let config = SimConfig {
+            upgrade_thresholds: vec![0, 30, 30, 300, 500],
+            ..SimConfig::default()
+        };
+        assert!(config.validate().is_err());
+        
+        let config = SimConfig {
+            step_seconds: 7_000,
+            ..SimConfig::default()
+        };
+        assert!(config.validate().is_err());
+    }
+    
+    #[test]
+    fn test_table_file() {
+        let file: ProbabilityTableFile = toml::from_str(
+            r#"
+version = 2
+name = "SpringEvent"
+grow_powers = [100, 180, 420]
+probability_thresholds = [5000, 8000, 10000]
+probability_percentages = [50.0, 30.0, 20.0]
+expected_value = 188
+"#,
+        )
+        .unwrap();
+        let table = table_from_file(file).unwrap();
+        assert_eq!(table.seed_count, 3);
+        assert_eq!(table.get_active_grow_powers(), &[100, 180, 420]);
+        
+        let config = SimConfig {
+            table,
+            ..SimConfig::default()
+        };
+        assert!(config.validate().is_ok());
+    }
+}
@@ -0,0 +1,14 @@
+//! Deterministic economic simulator for the farm_game program
+//! Runs thousands of agents buying packs, planting, claiming and referring over months of
+//! game time with the program's own emission, accumulator, referral and seed draw math
+
+pub mod agent;
+pub mod config;
+pub mod report;
+pub mod rng;
+pub mod world;
+
+pub use agent::{Agent, Strategy};
+pub use config::SimConfig;
+pub use report::{Report, StrategyRow, TimeseriesRow};
+pub use world::Simulation;
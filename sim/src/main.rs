@@ -0,0 +1,141 @@
+//! farm-game-sim: run the economic simulation and write CSV time series
+
+use anyhow::{bail, Result};
+use clap::Parser;
+use farm_game_sim::{config, SimConfig, Simulation};
+use std::path::PathBuf;
+
+#[derive(Debug, Parser)]
+#[command(name = "farm-game-sim", version, about = "Deterministic farm_game economy simulation")]
+struct Args {
+    /// Directory for timeseries.csv and strategies.csv
+    #[arg(long, default_value = "sim-out")]
+    out: PathBuf,
+    #[arg(long, default_value_t = SimConfig::default().seed)]
+    seed: u64,
+    #[arg(long, default_value_t = SimConfig::default().agents)]
+    agents: u32,
+    /// Days over which agents join
+    #[arg(long, default_value_t = SimConfig::default().join_days)]
+    join_days: u32,
+    /// Simulated game time in days
+    #[arg(long, default_value_t = SimConfig::default().days)]
+    days: u32,
+    /// Seconds per step (must divide a day)
+    #[arg(long, default_value_t = SimConfig::default().step_seconds)]
+    step_seconds: i64,
+    /// Emission per second in WEED base units
+    #[arg(long, default_value_t = SimConfig::default().base_rate)]
+    base_rate: u64,
+    #[arg(long, default_value_t = SimConfig::default().halving_interval)]
+    halving_interval: i64,
+    /// Seed pack price in WEED base units
+    #[arg(long, default_value_t = SimConfig::default().seed_pack_cost)]
+    seed_pack_cost: u64,
+    /// Farm space price in lamports
+    #[arg(long, default_value_t = SimConfig::default().farm_space_cost)]
+    farm_space_cost: u64,
+    /// standard, enhanced, or a .toml/.json table in the farm-game-cli format
+    #[arg(long, default_value = "standard")]
+    table: String,
+    /// Capacity of each farm level
+    #[arg(long, value_delimiter = ',')]
+    capacities: Option<Vec<u8>>,
+    /// Cumulative pack purchases needed for each farm level
+    #[arg(long, value_delimiter = ',')]
+    thresholds: Option<Vec<u32>>,
+    /// Strategy weights: hodler,seller,compounder,whale,referrer
+    #[arg(long, value_delimiter = ',')]
+    weights: Option<Vec<u32>>,
+    /// Chance in basis points that a joining agent was invited by a referrer
+    #[arg(long, default_value_t = SimConfig::default().referral_rate_bps)]
+    referral_rate_bps: u16,
+    /// Market price in lamports per whole WEED
+    #[arg(long, default_value_t = SimConfig::default().weed_price)]
+    weed_price: u64,
+    /// Lamports each whale spends buying WEED on the market
+    #[arg(long, default_value_t = SimConfig::default().whale_budget)]
+    whale_budget: u64,
+}
+
+impl Args {
+    fn into_config(self) -> Result<SimConfig> {
+        let defaults = SimConfig::default();
+        let mut strategy_weights = defaults.strategy_weights;
+        if let Some(weights) = &self.weights {
+            if weights.len() != strategy_weights.len() {
+                bail!("--weights needs one weight per strategy (hodler,seller,compounder,whale,referrer)");
+            }
+            strategy_weights.copy_from_slice(weights);
+        }
+        Ok(SimConfig {
+            seed: self.seed,
+            agents: self.agents,
+            join_days: self.join_days,
+            days: self.days,
+            step_seconds: self.step_seconds,
+            base_rate: self.base_rate,
+            halving_interval: self.halving_interval,
+            seed_pack_cost: self.seed_pack_cost,
+            farm_space_cost: self.farm_space_cost,
+            table: config::load_table(&self.table)?,
+            farm_capacities: self.capacities.unwrap_or(defaults.farm_capacities),
+            upgrade_thresholds: self.thresholds.unwrap_or(defaults.upgrade_thresholds),
+            strategy_weights,
+            referral_rate_bps: self.referral_rate_bps,
+            weed_price: self.weed_price,
+            whale_budget: self.whale_budget,
+        })
+    }
+}
+
+fn run(args: Args) -> Result<()> {
+    let out = args.out.clone();
+    let report = Simulation::new(args.into_config()?)?.run()?;
+    report.write_csv(&out)?;
+    
+    if let Some(last) = report.timeseries.last() {
+        println!(
+            "day {}: minted {}, burned {}, {} agents, total grow power {}",
+            last.day, last.minted, last.burned, last.agents, last.total_grow_power
+        );
+    }
+    println!("wrote {} and {}", out.join("timeseries.csv").display(), out.join("strategies.csv").display());
+    Ok(())
+}
+
+fn main() {
+    if let Err(error) = run(Args::parse()) {
+        eprintln!("error: {:#}", error);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+    
+    #[test]
+    fn test_sim_arguments() {
+        Args::command().debug_assert();
+        
+        let args = Args::try_parse_from([
+            "farm-game-sim",
+            "--capacities",
+            "4,6,10",
+            "--thresholds",
+            "0,20,80",
+            "--weights",
+            "0,0,1,0,0",
+        ])
+        .unwrap();
+        let config = args.into_config().unwrap();
+        assert_eq!(config.farm_capacities, vec![4, 6, 10]);
+        assert_eq!(config.strategy_weights, [0, 0, 1, 0, 0]);
+        config.validate().unwrap();
+        
+        let short_weights = Args::try_parse_from(["farm-game-sim", "--weights", "1,2"]).unwrap();
+        assert!(short_weights.into_config().is_err());
+    }
+}
@@ -0,0 +1,145 @@
+//! Daily samples and CSV output
+//! `timeseries.csv` has one row per day; `strategies.csv` has one row per day and strategy
+
+use anyhow::{Context, Result};
+use farm_game::economics::calculate_grow_power_share_bps;
+use serde::Serialize;
+use std::path::Path;
+
+/// Economy-wide state at the end of a day (amounts in WEED base units)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TimeseriesRow {
+    pub day: u32,
+    pub emission_rate: u64,
+    pub minted: u64,
+    pub burned: u64,
+    /// Minted minus burned
+    pub circulating: u64,
+    pub remaining_supply: u64,
+    /// Claimed after the supply ran out (the fee pool is not simulated)
+    pub unpaid: u64,
+    pub packs_purchased: u64,
+    pub agents: u32,
+    pub total_grow_power: u64,
+    pub grow_power_p50: u64,
+    pub grow_power_p90: u64,
+    pub grow_power_p99: u64,
+    pub grow_power_max: u64,
+    /// Share of total grow power held by the top 1% of agents
+    pub top1_share_bps: u64,
+    pub gini_bps: u64,
+}
+
+/// Totals for one strategy at the end of a day
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StrategyRow {
+    pub day: u32,
+    pub strategy: &'static str,
+    pub agents: u32,
+    pub avg_grow_power: u64,
+    pub avg_farm_level: f64,
+    pub weed_claimed: u64,
+    pub weed_burned: u64,
+    pub weed_held: u64,
+    pub sol_spent: u64,
+    pub sol_received: u64,
+    /// (SOL received + held WEED at the market price) / SOL spent - 1
+    pub roi: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Report {
+    pub timeseries: Vec<TimeseriesRow>,
+    pub strategies: Vec<StrategyRow>,
+}
+
+impl Report {
+    /// Write `timeseries.csv` and `strategies.csv` into `dir`
+    pub fn write_csv(&self, dir: &Path) -> Result<()> {
+        std::fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+        write_rows(&dir.join("timeseries.csv"), &self.timeseries)?;
+        write_rows(&dir.join("strategies.csv"), &self.strategies)
+    }
+}
+
+fn write_rows<T: Serialize>(path: &Path, rows: &[T]) -> Result<()> {
+    let mut writer = csv::Writer::from_path(path).with_context(|| format!("failed to create {}", path.display()))?;
+    for row in rows {
+        writer.serialize(row)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Grow power distribution across agents
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Distribution {
+    pub total: u64,
+    pub p50: u64,
+    pub p90: u64,
+    pub p99: u64,
+    pub max: u64,
+    pub top1_share_bps: u64,
+    pub gini_bps: u64,
+}
+
+impl Distribution {
+    pub fn from_grow_powers(mut grow_powers: Vec<u64>) -> Self {
+        if grow_powers.is_empty() {
+            return Self::default();
+        }
+        grow_powers.sort_unstable();
+        let count = grow_powers.len();
+        let total: u64 = grow_powers.iter().sum();
+        let percentile = |pct: usize| grow_powers[(count * pct / 100).min(count - 1)];
+        
+        let top_count = count.div_ceil(100);
+        let top: u64 = grow_powers[count - top_count..].iter().sum();
+        
+        // Gini = sum((2i - n - 1) * x_i) / (n * sum(x)) over ascending x, i from 1
+        let weighted: i128 = grow_powers
+            .iter()
+            .enumerate()
+            .map(|(i, &gp)| (2 * (i as i128 + 1) - count as i128 - 1) * gp as i128)
+            .sum();
+        let gini_bps = if total == 0 {
+            0
+        } else {
+            (weighted * 10000 / (count as i128 * total as i128)) as u64
+        };
+        
+        Self {
+            total,
+            p50: percentile(50),
+            p90: percentile(90),
+            p99: percentile(99),
+            max: grow_powers[count - 1],
+            top1_share_bps: calculate_grow_power_share_bps(top, total),
+            gini_bps,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_distribution() {
+        let equal = Distribution::from_grow_powers(vec![100; 50]);
+        assert_eq!(equal.total, 5000);
+        assert_eq!(equal.gini_bps, 0);
+        assert_eq!(equal.top1_share_bps, 200);
+        
+        // One agent holds everything: Gini approaches 1
+        let mut skewed = vec![0; 99];
+        skewed.push(1000);
+        let skewed = Distribution::from_grow_powers(skewed);
+        assert_eq!(skewed.gini_bps, 9900);
+        assert_eq!(skewed.top1_share_bps, 10000);
+        assert_eq!(skewed.p50, 0);
+        assert_eq!(skewed.max, 1000);
+        
+        assert_eq!(Distribution::from_grow_powers(vec![]), Distribution::default());
+    }
+}
@@ -0,0 +1,34 @@
+//! Seeded PRNG (SplitMix64)
+//! Every random choice in a run comes from one stream, so a seed fully determines the output
+
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+    
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+    
+    /// Uniform value in `0..bound` (0 when `bound` is 0)
+    pub fn below(&mut self, bound: u64) -> u64 {
+        if bound == 0 {
+            return 0;
+        }
+        ((self.next_u64() as u128 * bound as u128) >> 64) as u64
+    }
+    
+    /// True with probability `bps / 10000`
+    pub fn chance_bps(&mut self, bps: u16) -> bool {
+        self.below(10000) < bps as u64
+    }
+}
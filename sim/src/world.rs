@@ -0,0 +1,404 @@
+//! Simulation loop
+//! Each step advances the reward accumulator over the emission schedule, lets new agents join,
+//! then runs every agent whose claim is due: claim with the referral split, then spend
+
+use crate::agent::{Agent, Strategy, SECONDS_PER_DAY};
+use crate::config::SimConfig;
+use crate::report::{Distribution, Report, StrategyRow, TimeseriesRow};
+use crate::rng::Rng;
+use anyhow::{anyhow, Result};
+use farm_game::constants::{MAX_SEED_PACK_QUANTITY, TOTAL_WEED_SUPPLY};
+use farm_game::economics::{
+    calculate_acc_reward_per_grow_power, calculate_emission, calculate_referral_rewards, current_emission_rate,
+    halving_emission_segments,
+};
+use farm_game::instructions::seeds::derive_seed_randomness;
+use farm_game::state::{EmissionSegment, SeedType};
+use farm_game::utils::calculate_reward_percentages;
+use farm_game::validation::economic_validation::{cap_to_remaining_supply, get_remaining_supply};
+
+/// WEED base units per whole WEED (6 decimals)
+const WEED_UNIT: u128 = 1_000_000;
+
+/// Map program errors into anyhow errors
+fn program<T>(result: anchor_lang::Result<T>) -> Result<T> {
+    result.map_err(|error| anyhow!("program math failed: {}", error))
+}
+
+pub struct Simulation {
+    config: SimConfig,
+    rng: Rng,
+    segments: Vec<EmissionSegment>,
+    /// Seconds since launch
+    pub time: i64,
+    acc_reward_per_grow_power: u128,
+    last_reward_time: i64,
+    /// Emission credited through the accumulator, capped at TOTAL_WEED_SUPPLY
+    total_emission_accrued: u64,
+    pub total_grow_power: u64,
+    pub total_minted: u64,
+    pub total_burned: u64,
+    pub unpaid: u64,
+    pub packs_purchased: u64,
+    /// WEED sold by sellers and not yet bought by whales
+    pub market_weed: u64,
+    pub agents: Vec<Agent>,
+    /// Agents that have not joined yet, in join order
+    joins_remaining: u32,
+    /// Indices of joined referrer agents
+    referrers: Vec<usize>,
+}
+
+impl Simulation {
+    pub fn new(config: SimConfig) -> Result<Self> {
+        config.validate()?;
+        // Launch at t = 0 with the first halving one interval later (as initialize_config)
+        let segments = program(halving_emission_segments(
+            config.base_rate,
+            config.halving_interval,
+            config.halving_interval,
+            0,
+        ))?;
+        Ok(Self {
+            rng: Rng::new(config.seed),
+            segments,
+            time: 0,
+            acc_reward_per_grow_power: 0,
+            last_reward_time: 0,
+            total_emission_accrued: 0,
+            total_grow_power: 0,
+            total_minted: 0,
+            total_burned: 0,
+            unpaid: 0,
+            packs_purchased: 0,
+            market_weed: 0,
+            agents: Vec::with_capacity(config.agents as usize),
+            joins_remaining: config.agents,
+            referrers: Vec::new(),
+            config,
+        })
+    }
+    
+    /// Run for the configured number of days, sampling at the end of each day
+    pub fn run(mut self) -> Result<Report> {
+        let mut report = Report::default();
+        let steps_per_day = SECONDS_PER_DAY / self.config.step_seconds;
+        for day in 1..=self.config.days {
+            for _ in 0..steps_per_day {
+                self.step()?;
+            }
+            self.sample(day, &mut report);
+        }
+        Ok(report)
+    }
+    
+    pub fn step(&mut self) -> Result<()> {
+        self.time += self.config.step_seconds;
+        
+        // update_reward_accumulator over the step: nothing accrues without grow power or past the supply cap
+        let emission = if self.total_grow_power == 0 {
+            0
+        } else {
+            program(calculate_emission(&self.segments, self.last_reward_time, self.time))?
+                .min(TOTAL_WEED_SUPPLY - self.total_emission_accrued)
+        };
+        self.total_emission_accrued += emission;
+        self.acc_reward_per_grow_power = program(calculate_acc_reward_per_grow_power(
+            self.acc_reward_per_grow_power,
+            emission,
+            self.total_grow_power,
+        ))?;
+        self.last_reward_time = self.time;
+        
+        self.join_due_agents()?;
+        
+        for index in 0..self.agents.len() {
+            if self.agents[index].next_action > self.time {
+                continue;
+            }
+            self.claim(index)?;
+            self.spend(index)?;
+            let agent = &mut self.agents[index];
+            agent.next_action += agent.strategy.claim_interval();
+        }
+        Ok(())
+    }
+    
+    /// Time agent number `index` joins: evenly spread over the join window
+    fn join_time(&self, index: usize) -> i64 {
+        let window = self.config.join_days as i64 * SECONDS_PER_DAY;
+        window * index as i64 / self.config.agents as i64
+    }
+    
+    fn join_due_agents(&mut self) -> Result<()> {
+        while self.joins_remaining > 0 && self.join_time(self.agents.len()) <= self.time {
+            self.joins_remaining -= 1;
+            let strategy = self.pick_strategy();
+            let index = self.agents.len();
+            
+            // buy_farm_space: level 1 farm with the starter seed planted
+            let mut agent = Agent::new(strategy, self.time, self.config.farm_capacities[0]);
+            agent.sol_spent = self.config.farm_space_cost;
+            if strategy == Strategy::Whale {
+                agent.market_budget = self.config.whale_budget;
+            }
+            if !self.referrers.is_empty() && self.rng.chance_bps(self.config.referral_rate_bps) {
+                agent.referrer = Some(self.referrers[self.rng.below(self.referrers.len() as u64) as usize]);
+            }
+            // Spread claims over the interval instead of claiming in lockstep
+            agent.next_action = self.time + self.rng.below(strategy.claim_interval() as u64) as i64;
+            agent.planted.push(SeedType::Seed1.get_grow_power());
+            self.agents.push(agent);
+            self.apply_grow_power(index)?;
+            
+            if strategy == Strategy::Referrer {
+                self.referrers.push(index);
+            }
+        }
+        Ok(())
+    }
+    
+    fn pick_strategy(&mut self) -> Strategy {
+        let total: u32 = self.config.strategy_weights.iter().sum();
+        let mut roll = self.rng.below(total as u64) as u32;
+        for strategy in Strategy::ALL {
+            let weight = self.config.strategy_weight(strategy);
+            if roll < weight {
+                return strategy;
+            }
+            roll -= weight;
+        }
+        Strategy::Compounder
+    }
+    
+    /// Set the agent's grow power to its planted seeds, checkpointing the reward first
+    fn apply_grow_power(&mut self, index: usize) -> Result<()> {
+        let acc = self.acc_reward_per_grow_power;
+        let agent = &mut self.agents[index];
+        let grow_power = agent.planted.iter().sum();
+        let previous = program(agent.set_grow_power(grow_power, acc))?;
+        self.total_grow_power = self.total_grow_power - previous + grow_power;
+        Ok(())
+    }
+    
+    /// claim_reward_with_referral_rewards: the claimant keeps 100/90/85% of the farming reward,
+    /// referrers accrue 10%/5% of it on top, and minting stops at TOTAL_WEED_SUPPLY
+    fn claim(&mut self, index: usize) -> Result<()> {
+        let acc = self.acc_reward_per_grow_power;
+        let farming_reward = program(self.agents[index].farming_reward(acc))?;
+        let level1 = self.agents[index].referrer;
+        let level2 = level1.and_then(|referrer| self.agents[referrer].referrer);
+        
+        let (claimant_pct, _, _) = calculate_reward_percentages(level1.is_some(), level2.is_some(), false, false, false);
+        let claimant_amount = farming_reward.checked_mul(claimant_pct as u64).unwrap_or(0) / 10000;
+        
+        let agent = &mut self.agents[index];
+        let total_reward = claimant_amount + agent.pending_referral_rewards;
+        let paid = cap_to_remaining_supply(self.total_minted, total_reward);
+        self.total_minted += paid;
+        self.unpaid += total_reward - paid;
+        agent.weed += paid;
+        agent.weed_claimed += paid;
+        agent.pending_referral_rewards = 0;
+        agent.pending_farming_rewards = 0;
+        agent.reward_debt = program(farm_game::economics::calculate_reward_debt(agent.grow_power, acc))?;
+        
+        let (level1_reward, level2_reward) = program(calculate_referral_rewards(farming_reward))?;
+        if let Some(referrer) = level1 {
+            self.agents[referrer].pending_referral_rewards += level1_reward;
+        }
+        if let Some(referrer) = level2 {
+            self.agents[referrer].pending_referral_rewards += level2_reward;
+        }
+        Ok(())
+    }
+    
+    fn spend(&mut self, index: usize) -> Result<()> {
+        let price = self.config.weed_price as u128;
+        let strategy = self.agents[index].strategy;
+        
+        if strategy == Strategy::Seller {
+            let agent = &mut self.agents[index];
+            self.market_weed += agent.weed;
+            agent.sol_received += (agent.weed as u128 * price / WEED_UNIT) as u64;
+            agent.weed = 0;
+            return Ok(());
+        }
+        if strategy == Strategy::Whale {
+            let agent = &mut self.agents[index];
+            let affordable = (agent.market_budget as u128 * WEED_UNIT / price) as u64;
+            let bought = affordable.min(self.market_weed);
+            let cost = (bought as u128 * price).div_ceil(WEED_UNIT) as u64;
+            self.market_weed -= bought;
+            agent.weed += bought;
+            agent.market_budget = agent.market_budget.saturating_sub(cost);
+            agent.sol_spent += cost;
+        }
+        if !strategy.reinvests() {
+            return Ok(());
+        }
+        
+        let mut bought_any = false;
+        loop {
+            let quantity = (self.agents[index].weed / self.config.seed_pack_cost).min(MAX_SEED_PACK_QUANTITY as u64);
+            if quantity == 0 {
+                break;
+            }
+            self.purchase_and_open(index, quantity as u8);
+            bought_any = true;
+        }
+        if bought_any {
+            let keep = *self.config.farm_capacities.last().unwrap_or(&0) as usize;
+            self.agents[index].replant(keep);
+            self.apply_grow_power(index)?;
+        }
+        Ok(())
+    }
+    
+    /// purchase_seed_pack (cost burned, one farm level per purchase) followed by open_seed_pack
+    fn purchase_and_open(&mut self, index: usize, quantity: u8) {
+        let cost = self.config.seed_pack_cost * quantity as u64;
+        let base_random = self.rng.next_u64();
+        let table = &self.config.table;
+        let agent = &mut self.agents[index];
+        
+        agent.weed -= cost;
+        agent.weed_burned += cost;
+        self.total_burned += cost;
+        self.packs_purchased += quantity as u64;
+        agent.packs_purchased += quantity as u32;
+        
+        let max_level = self.config.farm_capacities.len() as u8;
+        if agent.level < max_level && agent.packs_purchased >= self.config.upgrade_thresholds[agent.level as usize] {
+            agent.level += 1;
+            agent.capacity = self.config.farm_capacities[agent.level as usize - 1];
+        }
+        
+        for i in 0..quantity {
+            let seed_type = SeedType::from_random_with_table(derive_seed_randomness(base_random, i), table);
+            agent.stored.push(table.grow_powers[seed_type as usize]);
+        }
+    }
+    
+    fn sample(&self, day: u32, report: &mut Report) {
+        let distribution = Distribution::from_grow_powers(self.agents.iter().map(|agent| agent.grow_power).collect());
+        report.timeseries.push(TimeseriesRow {
+            day,
+            emission_rate: current_emission_rate(&self.segments, self.time),
+            minted: self.total_minted,
+            burned: self.total_burned,
+            circulating: self.total_minted - self.total_burned,
+            remaining_supply: get_remaining_supply(self.total_minted),
+            unpaid: self.unpaid,
+            packs_purchased: self.packs_purchased,
+            agents: self.agents.len() as u32,
+            total_grow_power: distribution.total,
+            grow_power_p50: distribution.p50,
+            grow_power_p90: distribution.p90,
+            grow_power_p99: distribution.p99,
+            grow_power_max: distribution.max,
+            top1_share_bps: distribution.top1_share_bps,
+            gini_bps: distribution.gini_bps,
+        });
+        
+        let price = self.config.weed_price as u128;
+        for strategy in Strategy::ALL {
+            let members: Vec<&Agent> = self.agents.iter().filter(|agent| agent.strategy == strategy).collect();
+            let count = members.len() as u64;
+            let sum = |field: fn(&Agent) -> u64| members.iter().map(|agent| field(agent)).sum::<u64>();
+            let weed_held = sum(|agent| agent.weed);
+            let sol_spent = sum(|agent| agent.sol_spent);
+            let sol_received = sum(|agent| agent.sol_received);
+            let value = sol_received as f64 + (weed_held as u128 * price / WEED_UNIT) as f64;
+            
+            report.strategies.push(StrategyRow {
+                day,
+                strategy: strategy.name(),
+                agents: count as u32,
+                avg_grow_power: sum(|agent| agent.grow_power).checked_div(count).unwrap_or(0),
+                avg_farm_level: if count == 0 {
+                    0.0
+                } else {
+                    sum(|agent| agent.level as u64) as f64 / count as f64
+                },
+                weed_claimed: sum(|agent| agent.weed_claimed),
+                weed_burned: sum(|agent| agent.weed_burned),
+                weed_held,
+                sol_spent,
+                sol_received,
+                roi: if sol_spent == 0 { 0.0 } else { value / sol_spent as f64 - 1.0 },
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn small_config() -> SimConfig {
+        SimConfig {
+            agents: 200,
+            join_days: 5,
+            days: 20,
+            // Enough emission for packs to matter within a short run
+            base_rate: 2_000_000,
+            ..SimConfig::default()
+        }
+    }
+    
+    #[test]
+    fn test_same_seed_same_output() {
+        let first = Simulation::new(small_config()).unwrap().run().unwrap();
+        let second = Simulation::new(small_config()).unwrap().run().unwrap();
+        assert_eq!(first, second);
+        assert_eq!(first.timeseries.len(), 20);
+        assert_eq!(first.strategies.len(), 20 * Strategy::ALL.len());
+        
+        let other_seed = SimConfig {
+            seed: 2,
+            ..small_config()
+        };
+        assert_ne!(Simulation::new(other_seed).unwrap().run().unwrap(), first);
+    }
+    
+    #[test]
+    fn test_burn_and_grow_power_accounting() {
+        let config = small_config();
+        let pack_cost = config.seed_pack_cost;
+        let mut sim = Simulation::new(config).unwrap();
+        for _ in 0..20 * 24 {
+            sim.step().unwrap();
+        }
+        
+        assert!(sim.packs_purchased > 0);
+        assert_eq!(sim.total_burned, sim.packs_purchased * pack_cost);
+        assert_eq!(sim.total_burned, sim.agents.iter().map(|agent| agent.weed_burned).sum::<u64>());
+        assert_eq!(sim.total_grow_power, sim.agents.iter().map(|agent| agent.grow_power).sum::<u64>());
+        for agent in &sim.agents {
+            assert!(agent.planted.len() <= agent.capacity as usize);
+            assert_eq!(agent.grow_power, agent.planted.iter().sum::<u64>());
+        }
+        // Claimed WEED is either held, burned, or sold to the market and possibly bought back
+        let held: u64 = sim.agents.iter().map(|agent| agent.weed).sum();
+        assert_eq!(sim.total_minted, held + sim.total_burned + sim.market_weed);
+    }
+    
+    #[test]
+    fn test_supply_cap() {
+        // A rate that would emit several times TOTAL_WEED_SUPPLY in the first week
+        let config = SimConfig {
+            base_rate: TOTAL_WEED_SUPPLY / 86_400,
+            days: 10,
+            ..small_config()
+        };
+        let report = Simulation::new(config).unwrap().run().unwrap();
+        let last = report.timeseries.last().unwrap();
+        // Emission stops at the cap, so every accrued reward is still mintable;
+        // only the accumulator's rounding dust (under 0.001 WEED) is never minted
+        assert!(last.remaining_supply < 1_000);
+        assert_eq!(last.minted + last.remaining_supply, TOTAL_WEED_SUPPLY);
+        assert_eq!(last.unpaid, 0);
+        assert!(report.timeseries.iter().all(|row| row.minted <= TOTAL_WEED_SUPPLY));
+    }
+}
@@ -155,6 +155,18 @@ export class TestEnvironment {
       .signers([this.accounts.admin])
       .rpc();
 
+    // Initialize emission schedule (converted from the halving config)
+    await this.program.methods
+      .initializeEmissionSchedule()
+      .accountsPartial({
+        config: this.pdas.configPda,
+        globalStats: this.pdas.globalStatsPda,
+        admin: this.accounts.admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([this.accounts.admin])
+      .rpc();
+
     // Initialize fee pool
     await this.program.methods
       .initializeFeePool(this.accounts.treasury.publicKey)
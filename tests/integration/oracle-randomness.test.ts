@@ -0,0 +1,283 @@
+// Oracle randomness flow against the Switchboard stand-in (switchboard_stub)
+// Needs a localnet build so the timelock delay is short:
+//   anchor test -- --features localnet
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import {
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_SLOT_HASHES_PUBKEY,
+  Transaction,
+} from "@solana/web3.js";
+import {
+  TOKEN_2022_PROGRAM_ID,
+  createAssociatedTokenAccountIdempotentInstruction,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import { FarmGame } from "../../target/types/farm_game";
+import { SwitchboardStub } from "../../target/types/switchboard_stub";
+
+const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
+
+// 100 WEED per second, so one farm earns a seed pack within a few seconds
+const TEST_BASE_RATE = new BN(100_000_000);
+const MAX_VRF_FEE = new BN(0.01 * LAMPORTS_PER_SOL);
+
+describe("Oracle randomness (switchboard stub)", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.FarmGame as Program<FarmGame>;
+  const stub = anchor.workspace.SwitchboardStub as Program<SwitchboardStub>;
+  const admin = provider.wallet.publicKey;
+
+  const pda = (...seeds: (Buffer | Uint8Array)[]) =>
+    PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+  const u64 = (value: BN | number) => new BN(value).toArrayLike(Buffer, "le", 8);
+
+  const configPda = pda(Buffer.from("config"));
+  const globalStatsPda = pda(Buffer.from("global_stats"));
+  const emissionSchedulePda = pda(Buffer.from("emission_schedule"));
+  const feePoolPda = pda(Buffer.from("fee_pool"));
+  const rewardMintPda = pda(Buffer.from("reward_mint"));
+  const mintAuthorityPda = pda(Buffer.from("mint_authority"));
+  const probabilityTablePda = pda(Buffer.from("probability_table"));
+  const farmLevelConfigPda = pda(Buffer.from("farm_level_config"));
+
+  const user = Keypair.generate();
+  const userStatePda = pda(Buffer.from("user"), user.publicKey.toBuffer());
+  const farmSpacePda = pda(Buffer.from("farm_space"), user.publicKey.toBuffer());
+  const seedStoragePda = pda(Buffer.from("seed_storage"), user.publicKey.toBuffer());
+  const userTokenAccount = getAssociatedTokenAddressSync(
+    rewardMintPda,
+    user.publicKey,
+    false,
+    TOKEN_2022_PROGRAM_ID
+  );
+  const randomness = Keypair.generate();
+
+  // Queue a timelocked config change, wait out the delay and execute it
+  const applyConfigChange = async (change: any, touchesEmission: boolean) => {
+    const config = await program.account.config.fetch(configPda);
+    const pendingChange = pda(Buffer.from("pending_config_change"), u64(config.configChangeCounter));
+    await program.methods
+      .queueConfigChange(change)
+      .accountsPartial({ config: configPda, pendingChange, roleRegistry: null, authority: admin })
+      .rpc();
+    await sleep((config.configChangeDelay.toNumber() + 2) * 1000);
+    await program.methods
+      .executeConfigChange()
+      .accountsPartial({
+        config: configPda,
+        pendingChange,
+        probabilityTable: null,
+        emissionSchedule: touchesEmission ? emissionSchedulePda : null,
+        globalStats: touchesEmission ? globalStatsPda : null,
+        roleRegistry: null,
+        authority: admin,
+      })
+      .rpc();
+  };
+
+  before(async () => {
+    if (!(await program.account.config.fetchNullable(configPda))) {
+      await program.methods
+        .initializeConfig(TEST_BASE_RATE, null, admin, null)
+        .accountsPartial({ config: configPda, admin })
+        .rpc();
+    }
+    if (!(await provider.connection.getAccountInfo(rewardMintPda))) {
+      await program.methods
+        .createRewardMint()
+        .accountsPartial({
+          rewardMint: rewardMintPda,
+          mintAuthority: mintAuthorityPda,
+          transferFeeConfigAuthority: mintAuthorityPda,
+          withdrawWithheldAuthority: mintAuthorityPda,
+          admin,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .rpc();
+    }
+    if (!(await program.account.globalStats.fetchNullable(globalStatsPda))) {
+      await program.methods
+        .initializeGlobalStats()
+        .accountsPartial({ globalStats: globalStatsPda, admin })
+        .rpc();
+    }
+    if (!(await program.account.emissionSchedule.fetchNullable(emissionSchedulePda))) {
+      await program.methods
+        .initializeEmissionSchedule()
+        .accountsPartial({ emissionSchedule: emissionSchedulePda, config: configPda, globalStats: globalStatsPda, admin })
+        .rpc();
+    }
+    if (!(await program.account.feePool.fetchNullable(feePoolPda))) {
+      await program.methods
+        .initializeFeePool(admin)
+        .accountsPartial({ feePool: feePoolPda, config: configPda, admin })
+        .rpc();
+    }
+    if (!(await program.account.probabilityTable.fetchNullable(probabilityTablePda))) {
+      await program.methods
+        .initializeProbabilityTable()
+        .accountsPartial({ probabilityTable: probabilityTablePda, config: configPda, admin })
+        .rpc();
+    }
+    if (!(await program.account.farmLevelConfig.fetchNullable(farmLevelConfigPda))) {
+      await program.methods
+        .initializeFarmLevelConfig()
+        .accountsPartial({ farmLevelConfig: farmLevelConfigPda, config: configPda, admin })
+        .rpc();
+    }
+
+    // Point oracle randomness at the stub (and make sure a farm earns a pack quickly)
+    const config = await program.account.config.fetch(configPda);
+    if (config.baseRate.lt(TEST_BASE_RATE)) {
+      await applyConfigChange({ baseRate: { newBaseRate: TEST_BASE_RATE } }, true);
+    }
+    if (!config.oracleProgram.equals(stub.programId)) {
+      await applyConfigChange({ oracleProgram: { programId: stub.programId } }, false);
+    }
+    if (!("oracle" in config.randomnessSource)) {
+      await applyConfigChange({ randomnessSource: { source: { oracle: {} } } }, false);
+    }
+
+    // User with a farm and enough WEED for one pack
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(user.publicKey, 2 * LAMPORTS_PER_SOL),
+      "confirmed"
+    );
+    await program.methods
+      .initUser(null)
+      .accountsPartial({ userState: userStatePda, config: configPda, roleRegistry: null, user: user.publicKey, admin })
+      .signers([user])
+      .rpc();
+    await program.methods
+      .buyFarmSpace()
+      .accountsPartial({
+        userState: userStatePda,
+        farmSpace: farmSpacePda,
+        initialSeed: pda(Buffer.from("seed"), user.publicKey.toBuffer(), u64(0)),
+        config: configPda,
+        globalStats: globalStatsPda,
+        emissionSchedule: emissionSchedulePda,
+        treasury: config.treasury,
+        user: user.publicKey,
+      })
+      .signers([user])
+      .rpc();
+    await sleep(5000);
+    await program.methods
+      .claimRewardWithReferralRewards()
+      .accountsPartial({
+        userState: userStatePda,
+        config: configPda,
+        globalStats: globalStatsPda,
+        emissionSchedule: emissionSchedulePda,
+        rewardMint: rewardMintPda,
+        mintAuthority: mintAuthorityPda,
+        userTokenAccount,
+        user: user.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        feePool: null,
+        feePoolTokenAccount: null,
+        level1ReferrerState: null,
+        level1Referrer: null,
+        level2ReferrerState: null,
+        level2Referrer: null,
+      })
+      .preInstructions([
+        createAssociatedTokenAccountIdempotentInstruction(
+          user.publicKey,
+          userTokenAccount,
+          user.publicKey,
+          rewardMintPda,
+          TOKEN_2022_PROGRAM_ID
+        ),
+      ])
+      .signers([user])
+      .rpc();
+    await program.methods
+      .initializeSeedStorage()
+      .accountsPartial({ seedStorage: seedStoragePda, user: user.publicKey })
+      .signers([user])
+      .rpc();
+
+    await stub.methods
+      .initializeRandomness()
+      .accountsPartial({ randomness: randomness.publicKey, authority: user.publicKey })
+      .signers([user, randomness])
+      .rpc();
+  });
+
+  it("commits with the purchase and opens after the stub reveals", async () => {
+    const config = await program.account.config.fetch(configPda);
+    const packId = config.seedPackCounter;
+    const seedPackPda = pda(Buffer.from("seed_pack"), user.publicKey.toBuffer(), u64(packId));
+
+    // The oracle commit must land in the same transaction as the purchase
+    const commitIx = await stub.methods
+      .commitRandomness()
+      .accountsPartial({ randomness: randomness.publicKey, authority: user.publicKey })
+      .instruction();
+    const purchaseIx = await program.methods
+      .purchaseSeedPack(1, new BN(42), MAX_VRF_FEE)
+      .accountsPartial({
+        userState: userStatePda,
+        farmSpace: farmSpacePda,
+        config: configPda,
+        globalStats: globalStatsPda,
+        seedPack: seedPackPda,
+        rewardMint: rewardMintPda,
+        userTokenAccount,
+        feePool: feePoolPda,
+        vrfAccount: randomness.publicKey,
+        vrfPermission: null,
+        switchboardProgram: stub.programId,
+        randomnessBeacon: null,
+        user: user.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+    await provider.sendAndConfirm(new Transaction().add(commitIx, purchaseIx), [user]);
+
+    const committed = await program.account.seedPack.fetch(seedPackPda);
+    expect(committed.randomnessSource).to.have.property("oracle");
+    expect(committed.vrfAccount.equals(randomness.publicKey)).to.be.true;
+    expect(committed.isOpened).to.be.false;
+
+    await stub.methods
+      .revealRandomness(Array.from(Keypair.generate().publicKey.toBytes()))
+      .accountsPartial({ randomness: randomness.publicKey, authority: user.publicKey })
+      .signers([user])
+      .rpc();
+    // Let the committed slot pass so its hash is in SlotHashes
+    await sleep(2000);
+
+    await program.methods
+      .openSeedPack(1)
+      .accountsPartial({
+        seedPack: seedPackPda,
+        config: configPda,
+        globalStats: globalStatsPda,
+        seedStorage: seedStoragePda,
+        vrfAccount: randomness.publicKey,
+        switchboardProgram: stub.programId,
+        randomnessBeacon: null,
+        probabilityTable: probabilityTablePda,
+        slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
+        user: user.publicKey,
+      })
+      .signers([user])
+      .rpc();
+
+    const opened = await program.account.seedPack.fetch(seedPackPda);
+    expect(opened.isOpened).to.be.true;
+    expect(opened.finalRandomValue).to.not.be.null;
+    const storage = await program.account.seedStorage.fetch(seedStoragePda);
+    expect(storage.totalSeeds).to.be.greaterThan(0);
+  });
+});
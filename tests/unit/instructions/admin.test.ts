@@ -184,7 +184,6 @@ describe("Admin Instructions", () => {
 
     it("Should reject mint creation from non-admin", async () => {
       const nonAdmin = Keypair.generate();
-      const metadataAccount = Keypair.generate();
       
       // Airdrop SOL for transaction fees
       await testEnv.connection.requestAirdrop(nonAdmin.publicKey, LAMPORTS_PER_SOL);
@@ -207,12 +206,10 @@ describe("Admin Instructions", () => {
           .accountsPartial({
             rewardMint: rewardMintPda2,
             mintAuthority: mintAuthorityPda2,
-            metadataAccount: metadataAccount.publicKey,
             admin: nonAdmin.publicKey,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: anchor.web3.SystemProgram.programId,
             rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-            tokenMetadataProgram: new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"),
           })
           .signers([nonAdmin])
           .rpc();